@@ -0,0 +1,170 @@
+//! A bounded ring of timestamped log lines, for rendering an in-game console or attaching to bug reports.
+//!
+//! If a ring is registered with [`register`], then all of the logging methods from [`crate::error_ext::ErrorExt`] also get added to it.
+//!
+//! NB: Only enabled with the `console` feature
+//!
+//! ## Use
+//! ```rust
+//! use burntnail_utils::console::{LogLevel, LogRing};
+//!
+//! let mut ring = LogRing::<2>::default();
+//! ring.push(LogLevel::Info, "one");
+//! ring.push(LogLevel::Warn, "two");
+//! ring.push(LogLevel::Error, "three");
+//!
+//! assert_eq!(ring.recent(5).map(|e| e.msg.as_str()).collect::<Vec<_>>(), vec!["two", "three"]);
+//! assert_eq!(ring.filtered(LogLevel::Error).count(), 1);
+//! ```
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+    time::SystemTime,
+};
+
+pub use crate::error_ext::LogLevel;
+
+///One line in a [`LogRing`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    ///When the line was logged
+    pub time: SystemTime,
+    ///How important the line is
+    pub level: LogLevel,
+    ///The message
+    pub msg: String,
+}
+
+///Circular buffer of the last `N` [`LogEntry`]s, overwriting the oldest when full
+#[derive(Debug, Clone)]
+pub struct LogRing<const N: usize> {
+    ///The entries, oldest first
+    entries: VecDeque<LogEntry>,
+}
+
+impl<const N: usize> Default for LogRing<N> {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(N),
+        }
+    }
+}
+
+impl<const N: usize> LogRing<N> {
+    ///Adds a new line, timestamped now, overwriting the oldest line if full
+    pub fn push(&mut self, level: LogLevel, msg: impl Into<String>) {
+        if N == 0 {
+            return;
+        }
+        if self.entries.len() == N {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(LogEntry {
+            time: SystemTime::now(),
+            level,
+            msg: msg.into(),
+        });
+    }
+
+    ///Gets the newest `n` lines, oldest first
+    pub fn recent(&self, n: usize) -> impl Iterator<Item = &LogEntry> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(n))
+    }
+
+    ///Gets all of the lines which are at least as important as `min_level`, oldest first
+    pub fn filtered(&self, min_level: LogLevel) -> impl Iterator<Item = &LogEntry> {
+        self.entries.iter().filter(move |e| e.level >= min_level)
+    }
+
+    ///How many lines there currently are
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    ///Whether or not there are any lines
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+///Trait to allow [`LogRing`]s of any size to be registered globally
+trait LogSink: Send {
+    ///Adds a new line
+    fn push_line(&mut self, level: LogLevel, msg: String);
+}
+
+impl<const N: usize> LogSink for LogRing<N> {
+    fn push_line(&mut self, level: LogLevel, msg: String) {
+        self.push(level, msg);
+    }
+}
+
+///The globally registered ring, if there is one
+static GLOBAL: OnceLock<Arc<Mutex<dyn LogSink>>> = OnceLock::new();
+
+///Registers a [`LogRing`] globally, so that [`crate::error_ext::ErrorExt`] methods also log to it. Keep a clone of the [`Arc`] to read it.
+///
+///Can only be done once - returns `false` if there was already a ring registered.
+pub fn register<const N: usize>(ring: Arc<Mutex<LogRing<N>>>) -> bool {
+    GLOBAL.set(ring).is_ok()
+}
+
+///Adds a line to the globally registered ring, if there is one. The message is only formatted if there is a ring.
+pub(crate) fn record(level: LogLevel, msg: impl FnOnce() -> String) {
+    if let Some(ring) = GLOBAL.get() {
+        //not using `MutexExt::lock_panic` as that logs through here on failure
+        ring.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push_line(level, msg());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error_ext::ErrorExt, error_types::BError};
+
+    #[test]
+    fn error_ext_logs_to_registered_ring() {
+        //every other test logs into this too while it's registered, so it needs to be big enough that ours don't get pushed out
+        let ring = Arc::new(Mutex::new(LogRing::<1024>::default()));
+        assert!(register(ring.clone()));
+        assert!(!register(Arc::new(Mutex::new(LogRing::<1>::default()))));
+
+        Err::<(), _>(BError::msg("console ring test: first problem")).warn();
+        Err::<(), _>(BError::msg("console ring test: second problem")).error();
+        Ok::<(), BError>(()).warn();
+
+        let entries = ring
+            .lock()
+            .unwrap()
+            .filtered(LogLevel::Warn)
+            .filter(|e| e.msg.starts_with("console ring test"))
+            .map(|e| (e.level, e.msg.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                (
+                    LogLevel::Warn,
+                    "console ring test: first problem".to_string()
+                ),
+                (
+                    LogLevel::Error,
+                    "console ring test: second problem".to_string()
+                )
+            ]
+        );
+        assert!(ring
+            .lock()
+            .unwrap()
+            .filtered(LogLevel::Error)
+            .all(|e| e.level == LogLevel::Error));
+    }
+}
@@ -28,18 +28,48 @@
 //!
 //! There are also lots of conditional trait implementations, as you can see. For example, if your `T` provides [`std::fmt::Debug`], then the Coordinates will also be debuggable.
 //!
+//! ## Saturating Uses
+//! For things like a cursor in a UI grid, going off the edge should just stop at the edge rather than going OOB. For that, there are saturating versions which clamp each part to `0..MAX`:
+//!```rust
+//! use burntnail_utils::coords::Coords;
+//!
+//! let cursor: Coords<u32, 10, 5> = Coords::from((9, 2));
+//! assert_eq!(cursor.saturating_offset(1, 0), cursor); //pressing right at the right edge stays put
+//! assert_eq!(cursor.saturating_offset(-20, 7), Coords::InBounds(0, 4));
+//! assert_eq!(cursor.saturating_add(Coords::from((5, 1))), Coords::InBounds(9, 3));
+//!
+//! //OOB can't be clamped back, as the coordinates are gone - so clamp them before making the coordinate
+//! assert_eq!(Coords::<i32, 10, 5>::from_clamped((-3, 12)), Coords::InBounds(0, 4));
+//! ```
+//!
 //! ## Array-Related Uses
 //!
 //! These Coordinates can also be used in conjunction with arrays.
 //!
 //! For example, they can be used to index into [`crate::twod_array::TwoArray`] assuming `Coords::MAX_WIDTH == TwoArray::WIDTH && Coords::MAX_HEIGHT == TwoArray::HEIGHT`.
 //!
-//! Also, if you're running a 1D backing for a homemade 2D array, if `T: Into<usize>`, then you can get a usize index to index an array with.
+//! Also, if you're running a 1D backing for a homemade 2D array, if `T: TryInto<usize>`, then you can get a usize index to index an array with, and go back again with [`Coords::from_index`].
+//!
+//! To go to and from a pair of [`usize`]s without flattening, eg. for loop counters, there are [`Coords::from_usize_pair`] and [`Coords::to_usize_pair`].
+//!
+//! ## Laws
+//! All of the operations here stick to these, and they're checked exhaustively over small grids in the tests:
+//! - [`Coords::from`] gives [`Coords::InBounds`] if and only if `0 <= x < MAX_WIDTH` and `0 <= y < MAX_HEIGHT`, so negative coordinates are always OOB.
+//! - Any operation with an OOB coordinate on either side gives OOB.
+//! - `a + b` and `a - b` give the same result as doing the maths on the parts and then using [`Coords::from`], apart from the maths never overflowing or underflowing - if it would, the result is OOB.
+//! - `c * k` and `c / k` work the same way, with division by zero giving OOB.
+//! - `a += b`, `a -= b`, `c *= k` and `c /= k` are the same as `a = a + b` etc., so an operation going out of bounds sets the left side to OOB.
+//! - `c * 1` and `c / 1` are both `c`.
+//! - [`Coords::from_clamped`] is the same as [`Coords::from`] after clamping each part to `0..MAX`, so it is always in-bounds (unless a bound is zero).
+//! - `a.saturating_add(b)` and `c.saturating_offset(dx, dy)` are the same as [`Coords::from_clamped`] on the sums, apart from OOB inputs giving OOB.
+//! - For any in-bounds `c`, `c.to_usize().and_then(Coords::from_index) == Some(c)`, and `c.to_usize_pair()` gives back the parts that [`Coords::from_usize_pair`] takes.
 
-use num_traits::Num;
+use num_traits::{
+    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, NumCast, ToPrimitive,
+};
 use std::{
     fmt::Debug,
-    ops::{Add, AddAssign, Div, Mul, Sub},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
 };
 ///Utility type to hold a set of T coordinates (where T is a [`Num`] in an `(x, y)` format. Can also represent a piece which was taken. If you want coordinates for anywhere, just use `usize::MAX` for the bounds
 ///
@@ -59,6 +89,40 @@ pub enum Coords<T: Num + TryFrom<usize>, const MAX_WIDTH: usize, const MAX_HEIGH
 ///Utility type for coordinates that can exist without maximum x or y positions.
 pub type UnboundedCoord<T> = Coords<T, { usize::MAX }, { usize::MAX }>;
 
+///Which edges of the grid a coordinate is on, from [`Coords::edge_sides`]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)] //one for each side
+pub struct EdgeFlags {
+    ///Whether or not it is on the top row, where y is 0
+    pub top: bool,
+    ///Whether or not it is on the bottom row, where y is `MAX_HEIGHT - 1`
+    pub bottom: bool,
+    ///Whether or not it is on the leftmost column, where x is 0
+    pub left: bool,
+    ///Whether or not it is on the rightmost column, where x is `MAX_WIDTH - 1`
+    pub right: bool,
+}
+
+impl EdgeFlags {
+    ///Whether or not it is on any edge
+    #[must_use]
+    pub const fn any(self) -> bool {
+        self.top || self.bottom || self.left || self.right
+    }
+
+    ///Whether or not it is in a corner, so on both a row edge and a column edge
+    #[must_use]
+    pub const fn is_corner(self) -> bool {
+        (self.top || self.bottom) && (self.left || self.right)
+    }
+
+    ///Gets how many edges it is on - up to 4 for a 1x1 grid
+    #[must_use]
+    pub const fn count(self) -> usize {
+        self.top as usize + self.bottom as usize + self.left as usize + self.right as usize
+    }
+}
+
 impl<T: Num + TryFrom<usize>, const MAX_WIDTH: usize, const MAX_HEIGHT: usize> Default
     for Coords<T, MAX_WIDTH, MAX_HEIGHT>
 {
@@ -86,7 +150,9 @@ impl<T: Num + TryFrom<usize> + PartialOrd, const MAX_WIDTH: usize, const MAX_HEI
     From<(T, T)> for Coords<T, MAX_WIDTH, MAX_HEIGHT>
 {
     fn from((x, y): (T, T)) -> Self {
-        if T::try_from(MAX_WIDTH).map_or(false, |mw| x >= mw)
+        if x < T::zero()
+            || y < T::zero()
+            || T::try_from(MAX_WIDTH).map_or(false, |mw| x >= mw)
             || T::try_from(MAX_HEIGHT).map_or(false, |mh| y >= mh)
         {
             Self::OutOfBounds
@@ -96,19 +162,212 @@ impl<T: Num + TryFrom<usize> + PartialOrd, const MAX_WIDTH: usize, const MAX_HEI
     }
 }
 
-impl<T: Num + TryFrom<usize> + Into<usize>, const MAX_WIDTH: usize, const MAX_HEIGHT: usize>
+impl<T: Num + TryFrom<usize> + TryInto<usize>, const MAX_WIDTH: usize, const MAX_HEIGHT: usize>
     Coords<T, MAX_WIDTH, MAX_HEIGHT>
 {
     ///Provides an index with which to index a 1D array using the 2D coords, assuming a starting position of (0, 0)
+    ///
+    ///The maths is done in [`usize`], so this can't overflow `T`. Returns [`None`] if OOB, or if the index doesn't fit in a [`usize`].
     #[must_use]
     pub fn to_usize(self) -> Option<usize> {
         match self {
             Self::OutOfBounds => None,
             Self::InBounds(x, y) => {
-                T::try_from(MAX_WIDTH).map_or(None, |multiplier| Some((y * multiplier + x).into()))
+                let (x, y): (usize, usize) = (x.try_into().ok()?, y.try_into().ok()?);
+                y.checked_mul(MAX_WIDTH)?.checked_add(x)
             }
         }
     }
+
+    ///The inverse of [`Coords::to_usize`] - turns an index into a 1D array back into coordinates.
+    ///
+    ///Returns [`None`] if the index is past the end of a `MAX_WIDTH * MAX_HEIGHT` array.
+    #[must_use]
+    pub fn from_index(index: usize) -> Option<Self> {
+        if MAX_WIDTH == 0 || index / MAX_WIDTH >= MAX_HEIGHT {
+            return None;
+        }
+
+        let x = T::try_from(index % MAX_WIDTH).ok()?;
+        let y = T::try_from(index / MAX_WIDTH).ok()?;
+        Some(Self::InBounds(x, y))
+    }
+
+    ///Makes a coordinate from [`usize`] parts, eg. from loop counters.
+    ///
+    ///Gives [`Coords::OutOfBounds`] if the parts are out of bounds, or don't fit in `T`.
+    #[must_use]
+    pub fn from_usize_pair(x: usize, y: usize) -> Self {
+        if x >= MAX_WIDTH || y >= MAX_HEIGHT {
+            return Self::OutOfBounds;
+        }
+        match (T::try_from(x), T::try_from(y)) {
+            (Ok(x), Ok(y)) => Self::InBounds(x, y),
+            _ => Self::OutOfBounds,
+        }
+    }
+
+    ///Gets the parts as [`usize`]s, without flattening them like [`Coords::to_usize`]. Returns [`None`] if OOB, or if a part doesn't fit in a [`usize`].
+    #[must_use]
+    pub fn to_usize_pair(self) -> Option<(usize, usize)> {
+        match self {
+            Self::OutOfBounds => None,
+            Self::InBounds(x, y) => Some((x.try_into().ok()?, y.try_into().ok()?)),
+        }
+    }
+
+    ///Gets which edges of the grid the coordinate is on - none of them if OOB
+    #[must_use]
+    pub fn edge_sides(self) -> EdgeFlags {
+        let Some((x, y)) = self.to_usize_pair() else {
+            return EdgeFlags::default();
+        };
+        EdgeFlags {
+            top: y == 0,
+            bottom: y + 1 == MAX_HEIGHT,
+            left: x == 0,
+            right: x + 1 == MAX_WIDTH,
+        }
+    }
+
+    ///Whether or not the coordinate is on any edge of the grid. OOB coordinates aren't.
+    #[must_use]
+    pub fn is_on_edge(self) -> bool {
+        self.edge_sides().any()
+    }
+
+    ///Whether or not the coordinate is in a corner of the grid. OOB coordinates aren't.
+    #[must_use]
+    pub fn is_corner(self) -> bool {
+        self.edge_sides().is_corner()
+    }
+
+    ///Gets how many steps it is to the nearest edge, so 0 if it is on an edge. Returns [`None`] if OOB.
+    #[must_use]
+    pub fn distance_to_nearest_edge(self) -> Option<T> {
+        let (x, y) = self.to_usize_pair()?;
+        let nearest = x
+            .min(y)
+            .min(MAX_WIDTH.saturating_sub(x + 1))
+            .min(MAX_HEIGHT.saturating_sub(y + 1));
+        T::try_from(nearest).ok()
+    }
+
+    ///Iterates over the in-bounds coordinates which are exactly `radius` steps away, counting diagonals as one step (the Chebyshev distance) - so the square ring around `self`.
+    ///
+    ///Goes clockwise from the top left, along the top, down the right, back along the bottom and up the left. A radius of 0 gives just `self`, and an OOB centre gives nothing.
+    pub fn ring(self, radius: usize) -> impl Iterator<Item = Self> {
+        self.to_usize_pair()
+            .into_iter()
+            .flat_map(move |(x, y)| Self::base_ring(x, y, radius))
+    }
+
+    ///Iterates over every in-bounds coordinate, nearest first - `self`, then [`Coords::ring`] 1, then ring 2, etc. - eg. for finding the nearest free tile.
+    ///
+    ///Stops once the ring reaching the furthest corner is done, even if some rings in between are entirely off the grid. An OOB centre gives nothing.
+    pub fn spiral(self) -> impl Iterator<Item = Self> {
+        self.to_usize_pair().into_iter().flat_map(|(x, y)| {
+            let furthest = x
+                .max(y)
+                .max(MAX_WIDTH.saturating_sub(x + 1))
+                .max(MAX_HEIGHT.saturating_sub(y + 1));
+            (0..=furthest).flat_map(move |radius| Self::base_ring(x, y, radius))
+        })
+    }
+
+    ///Base function for a ring around `(x, y)`, which only walks the parts of each side that are on the grid
+    fn base_ring(x: usize, y: usize, radius: usize) -> impl Iterator<Item = Self> {
+        //i128 so nothing here can overflow, even on an unbounded grid
+        let (x, y, r) = (x as i128, y as i128, radius as i128);
+        let (width, height) = (MAX_WIDTH as i128, MAX_HEIGHT as i128);
+        let (left, right, top, bottom) = (x - r, x + r, y - r, y + r);
+
+        //the top and bottom rows have the corners, and the sides fill in between
+        let across = left.max(0)..=right.min(width - 1);
+        let down = (top + 1).max(0)..=(bottom - 1).min(height - 1);
+
+        let top_row = (top >= 0).then(|| across.clone().map(move |x| (x, top)));
+        let right_side = (right < width).then(|| down.clone().map(move |y| (right, y)));
+        let bottom_row = (r > 0 && bottom < height).then(|| across.rev().map(move |x| (x, bottom)));
+        let left_side = (r > 0 && left >= 0).then(|| down.rev().map(move |y| (left, y)));
+
+        top_row
+            .into_iter()
+            .flatten()
+            .chain(right_side.into_iter().flatten())
+            .chain(bottom_row.into_iter().flatten())
+            .chain(left_side.into_iter().flatten())
+            .filter_map(|(x, y)| {
+                let coords =
+                    Self::from_usize_pair(usize::try_from(x).ok()?, usize::try_from(y).ok()?);
+                coords.is_ib().then_some(coords)
+            })
+    }
+}
+
+impl<
+        T: Num + TryFrom<usize> + TryInto<usize> + PartialOrd + Bounded,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    ///Base function for clamping one part to `0..max`, as well as to whatever fits in `T`. Returns [`None`] if `max` is zero.
+    fn base_clamp_part(part: usize, max: usize) -> Option<T> {
+        let fits_in_t = T::max_value().try_into().unwrap_or(usize::MAX);
+        let limit = max.checked_sub(1)?.min(fits_in_t);
+        T::try_from(part.min(limit)).ok()
+    }
+
+    ///Base function for clamping both parts, going OOB only if a bound is zero
+    fn base_clamped(x: usize, y: usize) -> Self {
+        match (
+            Self::base_clamp_part(x, MAX_WIDTH),
+            Self::base_clamp_part(y, MAX_HEIGHT),
+        ) {
+            (Some(x), Some(y)) => Self::InBounds(x, y),
+            _ => Self::OutOfBounds,
+        }
+    }
+
+    ///Makes a coordinate, clamping each part to `0..MAX` rather than going OOB like [`Coords::from`].
+    ///
+    ///Only gives [`Coords::OutOfBounds`] if `MAX_WIDTH` or `MAX_HEIGHT` is zero, as then there are no in-bounds coordinates.
+    #[must_use]
+    pub fn from_clamped((x, y): (T, T)) -> Self {
+        //anything that doesn't fit in a usize is either negative or far too big
+        let to_part = |t: T| {
+            let negative = t < T::zero();
+            t.try_into()
+                .unwrap_or(if negative { 0 } else { usize::MAX })
+        };
+        Self::base_clamped(to_part(x), to_part(y))
+    }
+
+    ///Adds two coordinates, clamping each part to `0..MAX` rather than going OOB like [`Add`].
+    ///
+    ///If either coordinate is already OOB, then the result is OOB.
+    #[must_use]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        match (self.to_usize_pair(), rhs.to_usize_pair()) {
+            (Some((ax, ay)), Some((bx, by))) => {
+                Self::base_clamped(ax.saturating_add(bx), ay.saturating_add(by))
+            }
+            _ => Self::OutOfBounds,
+        }
+    }
+
+    ///Moves the coordinate by `dx` and `dy`, clamping each part to `0..MAX` rather than going OOB - eg. for moving a cursor, where going off the edge should stay at the edge.
+    ///
+    ///If the coordinate is already OOB, then it stays OOB.
+    #[must_use]
+    pub fn saturating_offset(self, dx: isize, dy: isize) -> Self {
+        match self.to_usize_pair() {
+            Some((x, y)) => {
+                Self::base_clamped(x.saturating_add_signed(dx), y.saturating_add_signed(dy))
+            }
+            None => Self::OutOfBounds,
+        }
+    }
 }
 
 impl<
@@ -187,40 +446,136 @@ impl<T: Num + TryFrom<usize>, const MAX_WIDTH: usize, const MAX_HEIGHT: usize>
     }
 }
 
-impl<T: Num + TryFrom<usize> + PartialOrd, const MAX_WIDTH: usize, const MAX_HEIGHT: usize> Add
-    for Coords<T, MAX_WIDTH, MAX_HEIGHT>
+impl<T: Num + TryFrom<usize> + PartialOrd, const MAX_WIDTH: usize, const MAX_HEIGHT: usize>
+    Coords<T, MAX_WIDTH, MAX_HEIGHT>
 {
-    type Output = Self;
+    ///Base function for arithmetic - applies `op` to both parts, going OOB if either part fails or the result is OOB
+    fn base_op(self, op: impl Fn(&T) -> Option<T>) -> Self {
+        match self {
+            Self::OutOfBounds => Self::OutOfBounds,
+            Self::InBounds(x, y) => match (op(&x), op(&y)) {
+                (Some(x), Some(y)) => Self::from((x, y)),
+                _ => Self::OutOfBounds,
+            },
+        }
+    }
 
-    fn add(self, rhs: Self) -> Self::Output {
+    ///Base function for arithmetic between two coordinates - applies `op` to the matching parts, going OOB if either part fails or the result is OOB
+    fn base_pair_op(self, rhs: Self, op: impl Fn(&T, &T) -> Option<T>) -> Self {
         match (self, rhs) {
             (Self::OutOfBounds, _) | (_, Self::OutOfBounds) => Self::OutOfBounds,
             (Self::InBounds(ax, ay), Self::InBounds(bx, by)) => {
-                let x: T = ax + bx;
-                let y: T = ay + by;
-                Self::from((x, y))
+                match (op(&ax, &bx), op(&ay, &by)) {
+                    (Some(x), Some(y)) => Self::from((x, y)),
+                    _ => Self::OutOfBounds,
+                }
             }
         }
     }
 }
-impl<T: Num + TryFrom<usize> + PartialOrd, const MAX_WIDTH: usize, const MAX_HEIGHT: usize> Sub
-    for Coords<T, MAX_WIDTH, MAX_HEIGHT>
+
+impl<T: Num + TryFrom<usize> + PartialOrd, const MAX_WIDTH: usize, const MAX_HEIGHT: usize>
+    Coords<T, MAX_WIDTH, MAX_HEIGHT>
 {
-    type Output = Self;
+    ///Base function for picking each part from one of two coordinates, using `pick_first` to choose. OOB if either is.
+    ///
+    ///As both parts come from in-bounds coordinates, the result is always in-bounds.
+    fn base_pick(self, rhs: Self, pick_first: impl Fn(&T, &T) -> bool) -> Self {
+        match (self, rhs) {
+            (Self::InBounds(ax, ay), Self::InBounds(bx, by)) => Self::InBounds(
+                if pick_first(&ax, &bx) { ax } else { bx },
+                if pick_first(&ay, &by) { ay } else { by },
+            ),
+            _ => Self::OutOfBounds,
+        }
+    }
 
-    fn sub(self, rhs: Self) -> Self::Output {
+    ///Gets the smaller of each part, eg. for the top left of a bounding box. OOB if either is.
+    #[must_use]
+    pub fn component_min(self, rhs: Self) -> Self {
+        self.base_pick(rhs, |a, b| a <= b)
+    }
+
+    ///Gets the bigger of each part, eg. for the bottom right of a bounding box. OOB if either is.
+    #[must_use]
+    pub fn component_max(self, rhs: Self) -> Self {
+        self.base_pick(rhs, |a, b| a >= b)
+    }
+}
+
+impl<
+        T: Num + TryFrom<usize> + CheckedAdd + CheckedMul,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    ///Gets the dot product, `ax * bx + ay * by`. Returns [`None`] if either is OOB, or if the maths overflows `T`.
+    #[must_use]
+    pub fn dot(self, rhs: Self) -> Option<T> {
         match (self, rhs) {
-            (Self::OutOfBounds, _) | (_, Self::OutOfBounds) => Self::OutOfBounds,
             (Self::InBounds(ax, ay), Self::InBounds(bx, by)) => {
-                let x: T = ax - bx;
-                let y: T = ay - by;
-                Self::from((x, y))
+                ax.checked_mul(&bx)?.checked_add(&ay.checked_mul(&by)?)
             }
+            _ => None,
         }
     }
 }
+
+impl<
+        T: Num + TryFrom<usize> + PartialOrd + ToPrimitive + NumCast,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    ///Linearly interpolates between two coordinates - `t` of 0.0 gives `self`, and 1.0 gives `rhs`. Values outside of that go past the ends, and are OOB if they go out of bounds.
+    ///
+    ///The maths is done in [`f64`], and then each part is rounded to the nearest integer, with halves going away from zero (like [`f64::round`]). Returns OOB if either is OOB, if `t` isn't finite, or if the result doesn't fit in `T`.
+    #[must_use]
+    pub fn lerp(self, rhs: Self, t: f64) -> Self {
+        ///Lerps one part, rounding to the nearest integer
+        fn part<T: ToPrimitive + NumCast>(a: &T, b: &T, t: f64) -> Option<T> {
+            let (a, b) = (a.to_f64()?, b.to_f64()?);
+            T::from((b - a).mul_add(t, a).round())
+        }
+
+        match (self, rhs) {
+            (Self::InBounds(ax, ay), Self::InBounds(bx, by)) if t.is_finite() => {
+                match (part(&ax, &bx, t), part(&ay, &by, t)) {
+                    (Some(x), Some(y)) => Self::from((x, y)),
+                    _ => Self::OutOfBounds,
+                }
+            }
+            _ => Self::OutOfBounds,
+        }
+    }
+}
+
+impl<
+        T: Num + TryFrom<usize> + PartialOrd + CheckedAdd,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > Add for Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.base_pair_op(rhs, T::checked_add)
+    }
+}
+impl<
+        T: Num + TryFrom<usize> + PartialOrd + CheckedSub,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > Sub for Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.base_pair_op(rhs, T::checked_sub)
+    }
+}
 impl<
-        T: Num + TryFrom<usize> + PartialOrd + Mul + Copy,
+        T: Num + TryFrom<usize> + PartialOrd + CheckedMul,
         const MAX_WIDTH: usize,
         const MAX_HEIGHT: usize,
     > Mul<T> for Coords<T, MAX_WIDTH, MAX_HEIGHT>
@@ -228,14 +583,11 @@ impl<
     type Output = Self;
 
     fn mul(self, rhs: T) -> Self::Output {
-        match self {
-            Self::OutOfBounds => Self::OutOfBounds,
-            Self::InBounds(x, y) => Self::from((x * rhs, y * rhs)),
-        }
+        self.base_op(|el| el.checked_mul(&rhs))
     }
 }
 impl<
-        T: Num + TryFrom<usize> + PartialOrd + Div + Copy,
+        T: Num + TryFrom<usize> + PartialOrd + CheckedDiv,
         const MAX_WIDTH: usize,
         const MAX_HEIGHT: usize,
     > Div<T> for Coords<T, MAX_WIDTH, MAX_HEIGHT>
@@ -243,16 +595,384 @@ impl<
     type Output = Self;
 
     fn div(self, rhs: T) -> Self::Output {
-        match self {
-            Self::OutOfBounds => Self::OutOfBounds,
-            Self::InBounds(x, y) => Self::from((x / rhs, y / rhs)),
-        }
+        self.base_op(|el| el.checked_div(&rhs))
+    }
+}
+
+impl<
+        T: Num + TryFrom<usize> + PartialOrd + CheckedAdd,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > AddAssign for Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::OutOfBounds) + rhs;
+    }
+}
+impl<
+        T: Num + TryFrom<usize> + PartialOrd + CheckedSub,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > SubAssign for Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = std::mem::replace(self, Self::OutOfBounds) - rhs;
+    }
+}
+impl<
+        T: Num + TryFrom<usize> + PartialOrd + CheckedMul,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > MulAssign<T> for Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = std::mem::replace(self, Self::OutOfBounds) * rhs;
+    }
+}
+impl<
+        T: Num + TryFrom<usize> + PartialOrd + CheckedDiv,
+        const MAX_WIDTH: usize,
+        const MAX_HEIGHT: usize,
+    > DivAssign<T> for Coords<T, MAX_WIDTH, MAX_HEIGHT>
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = std::mem::replace(self, Self::OutOfBounds) / rhs;
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::coords::Coords;
+    use crate::coords::{Coords, EdgeFlags, UnboundedCoord};
+
+    ///Checks all of the laws from the module docs for every pair of coordinates (and scalar) in `$range`, on a 5x4 grid
+    macro_rules! check_laws {
+        ($name:ident, $t:ty, $range:expr) => {
+            #[test]
+            #[allow(
+                clippy::cast_lossless,
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_possible_wrap
+            )]
+            fn $name() {
+                type C = Coords<$t, 5, 4>;
+                let expected = |x: i64, y: i64| -> C {
+                    if (0..5).contains(&x) && (0..4).contains(&y) {
+                        C::InBounds(x as $t, y as $t)
+                    } else {
+                        C::OutOfBounds
+                    }
+                };
+                let clamped =
+                    |x: i64, y: i64| C::InBounds(x.clamp(0, 4) as $t, y.clamp(0, 3) as $t);
+                let all = || $range.flat_map(|x: $t| $range.map(move |y: $t| (x, y)));
+
+                for (ax, ay) in all() {
+                    let a = C::from((ax, ay));
+                    assert_eq!(a, expected(ax as i64, ay as i64), "from {ax}, {ay}");
+
+                    if a.is_ib() {
+                        assert_eq!(a.to_usize().and_then(C::from_index), Some(a));
+                        let (x, y) = a.to_usize_pair().unwrap();
+                        assert_eq!(C::from_usize_pair(x, y), a);
+                    } else {
+                        assert_eq!(a.to_usize(), None);
+                        assert_eq!(a.to_usize_pair(), None);
+                    }
+                    assert_eq!(a * 1, a);
+                    assert_eq!(a / 1, a);
+                    assert!((a / 0).is_oob());
+                    let mut divided = a;
+                    divided /= 0;
+                    assert!(divided.is_oob());
+                    assert_eq!(C::from_clamped((ax, ay)), clamped(ax as i64, ay as i64));
+
+                    for (dx, dy) in [(0, 0), (1, -1), (-7, 3), (isize::MAX, isize::MIN)] {
+                        assert_eq!(
+                            a.saturating_offset(dx, dy),
+                            a.to_option().map_or(C::OutOfBounds, |(x, y)| clamped(
+                                (x as i64).saturating_add(dx as i64),
+                                (y as i64).saturating_add(dy as i64)
+                            )),
+                            "{a:?} offset by {dx}, {dy}"
+                        );
+                    }
+
+                    for k in $range {
+                        assert_eq!(
+                            a * k,
+                            a.to_option().map_or(C::OutOfBounds, |(x, y)| expected(
+                                x as i64 * k as i64,
+                                y as i64 * k as i64
+                            )),
+                            "{a:?} * {k}"
+                        );
+                        let (mut multiplied, mut divided) = (a, a);
+                        multiplied *= k;
+                        divided /= k;
+                        assert_eq!(multiplied, a * k, "{a:?} *= {k}");
+                        assert_eq!(divided, a / k, "{a:?} /= {k}");
+                    }
+
+                    for (bx, by) in all() {
+                        let b = C::from((bx, by));
+                        let (sum, difference) = match (a.to_option(), b.to_option()) {
+                            (Some((ax, ay)), Some((bx, by))) => (
+                                expected(ax as i64 + bx as i64, ay as i64 + by as i64),
+                                expected(ax as i64 - bx as i64, ay as i64 - by as i64),
+                            ),
+                            _ => (C::OutOfBounds, C::OutOfBounds),
+                        };
+                        assert_eq!(a + b, sum, "{a:?} + {b:?}");
+                        assert_eq!(
+                            a.saturating_add(b),
+                            match (a.to_option(), b.to_option()) {
+                                (Some((ax, ay)), Some((bx, by))) =>
+                                    clamped(ax as i64 + bx as i64, ay as i64 + by as i64),
+                                _ => C::OutOfBounds,
+                            },
+                            "{a:?} saturating + {b:?}"
+                        );
+                        assert_eq!(a - b, difference, "{a:?} - {b:?}");
+
+                        let (mut added, mut subtracted) = (a, a);
+                        added += b;
+                        subtracted -= b;
+                        assert_eq!(added, sum, "{a:?} += {b:?}");
+                        assert_eq!(subtracted, difference, "{a:?} -= {b:?}");
+                    }
+                }
+
+                assert_eq!(C::from_index(20), None);
+                assert!((C::OutOfBounds + C::OutOfBounds).is_oob());
+            }
+        };
+    }
+
+    check_laws!(laws_i8, i8, -3_i8..8);
+    check_laws!(laws_u8, u8, 0_u8..8);
+    check_laws!(laws_u16, u16, 0_u16..8);
+    check_laws!(laws_usize, usize, 0_usize..8);
+
+    #[test]
+    fn no_overflow_panics() {
+        let big: Coords<u8, 300, 300> = Coords::from((200, 200));
+        assert!(big.is_ib());
+        assert!((big + big).is_oob());
+        assert!((big * 2).is_oob());
+        assert_eq!(big.to_usize(), Some(200 * 300 + 200));
+        assert_eq!(big.to_usize_pair(), Some((200, 200)));
+
+        //too big for a u8, even though it is within the bounds
+        assert!(Coords::<u8, 300, 300>::from_usize_pair(256, 0).is_oob());
+        assert!(Coords::<u8, 300, 300>::from_usize_pair(0, 300).is_oob());
+        assert_eq!(
+            Coords::<u8, 300, 300>::from_usize_pair(255, 299),
+            Coords::OutOfBounds
+        );
+        assert_eq!(
+            Coords::<u16, 300, 300>::from_usize_pair(255, 299),
+            Coords::InBounds(255, 299)
+        );
+
+        let small: Coords<u8, 5, 5> = Coords::from((1, 1));
+        assert!((small - Coords::from((2, 0))).is_oob());
+
+        //bounds bigger than T can hold clamp to the biggest T instead
+        assert_eq!(big.saturating_add(big), Coords::InBounds(255, 255));
+        assert_eq!(
+            Coords::<u8, 300, 300>::from_clamped((7, 255)).saturating_offset(isize::MAX, -8),
+            Coords::InBounds(255, 247)
+        );
+        assert!(Coords::<u8, 0, 5>::from_clamped((1, 1)).is_oob());
+        assert!(UnboundedCoord::<u64>::from_clamped((u64::MAX, 0)).is_ib());
+    }
+
+    #[test]
+    fn assigning_operators() {
+        let mut pos: Coords<i32, 5, 5> = Coords::from((1, 1));
+        pos += Coords::from((2, 1));
+        assert_eq!(pos, Coords::InBounds(3, 2));
+        pos -= Coords::from((3, 0));
+        assert_eq!(pos, Coords::InBounds(0, 2));
+        pos *= 2;
+        assert_eq!(pos, Coords::InBounds(0, 4));
+        pos /= 3;
+        assert_eq!(pos, Coords::InBounds(0, 1));
+
+        //walking off the edge sets it to OOB, which then stays OOB
+        pos -= Coords::from((1, 0));
+        assert!(pos.is_oob());
+        pos += Coords::from((1, 0));
+        assert!(pos.is_oob());
+
+        //division by zero is OOB rather than a panic
+        let mut pos: Coords<u8, 5, 5> = Coords::from((4, 4));
+        assert!((pos / 0).is_oob());
+        pos /= 0;
+        assert!(pos.is_oob());
+        let mut unbounded = UnboundedCoord::<usize>::from((10, 20));
+        unbounded /= 0;
+        assert!(unbounded.is_oob());
+    }
+
+    #[test]
+    fn edges_of_a_5x5() {
+        type C = Coords<u8, 5, 5>;
+        let edges = |x, y| C::from((x, y)).edge_sides();
+
+        for (x, y) in [(0, 0), (4, 0), (0, 4), (4, 4)] {
+            let c = C::from((x, y));
+            assert!(c.is_corner() && c.is_on_edge(), "{c:?}");
+            assert_eq!(c.edge_sides().count(), 2);
+            assert_eq!(c.distance_to_nearest_edge(), Some(0));
+        }
+        assert_eq!(
+            edges(4, 0),
+            EdgeFlags {
+                top: true,
+                right: true,
+                ..EdgeFlags::default()
+            }
+        );
+
+        for (x, y) in [(2, 0), (0, 2), (4, 2), (2, 4)] {
+            let c = C::from((x, y));
+            assert!(c.is_on_edge() && !c.is_corner(), "{c:?}");
+            assert_eq!(c.edge_sides().count(), 1);
+            assert_eq!(c.distance_to_nearest_edge(), Some(0));
+        }
+        assert_eq!(
+            edges(2, 4),
+            EdgeFlags {
+                bottom: true,
+                ..EdgeFlags::default()
+            }
+        );
+
+        let centre = C::from((2, 2));
+        assert!(!centre.is_on_edge() && !centre.is_corner());
+        assert_eq!(centre.edge_sides(), EdgeFlags::default());
+        assert_eq!(centre.distance_to_nearest_edge(), Some(2));
+        assert_eq!(C::from((1, 3)).distance_to_nearest_edge(), Some(1));
+
+        let oob = C::OutOfBounds;
+        assert!(!oob.is_on_edge() && !oob.is_corner());
+        assert_eq!(oob.edge_sides(), EdgeFlags::default());
+        assert_eq!(oob.distance_to_nearest_edge(), None);
+
+        //every side of a 1x1 grid is an edge
+        assert_eq!(Coords::<u8, 1, 1>::default().edge_sides().count(), 4);
+    }
+
+    #[test]
+    fn vector_maths() {
+        type C = Coords<i32, 10, 10>;
+        let (a, b) = (C::from((1, 8)), C::from((6, 3)));
+
+        assert_eq!(a.dot(b), Some(30));
+        assert_eq!(a.dot(C::OutOfBounds), None);
+        assert_eq!(
+            Coords::<u8, 20, 20>::from((16, 0)).dot(Coords::from((16, 0))),
+            None
+        );
+
+        assert_eq!(a.component_min(b), C::from((1, 3)));
+        assert_eq!(a.component_max(b), C::from((6, 8)));
+        assert!(C::OutOfBounds.component_max(a).is_oob());
+    }
+
+    #[test]
+    fn lerp_rounding() {
+        type C = Coords<i32, 10, 10>;
+        let (a, b) = (C::from((0, 9)), C::from((5, 4)));
+
+        assert_eq!(a.lerp(b, 0.0), a);
+        assert_eq!(a.lerp(b, 1.0), b);
+        //2.5 and 6.5 both round away from zero
+        assert_eq!(a.lerp(b, 0.5), C::from((3, 7)));
+        //1.2 and 7.8 round to the nearest
+        assert_eq!(a.lerp(b, 0.24), C::from((1, 8)));
+
+        //going past the ends can go out of bounds
+        assert_eq!(a.lerp(b, 1.2), C::from((6, 3)));
+        assert!(a.lerp(b, -0.2).is_oob());
+        assert!(a.lerp(b, f64::NAN).is_oob());
+        assert!(a.lerp(C::OutOfBounds, 0.5).is_oob());
+
+        //halves round away from zero going backwards too, so 1.5 goes to 2
+        assert_eq!(C::from((3, 3)).lerp(C::from((0, 0)), 0.5), C::from((2, 2)));
+    }
+
+    ///Checks that every ring and spiral on a `W`x`H` grid matches working it out by brute force
+    fn check_rings<const W: usize, const H: usize>() {
+        type C<const W: usize, const H: usize> = Coords<u8, W, H>;
+        let all: Vec<C<W, H>> = (0..W * H).filter_map(C::from_index).collect();
+        let distance = |a: C<W, H>, b: C<W, H>| {
+            let ((ax, ay), (bx, by)) = (a.to_usize_pair().unwrap(), b.to_usize_pair().unwrap());
+            ax.abs_diff(bx).max(ay.abs_diff(by))
+        };
+
+        for &centre in &all {
+            for radius in 0..W.max(H) + 2 {
+                let mut ring: Vec<_> = centre.ring(radius).collect();
+                ring.sort_by_key(|c| c.to_usize());
+                let expected: Vec<_> = all
+                    .iter()
+                    .copied()
+                    .filter(|&c| distance(centre, c) == radius)
+                    .collect();
+                assert_eq!(ring, expected, "ring {radius} around {centre:?}");
+            }
+
+            let spiral: Vec<_> = centre.spiral().collect();
+            assert_eq!(spiral.first(), Some(&centre));
+            assert!(
+                spiral
+                    .windows(2)
+                    .all(|w| distance(centre, w[0]) <= distance(centre, w[1])),
+                "spiral around {centre:?} goes nearest first"
+            );
+            let mut sorted = spiral.clone();
+            sorted.sort_by_key(|c| c.to_usize());
+            assert_eq!(sorted, all, "spiral around {centre:?} gets everything once");
+        }
+
+        assert_eq!(C::<W, H>::OutOfBounds.ring(0).count(), 0);
+        assert_eq!(C::<W, H>::OutOfBounds.spiral().count(), 0);
+    }
+
+    #[test]
+    fn rings_and_spirals() {
+        check_rings::<1, 1>();
+        check_rings::<1, 4>();
+        check_rings::<4, 1>();
+        check_rings::<3, 3>();
+        check_rings::<5, 2>();
+        check_rings::<6, 5>();
+
+        //clockwise from the top left
+        let centre = Coords::<u8, 5, 5>::from((2, 2));
+        let ring: Vec<_> = centre.ring(1).filter_map(|c| c.to_option()).collect();
+        assert_eq!(
+            ring,
+            vec![
+                (1, 1),
+                (2, 1),
+                (3, 1),
+                (3, 2),
+                (3, 3),
+                (2, 3),
+                (1, 3),
+                (1, 2)
+            ]
+        );
+
+        //rings which are entirely off the grid on one side still carry on along the rest
+        let corner = Coords::<u8, 10, 2>::from((0, 0));
+        assert_eq!(corner.ring(5).count(), 2);
+        assert_eq!(corner.spiral().count(), 20);
+    }
 
     #[test]
     fn increment_test() {
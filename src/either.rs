@@ -1,12 +1,24 @@
 //! An Either type for representing something that can be A or B. Similar ergonomics to a [`std::result::Result`], and if need be you can convert easily to one.
 //!
+//! For three options, there's [`Either3`] rather than nesting an [`Either`] inside another one.
+//!
 //! Includes lots of transformers to get values out of an option, as well as conditional implementations like [`std::clone::Clone`] and [`std::fmt::Debug`]
 
-use std::fmt::{Debug, Formatter};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    future::Future,
+    hash::{Hash, Hasher},
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 ///Enum which can represent one of two values
 ///
 ///The Same as an `(Option<A>, Option<B>)` where one [`Option`] must always be [`Option::Some`] and the other must be [`Option::None`]
+///
+///With `serde`, this uses the externally tagged representation - eg. `{"Left": 5}`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[allow(clippy::unsafe_derive_deserialize)] //the only unsafe is pin projection, which doesn't rely on anything about the values
 pub enum Either<L, R> {
     ///The First variant of [`Either`]
     Left(L),
@@ -14,6 +26,11 @@ pub enum Either<L, R> {
     Right(R),
 }
 
+crate::variant_accessors!(impl<L, R> Either<L, R> {
+    Left(L) => is_left, to_left, ref_left, mut_ref_left, clone_left, expect_left;
+    Right(R) => is_right, to_right, ref_right, mut_ref_right, clone_right, expect_right;
+});
+
 impl<L, R> Either<L, R> {
     ///Constructor for [`Either::Left`] which uses [`Into::into`]
     pub fn l(a: impl Into<L>) -> Self {
@@ -25,16 +42,6 @@ impl<L, R> Either<L, R> {
         Self::Right(b.into())
     }
 
-    ///Utility function for checking which side it is. Here we check if it is the [`Either::Left`] variant
-    pub const fn is_left(&self) -> bool {
-        matches!(self, Self::Left(_))
-    }
-
-    ///Utility function for checking which side it is. Here we check if it is the [`Either::Right`] variant
-    pub const fn is_right(&self) -> bool {
-        matches!(self, Self::Right(_))
-    }
-
     //region transformers
     ///Utility function for [`Either::Left`] Vs [`Either::Right`] if you need nice variable names
     pub const fn to_unit(&self) -> Either<(), ()> {
@@ -45,103 +52,194 @@ impl<L, R> Either<L, R> {
         }
     }
 
-    ///Function to check if this is [`Either::Left`], and if so return [`Some`] of that, else [`None`]
+    ///Returns the value inside an [`Either::Left`], or `default` if this is an [`Either::Right`]
+    pub fn left_or(self, default: L) -> L {
+        self.left_or_else(|_| default)
+    }
+
+    ///Returns the value inside an [`Either::Left`], or turns the [`Either::Right`] value into an `L` using `f`
+    pub fn left_or_else(self, f: impl FnOnce(R) -> L) -> L {
+        match self {
+            Self::Left(l) => l,
+            Self::Right(r) => f(r),
+        }
+    }
+
+    ///Returns the value inside an [`Either::Right`], or `default` if this is an [`Either::Left`]
+    pub fn right_or(self, default: R) -> R {
+        self.right_or_else(|_| default)
+    }
+
+    ///Returns the value inside an [`Either::Right`], or turns the [`Either::Left`] value into an `R` using `f`
+    pub fn right_or_else(self, f: impl FnOnce(L) -> R) -> R {
+        match self {
+            Self::Left(l) => f(l),
+            Self::Right(r) => r,
+        }
+    }
+
+    ///Converts Either<L, R> to Result<L, R>
+    #[allow(clippy::missing_errors_doc, clippy::missing_const_for_fn)] //no need, issue with destructors
+    pub fn to_result(self) -> Result<L, R> {
+        match self {
+            Self::Left(l) => Ok(l),
+            Self::Right(r) => Err(r),
+        }
+    }
+
+    ///Converts a [`Result`] into an [`Either`], with [`Ok`] as [`Either::Left`] and [`Err`] as [`Either::Right`]. Same as the [`From`] implementation.
     ///
-    ///Returns an Owned `L`
+    ///```rust
+    /// use burntnail_utils::either::Either;
     ///
-    ///Non-const due to [E0493](https://doc.rust-lang.org/error-index.html#E0493)
-    #[allow(clippy::missing_const_for_fn)]
-    pub fn to_left(self) -> Option<L> {
-        if let Self::Left(l) = self {
-            Some(l)
-        } else {
-            None
+    /// let parsed: Either<u8, _> = Either::from_result("12".parse::<u8>());
+    /// assert_eq!(parsed.to_left(), Some(12));
+    ///```
+    #[allow(clippy::missing_const_for_fn)] //issue with destructors
+    pub fn from_result(res: Result<L, R>) -> Self {
+        match res {
+            Ok(l) => Self::Left(l),
+            Err(r) => Self::Right(r),
         }
     }
-    ///Function to check if this is [`Either::Left`], and if so return [`Some`] of that, else [`None`]
+
+    ///Converts to a [`Result`] where [`Either::Left`] is the success - the same as [`Either::to_result`]
     ///
-    ///Returns a reference to an `L`
-    pub const fn ref_left(&self) -> Option<&L> {
-        if let Self::Left(l) = self {
-            Some(l)
-        } else {
-            None
-        }
+    ///```rust
+    /// use burntnail_utils::either::Either;
+    ///
+    /// let e: Either<u8, &str> = Either::Right("not a number");
+    /// assert_eq!(e.ok_left(), Err("not a number"));
+    ///```
+    #[allow(clippy::missing_errors_doc)] //no need
+    pub fn ok_left(self) -> Result<L, R> {
+        self.to_result()
     }
-    ///Function to check if this is [`Either::Left`], and if so return [`Some`] of that, else [`None`]
+
+    ///Converts to a [`Result`] where [`Either::Right`] is the success
     ///
-    ///Returns a mutable reference to an `L`
-    pub fn mut_ref_left(&mut self) -> Option<&mut L> {
-        if let Self::Left(l) = self {
-            Some(l)
-        } else {
-            None
+    ///```rust
+    /// use burntnail_utils::either::Either;
+    ///
+    /// let e: Either<u8, &str> = Either::Right("found it");
+    /// assert_eq!(e.ok_right(), Ok("found it"));
+    ///```
+    #[allow(clippy::missing_errors_doc, clippy::missing_const_for_fn)] //no need, issue with destructors
+    pub fn ok_right(self) -> Result<R, L> {
+        match self {
+            Self::Left(l) => Err(l),
+            Self::Right(r) => Ok(r),
         }
     }
 
-    ///Function to check if this is [`Either::Right`], and if so return [`Some`] of that, else [`None`]
+    ///Splits into a pair of [`Option`]s, where exactly one is [`Some`]
     ///
-    ///Returns an Owned `L`
+    ///```rust
+    /// use burntnail_utils::either::Either;
     ///
-    ///Non-const due to [E0493](https://doc.rust-lang.org/error-index.html#E0493)
-    #[allow(clippy::missing_const_for_fn)]
-    pub fn to_right(self) -> Option<R> {
-        if let Self::Right(r) = self {
-            Some(r)
-        } else {
-            None
+    /// let e: Either<u8, &str> = Either::Left(5);
+    /// assert_eq!(e.into_options(), (Some(5), None));
+    ///```
+    #[allow(clippy::missing_const_for_fn)] //issue with destructors
+    pub fn into_options(self) -> (Option<L>, Option<R>) {
+        match self {
+            Self::Left(l) => (Some(l), None),
+            Self::Right(r) => (None, Some(r)),
         }
     }
-    ///Function to check if this is [`Either::Right`], and if so return [`Some`] of that, else [`None`]
-    ///
-    ///Returns a reference to an `L`
-    pub const fn ref_right(&self) -> Option<&R> {
-        if let Self::Right(r) = self {
-            Some(r)
-        } else {
-            None
+
+    //endregion
+
+    //region combinators
+    ///Transforms the value inside an [`Either::Left`], leaving an [`Either::Right`] alone
+    pub fn map_left<L2>(self, f: impl FnOnce(L) -> L2) -> Either<L2, R> {
+        match self {
+            Self::Left(l) => Either::Left(f(l)),
+            Self::Right(r) => Either::Right(r),
         }
     }
-    ///Function to check if this is [`Either::Right`], and if so return [`Some`] of that, else [`None`]
-    ///
-    ///Returns a mutable reference to an `L`
-    pub fn mut_ref_right(&mut self) -> Option<&mut R> {
-        if let Self::Right(r) = self {
-            Some(r)
-        } else {
-            None
+
+    ///Transforms the value inside an [`Either::Right`], leaving an [`Either::Left`] alone
+    pub fn map_right<R2>(self, f: impl FnOnce(R) -> R2) -> Either<L, R2> {
+        match self {
+            Self::Left(l) => Either::Left(l),
+            Self::Right(r) => Either::Right(f(r)),
         }
     }
 
-    ///Converts Either<L, R> to Result<L, R>
-    #[allow(clippy::missing_errors_doc, clippy::missing_const_for_fn)] //no need, issue with destructors
-    pub fn to_result(self) -> Result<L, R> {
+    ///Transforms the value inside, using `f` for an [`Either::Left`] and `g` for an [`Either::Right`]
+    pub fn map_either<L2, R2>(
+        self,
+        f: impl FnOnce(L) -> L2,
+        g: impl FnOnce(R) -> R2,
+    ) -> Either<L2, R2> {
         match self {
-            Self::Left(l) => Ok(l),
-            Self::Right(r) => Err(r),
+            Self::Left(l) => Either::Left(f(l)),
+            Self::Right(r) => Either::Right(g(r)),
+        }
+    }
+
+    ///Chains another step onto an [`Either::Left`], like [`Result::and_then`]. An [`Either::Right`] is passed straight through without calling `f`.
+    pub fn left_and_then<L2>(self, f: impl FnOnce(L) -> Either<L2, R>) -> Either<L2, R> {
+        match self {
+            Self::Left(l) => f(l),
+            Self::Right(r) => Either::Right(r),
+        }
+    }
+
+    ///Chains another step onto an [`Either::Right`]. An [`Either::Left`] is passed straight through without calling `f`.
+    pub fn right_and_then<R2>(self, f: impl FnOnce(R) -> Either<L, R2>) -> Either<L, R2> {
+        match self {
+            Self::Left(l) => Either::Left(l),
+            Self::Right(r) => f(r),
         }
     }
 
+    ///Collapses to a single value, using `f` for an [`Either::Left`] and `g` for an [`Either::Right`]
+    pub fn either<T>(self, f: impl FnOnce(L) -> T, g: impl FnOnce(R) -> T) -> T {
+        match self {
+            Self::Left(l) => f(l),
+            Self::Right(r) => g(r),
+        }
+    }
     //endregion
-    //TODO: Work out more elegant way (maybe macros) to do above and below transformers
 }
 
-impl<L: Clone, R: Clone> Either<L, R> {
-    ///Function to check if this is [`Either::Left`], and if so return [`Some`] of that, else [`None`]
-    pub fn clone_left(&self) -> Option<L> {
-        if let Self::Left(l) = self.clone() {
-            Some(l)
-        } else {
-            None
+impl<L, R: Debug> Either<L, R> {
+    ///Returns the value inside an [`Either::Left`]
+    ///
+    /// # Panics
+    /// If this is an [`Either::Right`], with a message including the value inside
+    #[track_caller]
+    pub fn unwrap_left(self) -> L {
+        match self {
+            Self::Left(l) => l,
+            Self::Right(r) => panic!("called `Either::unwrap_left` on Right({r:?})"),
+        }
+    }
+}
+
+impl<L: Debug, R> Either<L, R> {
+    ///Returns the value inside an [`Either::Right`]
+    ///
+    /// # Panics
+    /// If this is an [`Either::Left`], with a message including the value inside
+    #[track_caller]
+    pub fn unwrap_right(self) -> R {
+        match self {
+            Self::Left(l) => panic!("called `Either::unwrap_right` on Left({l:?})"),
+            Self::Right(r) => r,
         }
     }
+}
 
-    ///Function to check if this is [`Either::Right`], and if so return [`Some`] of that, else [`None`]
-    pub fn clone_right(&self) -> Option<R> {
-        if let Self::Right(r) = self.clone() {
-            Some(r)
-        } else {
-            None
+impl<L, R> Either<Option<L>, R> {
+    ///Pulls the [`Option`] out of an [`Either::Left`] - `Left(None)` becomes [`None`], and everything else becomes [`Some`]
+    #[allow(clippy::missing_const_for_fn)] //issue with destructors
+    pub fn transpose_left(self) -> Option<Either<L, R>> {
+        match self {
+            Self::Left(l) => l.map(Either::Left),
+            Self::Right(r) => Some(Either::Right(r)),
         }
     }
 }
@@ -157,6 +255,13 @@ impl<T> Either<T, T> {
     }
 }
 
+impl<L, R> From<Result<L, R>> for Either<L, R> {
+    ///Converts with [`Ok`] as [`Either::Left`] and [`Err`] as [`Either::Right`]
+    fn from(res: Result<L, R>) -> Self {
+        Self::from_result(res)
+    }
+}
+
 impl<L: Debug, R: Debug> Debug for Either<L, R> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -166,6 +271,16 @@ impl<L: Debug, R: Debug> Debug for Either<L, R> {
     }
 }
 
+impl<L: Display, R: Display> Display for Either<L, R> {
+    ///Displays whichever value is inside
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left(l) => l.fmt(f),
+            Self::Right(r) => r.fmt(f),
+        }
+    }
+}
+
 impl<L: Clone, R: Clone> Clone for Either<L, R> {
     fn clone(&self) -> Self {
         match self {
@@ -174,3 +289,715 @@ impl<L: Clone, R: Clone> Clone for Either<L, R> {
         }
     }
 }
+
+///An [`Either`] of two iterators with the same item is also an iterator, which lets a function return one of two different iterators without boxing.
+///
+///```rust
+/// use burntnail_utils::either::Either;
+///
+/// fn evens_or_all(v: &[u32], only_evens: bool) -> impl Iterator<Item = &u32> {
+///     if only_evens {
+///         Either::Left(v.iter().filter(|x| *x % 2 == 0))
+///     } else {
+///         Either::Right(v.iter())
+///     }
+/// }
+///
+/// assert_eq!(evens_or_all(&[1, 2, 3, 4], true).count(), 2);
+/// assert_eq!(evens_or_all(&[1, 2, 3, 4], false).count(), 4);
+///```
+impl<L: Iterator, R: Iterator<Item = L::Item>> Iterator for Either<L, R> {
+    type Item = L::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Left(l) => l.next(),
+            Self::Right(r) => r.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Left(l) => l.size_hint(),
+            Self::Right(r) => r.size_hint(),
+        }
+    }
+}
+
+impl<L: DoubleEndedIterator, R: DoubleEndedIterator<Item = L::Item>> DoubleEndedIterator
+    for Either<L, R>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Left(l) => l.next_back(),
+            Self::Right(r) => r.next_back(),
+        }
+    }
+}
+
+impl<L: ExactSizeIterator, R: ExactSizeIterator<Item = L::Item>> ExactSizeIterator
+    for Either<L, R>
+{
+}
+
+impl<L, R> Either<L, R> {
+    ///Goes from a pinned reference to an [`Either`] to an [`Either`] of pinned references to whichever side is there - like [`Option::as_pin_ref`]
+    #[must_use]
+    pub const fn as_pin_ref(self: Pin<&Self>) -> Either<Pin<&L>, Pin<&R>> {
+        //SAFETY: the value inside is pinned for as long as `self` is, as it's never moved out of a pinned `Either`
+        unsafe {
+            match self.get_ref() {
+                Self::Left(l) => Either::Left(Pin::new_unchecked(l)),
+                Self::Right(r) => Either::Right(Pin::new_unchecked(r)),
+            }
+        }
+    }
+
+    ///Goes from a pinned mutable reference to an [`Either`] to an [`Either`] of pinned mutable references to whichever side is there - like [`Option::as_pin_mut`]
+    #[must_use]
+    pub const fn as_pin_mut(self: Pin<&mut Self>) -> Either<Pin<&mut L>, Pin<&mut R>> {
+        //SAFETY: the value inside is pinned for as long as `self` is, and nothing here moves it out - `Either` has no `Drop` impl, and is only `Unpin` if both sides are
+        unsafe {
+            match self.get_unchecked_mut() {
+                Self::Left(l) => Either::Left(Pin::new_unchecked(l)),
+                Self::Right(r) => Either::Right(Pin::new_unchecked(r)),
+            }
+        }
+    }
+}
+
+///If both sides are futures with the same output, then the [`Either`] is too - so two branches can give back different futures without boxing them
+///
+///```rust
+/// use burntnail_utils::either::Either;
+/// use std::future::Future;
+///
+/// async fn from_cache() -> u32 {
+///     1
+/// }
+/// async fn from_network(id: u32) -> u32 {
+///     id * 2
+/// }
+///
+/// fn fetch(id: u32, cached: bool) -> impl Future<Output = u32> {
+///     if cached {
+///         Either::Left(from_cache())
+///     } else {
+///         Either::Right(from_network(id))
+///     }
+/// }
+/// # let _ = fetch(1, true);
+///```
+impl<L: Future, R: Future<Output = L::Output>> Future for Either<L, R> {
+    type Output = L::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.as_pin_mut() {
+            Either::Left(l) => l.poll(cx),
+            Either::Right(r) => r.poll(cx),
+        }
+    }
+}
+
+///Splits an iterator of [`Either`]s into all of the [`Either::Left`] values and all of the [`Either::Right`] values, in one pass
+///
+///```rust
+/// use burntnail_utils::either::{partition_either, Either};
+///
+/// let parsed = ["1", "x", "3"].into_iter().map(|s| Either::from(s.parse::<u8>()));
+/// let (numbers, failures) = partition_either(parsed);
+/// assert_eq!(numbers, vec![1, 3]);
+/// assert_eq!(failures.len(), 1);
+///```
+pub fn partition_either<L, R, I: IntoIterator<Item = Either<L, R>>>(iter: I) -> (Vec<L>, Vec<R>) {
+    let mut lefts = vec![];
+    let mut rights = vec![];
+    for e in iter {
+        match e {
+            Either::Left(l) => lefts.push(l),
+            Either::Right(r) => rights.push(r),
+        }
+    }
+    (lefts, rights)
+}
+
+///Module to stop [`EitherIteratorExt`] being implemented outside this crate
+mod sealed {
+    ///Trait which cannot be externally implemented
+    pub trait Sealed {}
+    impl<I: Iterator> Sealed for I {}
+}
+
+///Extension trait for splitting iterators using [`Either`]. Implemented for all iterators.
+///
+///NB: `itertools` has a method with the same name - if both traits are in scope, call this as `EitherIteratorExt::partition_map(iter, f)`
+pub trait EitherIteratorExt: Iterator + sealed::Sealed + Sized {
+    ///Maps every element to an [`Either`], and splits them into the [`Either::Left`] values and the [`Either::Right`] values, in one pass
+    ///
+    ///```rust
+    /// use burntnail_utils::either::{Either, EitherIteratorExt};
+    ///
+    /// let (evens, odds): (Vec<_>, Vec<_>) = (0..5).partition_map(|i| if i % 2 == 0 { Either::Left(i) } else { Either::Right(i) });
+    /// assert_eq!(evens, vec![0, 2, 4]);
+    /// assert_eq!(odds, vec![1, 3]);
+    ///```
+    fn partition_map<L, R>(self, f: impl FnMut(Self::Item) -> Either<L, R>) -> (Vec<L>, Vec<R>) {
+        partition_either(self.map(f))
+    }
+}
+
+impl<I: Iterator> EitherIteratorExt for I {}
+
+impl<L: PartialEq, R: PartialEq> PartialEq for Either<L, R> {
+    ///Only equal if both are on the same side, and the values are equal
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Left(a), Self::Left(b)) => a == b,
+            (Self::Right(a), Self::Right(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<L: Eq, R: Eq> Eq for Either<L, R> {}
+
+impl<L: Hash, R: Hash> Hash for Either<L, R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_left().hash(state);
+        match self {
+            Self::Left(l) => l.hash(state),
+            Self::Right(r) => r.hash(state),
+        }
+    }
+}
+
+impl<L: Default, R> Default for Either<L, R> {
+    ///Defaults to an [`Either::Left`] with the default `L`
+    fn default() -> Self {
+        Self::Left(L::default())
+    }
+}
+
+///Enum which can represent one of three values - like an [`Either`] with an extra variant, so a third branch doesn't need an `Either<A, Either<B, C>>`.
+///
+///Converts to and from that nested form with [`From`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Either3<A, B, C> {
+    ///The first variant of [`Either3`]
+    First(A),
+    ///The second variant of [`Either3`]
+    Second(B),
+    ///The third variant of [`Either3`]
+    Third(C),
+}
+
+crate::variant_accessors!(impl<A, B, C> Either3<A, B, C> {
+    First(A) => is_first, to_first, ref_first, mut_ref_first, clone_first, expect_first;
+    Second(B) => is_second, to_second, ref_second, mut_ref_second, clone_second, expect_second;
+    Third(C) => is_third, to_third, ref_third, mut_ref_third, clone_third, expect_third;
+});
+
+impl<A, B, C> Either3<A, B, C> {
+    ///Transforms the value inside an [`Either3::First`], leaving the others alone
+    pub fn map_first<A2>(self, f: impl FnOnce(A) -> A2) -> Either3<A2, B, C> {
+        self.map_all(f, |b| b, |c| c)
+    }
+
+    ///Transforms the value inside an [`Either3::Second`], leaving the others alone
+    pub fn map_second<B2>(self, f: impl FnOnce(B) -> B2) -> Either3<A, B2, C> {
+        self.map_all(|a| a, f, |c| c)
+    }
+
+    ///Transforms the value inside an [`Either3::Third`], leaving the others alone
+    pub fn map_third<C2>(self, f: impl FnOnce(C) -> C2) -> Either3<A, B, C2> {
+        self.map_all(|a| a, |b| b, f)
+    }
+
+    ///Transforms the value inside, using `f` for an [`Either3::First`], `g` for an [`Either3::Second`] and `h` for an [`Either3::Third`]
+    pub fn map_all<A2, B2, C2>(
+        self,
+        f: impl FnOnce(A) -> A2,
+        g: impl FnOnce(B) -> B2,
+        h: impl FnOnce(C) -> C2,
+    ) -> Either3<A2, B2, C2> {
+        match self {
+            Self::First(a) => Either3::First(f(a)),
+            Self::Second(b) => Either3::Second(g(b)),
+            Self::Third(c) => Either3::Third(h(c)),
+        }
+    }
+
+    ///Collapses to a single value, using `f` for an [`Either3::First`], `g` for an [`Either3::Second`] and `h` for an [`Either3::Third`]
+    pub fn either<T>(
+        self,
+        f: impl FnOnce(A) -> T,
+        g: impl FnOnce(B) -> T,
+        h: impl FnOnce(C) -> T,
+    ) -> T {
+        match self {
+            Self::First(a) => f(a),
+            Self::Second(b) => g(b),
+            Self::Third(c) => h(c),
+        }
+    }
+}
+
+impl<T> Either3<T, T, T> {
+    ///If all three types are the same then this function will return the value, whichever variant it is in
+    #[allow(clippy::missing_const_for_fn)] //issue with destructors
+    pub fn one_type(self) -> T {
+        self.either(|a| a, |b| b, |c| c)
+    }
+}
+
+impl<A, B, C> From<Either<A, Either<B, C>>> for Either3<A, B, C> {
+    ///Converts with [`Either::Left`] as [`Either3::First`], and the inner [`Either`] as the other two
+    fn from(nested: Either<A, Either<B, C>>) -> Self {
+        match nested {
+            Either::Left(a) => Self::First(a),
+            Either::Right(Either::Left(b)) => Self::Second(b),
+            Either::Right(Either::Right(c)) => Self::Third(c),
+        }
+    }
+}
+
+impl<A, B, C> From<Either3<A, B, C>> for Either<A, Either<B, C>> {
+    ///Converts with [`Either3::First`] as [`Either::Left`], and the other two in an inner [`Either`]
+    fn from(either: Either3<A, B, C>) -> Self {
+        either.either(
+            Self::Left,
+            |b| Self::Right(Either::Left(b)),
+            |c| Self::Right(Either::Right(c)),
+        )
+    }
+}
+
+impl<A: Display, B: Display, C: Display> Display for Either3<A, B, C> {
+    ///Displays whichever value is inside
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::First(a) => a.fmt(f),
+            Self::Second(b) => b.fmt(f),
+            Self::Third(c) => c.fmt(f),
+        }
+    }
+}
+
+///An [`Either3`] of three iterators with the same item is also an iterator, eg. for returning a different iterator from each arm of a `match`.
+///
+///```rust
+/// use burntnail_utils::either::Either3;
+///
+/// fn pick(v: &[u32], mode: u8) -> impl Iterator<Item = &u32> {
+///     match mode {
+///         0 => Either3::First(v.iter()),
+///         1 => Either3::Second(v.iter().rev()),
+///         _ => Either3::Third(v.iter().step_by(2)),
+///     }
+/// }
+///
+/// assert_eq!(pick(&[1, 2, 3], 1).next(), Some(&3));
+/// assert_eq!(pick(&[1, 2, 3], 2).count(), 2);
+///```
+impl<A: Iterator, B: Iterator<Item = A::Item>, C: Iterator<Item = A::Item>> Iterator
+    for Either3<A, B, C>
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::First(a) => a.next(),
+            Self::Second(b) => b.next(),
+            Self::Third(c) => c.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::First(a) => a.size_hint(),
+            Self::Second(b) => b.size_hint(),
+            Self::Third(c) => c.size_hint(),
+        }
+    }
+}
+
+impl<
+        A: DoubleEndedIterator,
+        B: DoubleEndedIterator<Item = A::Item>,
+        C: DoubleEndedIterator<Item = A::Item>,
+    > DoubleEndedIterator for Either3<A, B, C>
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::First(a) => a.next_back(),
+            Self::Second(b) => b.next_back(),
+            Self::Third(c) => c.next_back(),
+        }
+    }
+}
+
+impl<
+        A: ExactSizeIterator,
+        B: ExactSizeIterator<Item = A::Item>,
+        C: ExactSizeIterator<Item = A::Item>,
+    > ExactSizeIterator for Either3<A, B, C>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::Arc,
+        task::{Wake, Waker},
+        thread::Thread,
+    };
+
+    ///Wakes up the thread running [`block_on`]
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    ///A tiny executor which polls a future on this thread until it finishes
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = std::pin::pin!(fut);
+        let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(out) => return out,
+                Poll::Pending => std::thread::park(),
+            }
+        }
+    }
+
+    ///A future which is pending `remaining` times (waking itself each time) before giving back `value`
+    struct YieldThen<T> {
+        ///How many more times to be pending
+        remaining: usize,
+        ///What to give back at the end
+        value: Option<T>,
+    }
+
+    ///Makes a [`YieldThen`]
+    const fn yielding<T>(remaining: usize, value: T) -> YieldThen<T> {
+        YieldThen {
+            remaining,
+            value: Some(value),
+        }
+    }
+
+    impl<T: Unpin> Future for YieldThen<T> {
+        type Output = T;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+            if self.remaining == 0 {
+                Poll::Ready(self.value.take().expect("polled after finishing"))
+            } else {
+                self.remaining -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    fn never<T, U>(_: T) -> U {
+        panic!("wrong closure called")
+    }
+
+    #[test]
+    fn maps() {
+        let left: Either<i32, &str> = Either::Left(2);
+        let right: Either<i32, &str> = Either::Right("two");
+
+        assert_eq!(left.clone().map_left(|l| l * 10).to_left(), Some(20));
+        assert_eq!(
+            right.clone().map_left(never::<_, i32>).to_right(),
+            Some("two")
+        );
+
+        assert_eq!(right.clone().map_right(str::len).to_right(), Some(3));
+        assert_eq!(left.clone().map_right(never::<_, usize>).to_left(), Some(2));
+
+        assert_eq!(
+            left.clone().map_either(|l| l + 1, never::<_, ()>).to_left(),
+            Some(3)
+        );
+        assert_eq!(
+            right
+                .clone()
+                .map_either(never::<_, ()>, str::len)
+                .to_right(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn unwraps_and_defaults() {
+        let left: Either<i32, &str> = Either::Left(2);
+        let right: Either<i32, &str> = Either::Right("two");
+
+        assert_eq!(left.clone().unwrap_left(), 2);
+        assert_eq!(right.clone().expect_right("should be right"), "two");
+        assert_eq!(left.clone().left_or(0), 2);
+        assert_eq!(right.clone().left_or(0), 0);
+        assert_eq!(
+            right
+                .clone()
+                .left_or_else(|r| if r == "two" { 2 } else { 0 }),
+            2
+        );
+        assert_eq!(left.clone().right_or("none"), "none");
+        assert_eq!(left.right_or_else(|_| "from left"), "from left");
+        assert_eq!(right.right_or_else(never), "two");
+    }
+
+    #[test]
+    #[should_panic(expected = "called `Either::unwrap_left` on Right(\"oops\")")]
+    fn unwrap_left_panic_message() {
+        Either::<i32, &str>::Right("oops").unwrap_left();
+    }
+
+    #[test]
+    #[should_panic(expected = "wanted a number: Left(5)")]
+    fn expect_right_panic_message() {
+        Either::<i32, &str>::Left(5).expect_right("wanted a number");
+    }
+
+    #[test]
+    fn result_round_trips() {
+        let ok: Result<i32, &str> = Ok(1);
+        let err: Result<i32, &str> = Err("bad");
+
+        assert_eq!(Either::from(ok).ok_left(), ok);
+        assert_eq!(Either::from_result(err).ok_left(), err);
+        assert_eq!(Either::from(ok).ok_right(), Err(1));
+        assert_eq!(Either::from(err).into_options(), (None, Some("bad")));
+    }
+
+    #[test]
+    fn equality_and_hashing() {
+        use std::collections::HashSet;
+
+        let same_types: [Either<i32, i32>; 3] =
+            [Either::Left(1), Either::Right(1), Either::Left(1)];
+        assert_ne!(same_types[0], same_types[1]);
+        assert_eq!(same_types[0], same_types[2]);
+        assert_eq!(HashSet::from(same_types).len(), 2);
+
+        assert_eq!(Either::<i32, &str>::default(), Either::Left(0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips() {
+        let left: Either<i32, String> = Either::Left(5);
+        let right: Either<i32, String> = Either::Right("inline".to_string());
+
+        let left_json = serde_json::to_string(&left).unwrap();
+        let right_json = serde_json::to_string(&right).unwrap();
+        assert_eq!(left_json, r#"{"Left":5}"#);
+        assert_eq!(right_json, r#"{"Right":"inline"}"#);
+
+        assert_eq!(
+            serde_json::from_str::<Either<i32, String>>(&left_json).unwrap(),
+            left
+        );
+        assert_eq!(
+            serde_json::from_str::<Either<i32, String>>(&right_json).unwrap(),
+            right
+        );
+    }
+
+    #[test]
+    fn iterates_either_side() {
+        let make = |left: bool| -> Either<std::ops::Range<i32>, std::vec::IntoIter<i32>> {
+            if left {
+                Either::Left(0..3)
+            } else {
+                Either::Right(vec![10, 20].into_iter())
+            }
+        };
+
+        assert_eq!(make(true).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(make(false).rev().collect::<Vec<_>>(), vec![20, 10]);
+        assert_eq!(make(true).len(), 3);
+        assert_eq!(make(false).size_hint(), (2, Some(2)));
+    }
+
+    #[test]
+    fn chains_short_circuit() {
+        fn parse(s: &str) -> Either<i32, String> {
+            s.parse().map_err(|_| format!("{s} isn't a number")).into()
+        }
+        fn halve(n: i32) -> Either<i32, String> {
+            if n % 2 == 0 {
+                Either::Left(n / 2)
+            } else {
+                Either::Right(format!("{n} is odd"))
+            }
+        }
+
+        assert_eq!(
+            parse("12").left_and_then(halve).left_and_then(halve),
+            Either::Left(3)
+        );
+        assert_eq!(
+            parse("6").left_and_then(halve).left_and_then(halve),
+            Either::Right("3 is odd".to_string())
+        );
+        assert_eq!(
+            parse("x")
+                .left_and_then(never::<_, Either<i32, _>>)
+                .left_and_then(never::<_, Either<i32, _>>),
+            Either::Right("x isn't a number".to_string())
+        );
+
+        assert_eq!(
+            Either::<i32, i32>::Right(1).right_and_then(|r| Either::Right(r + 1)),
+            Either::Right(2)
+        );
+    }
+
+    #[test]
+    fn transposes() {
+        assert_eq!(
+            Either::<Option<i32>, ()>::Left(Some(1)).transpose_left(),
+            Some(Either::Left(1))
+        );
+        assert_eq!(Either::<Option<i32>, ()>::Left(None).transpose_left(), None);
+        assert_eq!(
+            Either::<Option<i32>, ()>::Right(()).transpose_left(),
+            Some(Either::Right(()))
+        );
+    }
+
+    #[test]
+    fn collapses() {
+        let left: Either<i32, &str> = Either::Left(2);
+        let right: Either<i32, &str> = Either::Right("two");
+
+        assert_eq!(left.either(|l| l.to_string(), never), "2");
+        assert_eq!(right.either(never, str::to_uppercase), "TWO");
+    }
+
+    #[test]
+    fn futures() {
+        let left: Either<_, YieldThen<u32>> = Either::Left(yielding(3, 1_u32));
+        let right: Either<YieldThen<u32>, _> = Either::Right(yielding(2, 2_u32));
+        assert_eq!(block_on(left), 1);
+        assert_eq!(block_on(right), 2);
+
+        //async blocks aren't `Unpin`, so this needs the pin projection
+        let pick = |use_left: bool| {
+            if use_left {
+                Either::Left(async { yielding(1, "left").await })
+            } else {
+                Either::Right(async {
+                    let a = yielding(2, 20).await;
+                    let b = yielding(1, 22).await;
+                    if a + b == 42 {
+                        "right"
+                    } else {
+                        "wrong"
+                    }
+                })
+            }
+        };
+        assert_eq!(block_on(pick(true)), "left");
+        assert_eq!(block_on(pick(false)), "right");
+    }
+
+    #[test]
+    fn pin_projection() {
+        let mut either: Either<u8, String> = Either::Right("pinned".to_string());
+
+        let pinned = Pin::new(&either);
+        assert_eq!(
+            pinned.as_pin_ref().map_right(|r| r.len()).to_right(),
+            Some(6)
+        );
+
+        if let Either::Right(mut r) = Pin::new(&mut either).as_pin_mut() {
+            r.push('!');
+        }
+        assert_eq!(either.to_right().as_deref(), Some("pinned!"));
+    }
+
+    #[test]
+    fn either3_accessors_and_maps() {
+        let mut first: Either3<i32, &str, char> = Either3::First(2);
+        let second: Either3<i32, &str, char> = Either3::Second("two");
+        let third: Either3<i32, &str, char> = Either3::Third('c');
+
+        assert!(first.is_first() && !first.is_second() && !first.is_third());
+        assert_eq!(second.ref_second(), Some(&"two"));
+        assert_eq!(third.to_first(), None);
+        *first.mut_ref_first().unwrap() += 1;
+        assert_eq!(first.clone_first(), Some(3));
+        assert_eq!(third.expect_third("should be third"), 'c');
+
+        assert_eq!(first.map_first(|a| a * 10), Either3::First(30));
+        assert_eq!(first.map_second(never::<_, usize>), Either3::First(3));
+        assert_eq!(second.map_second(str::len), Either3::Second(3));
+        assert_eq!(
+            third.map_third(|c| c.to_ascii_uppercase()),
+            Either3::Third('C')
+        );
+        assert_eq!(
+            second.map_all(never::<_, ()>, str::len, never::<_, ()>),
+            Either3::Second(3)
+        );
+        assert_eq!(third.either(|_| 0, |_| 1, |c| c as usize), 'c' as usize);
+
+        let same: Either3<u8, u8, u8> = Either3::Third(7);
+        assert_eq!(same.one_type(), 7);
+
+        assert_eq!(first.to_string(), "3");
+        assert_eq!(second.to_string(), "two");
+        assert_eq!(Either::<u8, &str>::Right("right").to_string(), "right");
+    }
+
+    #[test]
+    #[should_panic(expected = "wanted the first: Second(\"two\")")]
+    fn either3_expect_panics() {
+        let second: Either3<i32, &str, char> = Either3::Second("two");
+        second.expect_first("wanted the first");
+    }
+
+    #[test]
+    fn either3_nested_round_trip() {
+        let all: [Either3<u8, &str, char>; 3] =
+            [Either3::First(1), Either3::Second("2"), Either3::Third('3')];
+
+        for e in all {
+            let nested: Either<u8, Either<&str, char>> = e.into();
+            assert_eq!(Either3::from(nested.clone()), e);
+            assert_eq!(nested.is_left(), e.is_first());
+        }
+        assert_eq!(
+            Either3::from(Either::<u8, Either<&str, char>>::Right(Either::Right('x'))),
+            Either3::Third('x')
+        );
+    }
+
+    #[test]
+    fn either3_iterators() {
+        let v = [1, 2, 3, 4];
+        let pick = |mode| match mode {
+            0 => Either3::First(v.iter()),
+            1 => Either3::Second(v.iter().skip(1)),
+            _ => Either3::Third(v.iter().take(1)),
+        };
+
+        assert_eq!(pick(0).copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(pick(1).rev().copied().collect::<Vec<_>>(), vec![4, 3, 2]);
+        assert_eq!(pick(2).len(), 1);
+    }
+}
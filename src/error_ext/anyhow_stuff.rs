@@ -1,8 +1,17 @@
-use crate::error_types::{BError, BResult, Contextable};
+use super::LogLevel;
+use crate::{
+    error_types::{BError, BResult, Contextable},
+    time_based_structs::{clock::Clock, rate_limited_logger::RateLimitedLogger},
+};
 use std::{
     any::Any,
+    env::VarError,
     fmt::Display,
-    sync::{LockResult, Mutex, MutexGuard},
+    sync::{
+        mpsc::SendError, LockResult, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+        TryLockError, TryLockResult,
+    },
+    thread::JoinHandle,
 };
 
 ///Extension trait for errors to quickly do things
@@ -11,18 +20,202 @@ pub trait ErrorExt<T> {
     fn warn(self);
     ///If `Err` write to `error!`
     fn error(self);
-    ///If `Err` write to error out if not using stdlib logging (eg. `error!`) and then to [`panic!`] with the error.
+    ///If `Err` write to `error!`/[`eprintln!`] and then follow the [`super::ExitStrategy`] (by default, [`std::process::exit`] with code 1).
     fn error_exit(self);
-    ///If `Err` write to error (eg. `error!` and [`eprintln!`]) and then to [`std::process::exit`] with code 1.
+    ///If `Err` write to [`eprintln!`] and then follow the [`super::ExitStrategy`] (by default, [`std::process::exit`] with code 1).
     fn eprint_exit(self);
-    ///If `Err` write to `error!`/[`eprintln!`] and [`std::process::exit`] with code 1, else return `Ok` value
+    ///If `Err` write to `error!`/[`eprintln!`] and then follow the [`super::ExitStrategy`] (by default, [`std::process::exit`] with code 1), else return `Ok` value
     fn unwrap_log_error(self) -> T;
+    ///If `Err` write to `warn!` and return [`None`], else return [`Some`] of the `Ok` value
+    fn warn_and_continue(self) -> Option<T>;
+    ///If `Err` write to `error!` and return [`None`], else return [`Some`] of the `Ok` value
+    fn error_and_continue(self) -> Option<T>;
+    ///Like [`Result::ok`], but if `Err` write to `error!` first - eg. `let save = load_save().ok_logged().unwrap_or_default();`. Same as [`ErrorExt::error_and_continue`].
+    fn ok_logged(self) -> Option<T>;
+    ///Like [`Result::ok`], but if `Err` write to `warn!` first - eg. `let cfg = load_config().ok_warned().unwrap_or_default();`. Same as [`ErrorExt::warn_and_continue`].
+    fn ok_warned(self) -> Option<T>;
+    ///If `Err` write to `error!`, and then give back `self` unchanged for more handling
+    #[must_use]
+    fn log_err(self) -> Self;
+    ///If `Err` write to `info!`
+    fn info(self);
+    ///If `Err` write to `debug!` - eg. for expected failures which have a fallback
+    fn debug(self);
+    ///If `Err` write at the given level. Without `tracing`, lines are prefixed with the level name.
+    fn log_at(self, level: LogLevel);
+    ///If `Err` write at the given level and return [`None`], else return [`Some`] of the `Ok` value
+    fn unwrap_log_at(self, level: LogLevel) -> Option<T>;
+    ///If `Err` write to `warn!`, with `msg` attached to say where the error came from
+    fn warn_context(self, msg: impl Display);
+    ///If `Err` write to `error!`, with `msg` attached to say where the error came from
+    fn error_context(self, msg: impl Display);
+    ///If `Err` write to `warn!`, with the context from `f` attached. `f` is only called if there is an error.
+    fn warn_with_context<C: Display>(self, f: impl FnOnce() -> C);
+    ///If `Err` write to `warn!`, but at most once per interval for `key` - eg. for errors which happen every frame. See [`RateLimitedLogger`].
+    fn warn_rate_limited<C: Clock + Clone>(self, limiter: &mut RateLimitedLogger<C>, key: &str);
+}
+
+///Module to stop [`AnyErrorExt`] being implemented outside this crate
+mod sealed {
+    ///Trait which cannot be externally implemented
+    pub trait Sealed {}
+    impl<T, E: std::error::Error + Send + Sync + 'static> Sealed for Result<T, E> {}
+}
+
+///The same as [`ErrorExt`], but for any `Result<T, E>` where `E` is a [`std::error::Error`], so there's no need to call [`ToErr::ae`] first.
+///
+///This is a separate trait so it doesn't overlap with [`ErrorExt`] on [`BResult`]. All of the methods convert the error and then do the same as their [`ErrorExt`] counterparts.
+pub trait AnyErrorExt<T>: sealed::Sealed {
+    ///See [`ErrorExt::warn`]
+    fn warn(self);
+    ///See [`ErrorExt::error`]
+    fn error(self);
+    ///See [`ErrorExt::error_exit`]
+    fn error_exit(self);
+    ///See [`ErrorExt::eprint_exit`]
+    fn eprint_exit(self);
+    ///See [`ErrorExt::unwrap_log_error`]
+    fn unwrap_log_error(self) -> T;
+    ///See [`ErrorExt::warn_and_continue`]
+    fn warn_and_continue(self) -> Option<T>;
+    ///See [`ErrorExt::error_and_continue`]
+    fn error_and_continue(self) -> Option<T>;
+    ///See [`ErrorExt::ok_logged`]
+    fn ok_logged(self) -> Option<T>;
+    ///See [`ErrorExt::ok_warned`]
+    fn ok_warned(self) -> Option<T>;
+    ///See [`ErrorExt::info`]
+    fn info(self);
+    ///See [`ErrorExt::debug`]
+    fn debug(self);
+    ///See [`ErrorExt::log_at`]
+    fn log_at(self, level: LogLevel);
+    ///See [`ErrorExt::unwrap_log_at`]
+    fn unwrap_log_at(self, level: LogLevel) -> Option<T>;
+    ///See [`ErrorExt::warn_context`]
+    fn warn_context(self, msg: impl Display);
+    ///See [`ErrorExt::error_context`]
+    fn error_context(self, msg: impl Display);
+    ///See [`ErrorExt::warn_with_context`]
+    fn warn_with_context<C: Display>(self, f: impl FnOnce() -> C);
+    ///See [`ErrorExt::warn_rate_limited`]
+    fn warn_rate_limited<C: Clock + Clone>(self, limiter: &mut RateLimitedLogger<C>, key: &str);
+}
+
+impl<T, E: std::error::Error + Send + Sync + 'static> AnyErrorExt<T> for Result<T, E> {
+    fn warn(self) {
+        self.ae().warn();
+    }
+
+    fn error(self) {
+        self.ae().error();
+    }
+
+    fn error_exit(self) {
+        self.ae().error_exit();
+    }
+
+    fn eprint_exit(self) {
+        self.ae().eprint_exit();
+    }
+
+    fn unwrap_log_error(self) -> T {
+        self.ae().unwrap_log_error()
+    }
+
+    fn warn_and_continue(self) -> Option<T> {
+        self.ae().warn_and_continue()
+    }
+
+    fn error_and_continue(self) -> Option<T> {
+        self.ae().error_and_continue()
+    }
+
+    fn ok_logged(self) -> Option<T> {
+        self.ae().ok_logged()
+    }
+
+    fn ok_warned(self) -> Option<T> {
+        self.ae().ok_warned()
+    }
+
+    fn info(self) {
+        self.ae().info();
+    }
+
+    fn debug(self) {
+        self.ae().debug();
+    }
+
+    fn log_at(self, level: LogLevel) {
+        self.ae().log_at(level);
+    }
+
+    fn unwrap_log_at(self, level: LogLevel) -> Option<T> {
+        self.ae().unwrap_log_at(level)
+    }
+
+    fn warn_context(self, msg: impl Display) {
+        self.ae().warn_context(msg);
+    }
+
+    fn error_context(self, msg: impl Display) {
+        self.ae().error_context(msg);
+    }
+
+    fn warn_with_context<C: Display>(self, f: impl FnOnce() -> C) {
+        self.ae().warn_with_context(f);
+    }
+
+    fn warn_rate_limited<C: Clock + Clone>(self, limiter: &mut RateLimitedLogger<C>, key: &str) {
+        self.ae().warn_rate_limited(limiter, key);
+    }
+}
+
+///Extension trait for logging [`None`]s, the same way as [`ErrorExt`] does for errors. The message is required, as there is no error to describe what went wrong.
+pub trait OptionExt<T> {
+    ///If [`None`] write `msg` to `warn!`, and then give back `self` unchanged
+    #[must_use]
+    fn warn_none(self, msg: impl Display) -> Self;
+    ///If [`None`] write `msg` to `error!`, and then give back `self` unchanged
+    #[must_use]
+    fn error_none(self, msg: impl Display) -> Self;
+    ///If [`None`] behave like [`ErrorExt::unwrap_log_error`] with `msg`, else return the [`Some`] value
+    fn unwrap_log_none(self, msg: impl Display) -> T;
 }
 
 ///Utility trait for Mutexes
 pub trait MutexExt<T> {
     ///Function to unlock or panic using `error!`
     fn lock_panic<C: Display + Send + Sync + 'static>(&self, msg: C) -> MutexGuard<T>;
+    ///Function to lock, and if the mutex was poisoned by a thread panicking, write to `warn!` and carry on with the data anyway
+    fn lock_recover(&self) -> MutexGuard<'_, T>;
+    ///Function to lock, and if the mutex was poisoned by a thread panicking, write to `warn!` and let `f` repair the data before carrying on. The poison is then cleared.
+    fn lock_or_else(&self, f: impl FnOnce(&mut T)) -> MutexGuard<'_, T>;
+}
+
+///Utility trait for [`RwLock`]s, matching [`MutexExt`]
+pub trait RwLockExt<T> {
+    ///Function to get read access or panic using `error!`
+    fn read_panic<C: Display + Send + Sync + 'static>(&self, msg: C) -> RwLockReadGuard<'_, T>;
+    ///Function to get write access or panic using `error!`
+    fn write_panic<C: Display + Send + Sync + 'static>(&self, msg: C) -> RwLockWriteGuard<'_, T>;
+    ///Function to try to get read access without blocking. If the lock is contended or poisoned, writes `msg` to `warn!` and returns [`None`]
+    fn try_read_logged<C: Display + Send + Sync + 'static>(
+        &self,
+        msg: C,
+    ) -> Option<RwLockReadGuard<'_, T>>;
+    ///Function to try to get write access without blocking. If the lock is contended or poisoned, writes `msg` to `warn!` and returns [`None`]
+    fn try_write_logged<C: Display + Send + Sync + 'static>(
+        &self,
+        msg: C,
+    ) -> Option<RwLockWriteGuard<'_, T>>;
+}
+
+///Utility trait for thread handles
+pub trait JoinHandleExt<T> {
+    ///Joins the thread, and if it panicked write the panic message to `error!` and return [`None`]
+    fn join_logged(self) -> Option<T>;
 }
 
 ///Creates a trait with a function `ae(self) -> crate::error_types::Result`
@@ -44,10 +237,12 @@ macro_rules! to_error_result_trait {
     };
 }
 to_error_result_trait!(
-    ToErr => "Trait to turn [`std::error::Error`] to [`Error`]",
-    ToNotErr => "Trait to turn non-errors (like [`Option`]) to [`Error`]",
-    ToPoisonErr => "Trait to turn `Box<dyn Any + Send + 'static>` to [`Error`]",
-    ToThreadErr => "Trait to turn [`std::sync::LockResult`] to [`Result`]"
+    ToErr => "Trait to turn [`std::error::Error`] to [`BError`](crate::error_types::BError)",
+    ToNotErr => "Trait to turn non-errors (like [`Option`]) to [`BError`](crate::error_types::BError)",
+    ToPoisonErr => "Trait to turn [`std::sync::LockResult`] to [`Result`]",
+    ToThreadErr => "Trait to turn `Box<dyn Any + Send + 'static>` to [`BError`](crate::error_types::BError)",
+    ToTryLockErr => "Trait to turn [`std::sync::TryLockResult`] to [`Result`]",
+    ToBoxedErr => "Trait to turn `Box<dyn Error + Send + Sync>` to [`BError`](crate::error_types::BError)"
 );
 //To avoid overlapping trait bounds
 
@@ -68,6 +263,27 @@ impl<T> ToNotErr<T> for Option<T> {
     }
 }
 
+impl<T> OptionExt<T> for Option<T> {
+    fn warn_none(self, msg: impl Display) -> Self {
+        if self.is_none() {
+            Err::<(), _>(BError::msg(msg.to_string())).warn();
+        }
+        self
+    }
+
+    fn error_none(self, msg: impl Display) -> Self {
+        if self.is_none() {
+            Err::<(), _>(BError::msg(msg.to_string())).error();
+        }
+        self
+    }
+
+    fn unwrap_log_none(self, msg: impl Display) -> T {
+        self.ok_or_else(|| BError::msg(msg.to_string()))
+            .unwrap_log_error()
+    }
+}
+
 impl<T, E: std::error::Error + Send + Sync + 'static> ToErr<T> for std::result::Result<T, E> {
     fn ae(self) -> BResult<T> {
         self.map_err(BError::new)
@@ -86,7 +302,7 @@ impl<T, E: std::error::Error + Send + Sync + 'static> ToErr<T> for std::result::
 }
 impl<T> ToThreadErr<T> for std::result::Result<T, Box<dyn Any + Send + 'static>> {
     fn ae(self) -> BResult<T> {
-        self.map_err(|_| BError::msg("Error joining thread"))
+        self.map_err(|payload| BError::msg(panic_message(payload.as_ref())))
     }
 
     fn unwrap_log_error_with_context<C: Display + Send + Sync + 'static, F: FnOnce() -> C>(
@@ -100,6 +316,19 @@ impl<T> ToThreadErr<T> for std::result::Result<T, Box<dyn Any + Send + 'static>>
         self.ae().context(c).unwrap_log_error()
     }
 }
+
+///Gets the message out of a panic payload. `panic!` only ever gives a `&str` or a `String`, and the concrete type name can't be recovered from a `dyn Any` for anything else.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .map_or_else(
+            || "Thread panicked with a non-string payload".to_string(),
+            |s| format!("Thread panicked: {s}"),
+        )
+}
+
 impl<T> ToPoisonErr<T> for LockResult<T> {
     fn ae(self) -> BResult<T> {
         self.map_err(|e| BError::msg(format!("{}", e)))
@@ -117,8 +346,364 @@ impl<T> ToPoisonErr<T> for LockResult<T> {
     }
 }
 
+impl<T, G> ToTryLockErr<T> for Result<T, TryLockError<G>> {
+    fn ae(self) -> BResult<T> {
+        self.map_err(|e| match e {
+            TryLockError::WouldBlock => BError::msg("Lock contended"),
+            TryLockError::Poisoned(p) => BError::msg(p.to_string()),
+        })
+    }
+
+    fn unwrap_log_error_with_context<C: Display + Send + Sync + 'static, F: FnOnce() -> C>(
+        self,
+        f: F,
+    ) -> T {
+        self.ae().with_context(f).unwrap_log_error()
+    }
+
+    fn unwap_log_error_context<C: Display + Send + Sync + 'static>(self, c: C) -> T {
+        self.ae().context(c).unwrap_log_error()
+    }
+}
+
+///Wrapper to let a `Box<dyn Error>` be used as an [`Error`](std::error::Error), as the box itself isn't one
+#[derive(Debug)]
+struct BoxedError(Box<dyn std::error::Error + Send + Sync + 'static>);
+
+impl Display for BoxedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for BoxedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+impl<T> ToBoxedErr<T> for Result<T, Box<dyn std::error::Error + Send + Sync + 'static>> {
+    fn ae(self) -> BResult<T> {
+        self.map_err(|e| BError::new(BoxedError(e)))
+    }
+
+    fn unwrap_log_error_with_context<C: Display + Send + Sync + 'static, F: FnOnce() -> C>(
+        self,
+        f: F,
+    ) -> T {
+        self.ae().with_context(f).unwrap_log_error()
+    }
+
+    fn unwap_log_error_context<C: Display + Send + Sync + 'static>(self, c: C) -> T {
+        self.ae().context(c).unwrap_log_error()
+    }
+}
+
+///Trait to turn [`SendError`] to [`BError`](crate::error_types::BError), even if the unsent value isn't `Sync`.
+///
+///If the value is `Send + Sync`, [`ToErr::ae`] works too - this uses different method names so the two don't clash. The unsent value is dropped.
+pub trait ToSendErr<T> {
+    ///Converter function to [`Result`]
+    #[allow(clippy::missing_errors_doc)]
+    fn ae_send(self) -> BResult<T>;
+    ///Function that is the same as [`ErrorExt::unwrap_log_error`] only this includes an easy way to get context
+    fn unwrap_log_send_error_with_context<C: Display + Send + Sync + 'static, F: FnOnce() -> C>(
+        self,
+        f: F,
+    ) -> T;
+    ///Function that is the same as [`ErrorExt::unwrap_log_error`] only this includes an easy way to get context
+    fn unwrap_log_send_error_context<C: Display + Send + Sync + 'static>(self, c: C) -> T;
+}
+
+impl<T, U> ToSendErr<T> for Result<T, SendError<U>> {
+    fn ae_send(self) -> BResult<T> {
+        self.map_err(|e| BError::msg(e.to_string()))
+    }
+
+    fn unwrap_log_send_error_with_context<C: Display + Send + Sync + 'static, F: FnOnce() -> C>(
+        self,
+        f: F,
+    ) -> T {
+        self.ae_send().with_context(f).unwrap_log_error()
+    }
+
+    fn unwrap_log_send_error_context<C: Display + Send + Sync + 'static>(self, c: C) -> T {
+        self.ae_send().context(c).unwrap_log_error()
+    }
+}
+
+///Extension trait for the results of [`std::env::var`]
+pub trait VarErrorExt {
+    ///Converts to a [`Result`], keeping a missing variable separate from an invalid one.
+    ///
+    /// # Errors
+    /// If the variable is set, but isn't valid unicode
+    fn ae_opt(self) -> BResult<Option<String>>;
+}
+
+impl VarErrorExt for Result<String, VarError> {
+    fn ae_opt(self) -> BResult<Option<String>> {
+        match self {
+            Ok(s) => Ok(Some(s)),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(e) => Err(BError::new(e)),
+        }
+    }
+}
+
 impl<T> MutexExt<T> for Mutex<T> {
     fn lock_panic<C: Display + Send + Sync + 'static>(&self, msg: C) -> MutexGuard<T> {
         self.lock().ae().context(msg).unwrap_log_error()
     }
+
+    fn lock_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|p| {
+            Err::<(), _>(BError::msg("Mutex was poisoned, recovering")).warn();
+            p.into_inner()
+        })
+    }
+
+    fn lock_or_else(&self, f: impl FnOnce(&mut T)) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(|p| {
+            Err::<(), _>(BError::msg("Mutex was poisoned, repairing")).warn();
+            let mut guard = p.into_inner();
+            f(&mut guard);
+            self.clear_poison();
+            guard
+        })
+    }
+}
+
+///Turns a [`TryLockResult`] into an [`Option`], logging why if the lock couldn't be got
+fn base_try_logged<G, C: Display + Send + Sync + 'static>(
+    res: TryLockResult<G>,
+    msg: C,
+) -> Option<G> {
+    ToTryLockErr::ae(res).context(msg).warn_and_continue()
+}
+
+impl<T> RwLockExt<T> for RwLock<T> {
+    fn read_panic<C: Display + Send + Sync + 'static>(&self, msg: C) -> RwLockReadGuard<'_, T> {
+        self.read().ae().context(msg).unwrap_log_error()
+    }
+
+    fn write_panic<C: Display + Send + Sync + 'static>(&self, msg: C) -> RwLockWriteGuard<'_, T> {
+        self.write().ae().context(msg).unwrap_log_error()
+    }
+
+    fn try_read_logged<C: Display + Send + Sync + 'static>(
+        &self,
+        msg: C,
+    ) -> Option<RwLockReadGuard<'_, T>> {
+        base_try_logged(self.try_read(), msg)
+    }
+
+    fn try_write_logged<C: Display + Send + Sync + 'static>(
+        &self,
+        msg: C,
+    ) -> Option<RwLockWriteGuard<'_, T>> {
+        base_try_logged(self.try_write(), msg)
+    }
+}
+
+impl<T> JoinHandleExt<T> for JoinHandle<T> {
+    fn join_logged(self) -> Option<T> {
+        self.join().ae().error_and_continue()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn continuing_keeps_values() {
+        let ok: BResult<i32> = Ok(1);
+        assert_eq!(ok.warn_and_continue(), Some(1));
+        assert_eq!(
+            BResult::<i32>::Err(BError::msg("nope")).error_and_continue(),
+            None
+        );
+
+        assert_eq!(BResult::Ok(3).ok_logged(), Some(3));
+        assert_eq!(BResult::<i32>::Err(BError::msg("logged")).ok_logged(), None);
+        assert_eq!(
+            BResult::<i32>::Err(BError::msg("warned"))
+                .ok_warned()
+                .unwrap_or_default(),
+            0
+        );
+
+        let err = BResult::<i32>::Err(BError::msg("still here")).log_err();
+        assert_eq!(err.unwrap_err().to_string(), "still here");
+        assert_eq!(BResult::Ok(2).log_err().ok(), Some(2));
+    }
+
+    #[test]
+    fn rw_lock_access() {
+        let lock = RwLock::new(5);
+        assert_eq!(*lock.read_panic("reading"), 5);
+        *lock.write_panic("writing") += 1;
+
+        let reader = lock.read_panic("reading");
+        assert_eq!(lock.try_read_logged("second reader").map(|g| *g), Some(6));
+        assert!(lock.try_write_logged("contended writer").is_none());
+        drop(reader);
+
+        assert!(lock.try_write_logged("free writer").is_some());
+    }
+
+    #[test]
+    fn recovering_from_poison() {
+        use std::sync::Arc;
+
+        let poison = |m: &Arc<Mutex<Vec<i32>>>| {
+            let m = m.clone();
+            assert!(std::thread::spawn(move || {
+                let _guard = m.lock().unwrap();
+                panic!("poisoning the mutex");
+            })
+            .join()
+            .is_err());
+        };
+
+        let m = Arc::new(Mutex::new(vec![1, 2]));
+        poison(&m);
+        assert!(m.is_poisoned());
+        assert_eq!(*m.lock_recover(), vec![1, 2]);
+        assert!(m.is_poisoned());
+
+        let mut repaired = false;
+        m.lock_or_else(|v| {
+            v.clear();
+            repaired = true;
+        })
+        .push(3);
+        assert!(repaired);
+        assert!(!m.is_poisoned());
+        assert_eq!(
+            *m.lock_or_else(|_| panic!("not poisoned any more")),
+            vec![3]
+        );
+    }
+
+    #[test]
+    fn any_error_without_ae() {
+        let io: Result<i32, std::io::Error> = Err(std::io::Error::other("disk on fire"));
+        assert_eq!(io.warn_and_continue(), None);
+        assert_eq!("7".parse::<u8>().ok_warned(), Some(7));
+        assert_eq!("x".parse::<u8>().ok_logged(), None);
+
+        let parsed = "12".parse::<u8>();
+        assert_eq!(parsed.unwrap_log_error(), 12);
+        "x".parse::<u8>().warn();
+
+        //both traits in scope at once doesn't make anyhow results ambiguous
+        assert_eq!(BResult::Ok(1).warn_and_continue(), Some(1));
+    }
+
+    #[test]
+    fn logging_at_levels() {
+        BResult::<()>::Err(BError::msg("cache miss")).debug();
+        BResult::<()>::Err(BError::msg("falling back")).info();
+        BResult::<()>::Err(BError::msg("levelled")).log_at(LogLevel::Warn);
+        assert_eq!(
+            BResult::<i32>::Err(BError::msg("x")).unwrap_log_at(LogLevel::Debug),
+            None
+        );
+        assert_eq!(BResult::Ok(4).unwrap_log_at(LogLevel::Error), Some(4));
+        assert!(LogLevel::Debug < LogLevel::Error);
+    }
+
+    #[test]
+    fn logging_with_context() {
+        BResult::<()>::Err(BError::msg("missing")).warn_context("loading save file");
+        BResult::<()>::Err(BError::msg("missing")).error_context(format_args!("slot {}", 3));
+        BResult::<()>::Ok(()).warn_with_context(|| -> &str { panic!("should be lazy") });
+
+        let mut called = false;
+        BResult::<()>::Err(BError::msg("missing")).warn_with_context(|| {
+            called = true;
+            "loading config"
+        });
+        assert!(called);
+
+        "not a number"
+            .parse::<u8>()
+            .warn_context("parsing the port");
+    }
+
+    #[test]
+    fn thread_panics_keep_their_message() {
+        let id = 3;
+        let res = std::thread::spawn(move || -> u8 { panic!("worker {id} died") }).join();
+        assert_eq!(
+            res.ae().unwrap_err().to_string(),
+            "Thread panicked: worker 3 died"
+        );
+
+        let res = std::thread::spawn(|| -> u8 { panic!("static message") }).join();
+        assert!(res.ae().unwrap_err().to_string().contains("static message"));
+
+        let res = std::thread::spawn(|| -> u8 { std::panic::panic_any(5_u8) }).join();
+        assert_eq!(
+            res.ae().unwrap_err().to_string(),
+            "Thread panicked with a non-string payload"
+        );
+    }
+
+    #[test]
+    fn join_logged() {
+        assert_eq!(std::thread::spawn(|| 5).join_logged(), Some(5));
+        assert_eq!(
+            std::thread::spawn(|| -> u8 { panic!("oh no") }).join_logged(),
+            None
+        );
+    }
+
+    #[test]
+    fn awkward_errors_keep_their_messages() {
+        let (tx, rx) = std::sync::mpsc::channel::<std::cell::Cell<u8>>();
+        drop(rx);
+        assert_eq!(
+            tx.send(std::cell::Cell::new(1))
+                .ae_send()
+                .unwrap_err()
+                .to_string(),
+            "sending on a closed channel"
+        );
+
+        let mutex = Mutex::new(1);
+        let guard = mutex.lock().unwrap();
+        assert_eq!(
+            mutex.try_lock().ae().unwrap_err().to_string(),
+            "Lock contended"
+        );
+        drop(guard);
+        assert_eq!(*mutex.try_lock().ae().unwrap(), 1);
+
+        let boxed: Result<(), Box<dyn std::error::Error + Send + Sync>> = Err("bad config".into());
+        assert_eq!(boxed.ae().unwrap_err().to_string(), "bad config");
+
+        assert_eq!(
+            std::env::var("BURNTNAIL_UTILS_DEFINITELY_NOT_SET")
+                .ae_opt()
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            std::env::var("BURNTNAIL_UTILS_DEFINITELY_NOT_SET")
+                .ae()
+                .unwrap_err()
+                .to_string(),
+            VarError::NotPresent.to_string()
+        );
+    }
+
+    #[test]
+    fn logging_nones() {
+        assert_eq!(Some(1).warn_none("missing key"), Some(1));
+        assert_eq!(None::<i32>.error_none("empty cache"), None);
+        assert_eq!(Some(3).unwrap_log_none("never logged"), 3);
+    }
 }
@@ -0,0 +1,68 @@
+use std::{
+    fmt::Arguments,
+    sync::{PoisonError, RwLock},
+};
+
+///What to do when one of the fatal methods on [`super::ErrorExt`] (like [`super::ErrorExt::unwrap_log_error`]) hits an error, after logging it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExitStrategy {
+    ///[`panic!`] with the error, which unwinds and runs destructors (unless the binary uses `panic = "abort"`)
+    Panic,
+    ///[`std::process::exit`] with the given code. This doesn't run destructors.
+    Exit(i32),
+    ///[`std::process::abort`] straight away
+    Abort,
+}
+
+impl Default for ExitStrategy {
+    ///Exits with code 1
+    fn default() -> Self {
+        Self::Exit(1)
+    }
+}
+
+///The current strategy
+static STRATEGY: RwLock<ExitStrategy> = RwLock::new(ExitStrategy::Exit(1));
+
+///Sets what the fatal methods on [`super::ErrorExt`] do, for the whole process
+pub fn set_exit_strategy(strategy: ExitStrategy) {
+    *STRATEGY.write().unwrap_or_else(PoisonError::into_inner) = strategy;
+}
+
+///Gets what the fatal methods on [`super::ErrorExt`] currently do
+#[must_use]
+pub fn exit_strategy() -> ExitStrategy {
+    *STRATEGY.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+///Follows the current [`ExitStrategy`], with `msg` as the panic message if it's [`ExitStrategy::Panic`]
+pub fn fatal(msg: Arguments<'_>) -> ! {
+    match exit_strategy() {
+        ExitStrategy::Panic => panic!("{msg}"),
+        ExitStrategy::Exit(code) => std::process::exit(code),
+        ExitStrategy::Abort => std::process::abort(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error_ext::ErrorExt, error_types::BError};
+
+    #[test]
+    fn panic_strategy_unwinds() {
+        set_exit_strategy(ExitStrategy::Panic);
+        assert_eq!(exit_strategy(), ExitStrategy::Panic);
+
+        let res = std::panic::catch_unwind(|| {
+            Err::<(), _>(BError::msg("broken")).unwrap_log_error();
+        });
+        let msg = res.unwrap_err();
+        assert!(msg
+            .downcast_ref::<String>()
+            .is_some_and(|m| m.contains("broken")));
+
+        assert!(std::panic::catch_unwind(|| Err::<(), _>(BError::msg("x")).error_exit()).is_err());
+        set_exit_strategy(ExitStrategy::default());
+    }
+}
@@ -0,0 +1,14 @@
+///How important a log line is, for [`super::ErrorExt::log_at`] (and the `console` ring, if enabled)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    ///Very fine-grained information, eg. for hot paths
+    Trace,
+    ///Debugging information
+    Debug,
+    ///General information
+    Info,
+    ///Something went wrong, but we can carry on
+    Warn,
+    ///Something went very wrong
+    Error,
+}
@@ -3,6 +3,15 @@
 ///All of the stuff from anyhow that I use
 mod anyhow_stuff;
 pub use anyhow_stuff::*;
+///Configuring what happens on fatal errors
+mod exit_strategy;
+pub use exit_strategy::{exit_strategy, set_exit_strategy, ExitStrategy};
+///Levels to log at
+mod log_level;
+pub use log_level::*;
+///Retrying fallible operations
+mod retry;
+pub use retry::*;
 
 #[cfg(feature = "tracing")]
 ///All of the impls with tracing
@@ -0,0 +1,160 @@
+use super::ErrorExt;
+use crate::error_types::{BError, BResult, Contextable};
+use std::time::Duration;
+
+///Base function for retrying `f`, sleeping for the next item of `delays` between attempts.
+///
+///Every failure apart from the last gets written to `warn!`, and the last one is given back with the attempt count as context. An `attempts` of 0 is treated as 1.
+fn base_retry<T, E: Into<BError>>(
+    attempts: usize,
+    mut delays: impl Iterator<Item = Duration>,
+    mut f: impl FnMut() -> Result<T, E>,
+) -> BResult<T> {
+    let attempts = attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Ok(t) => return Ok(t),
+            Err(e) if attempt == attempts => {
+                return Err(e.into()).context(format!("Failed after {attempts} attempts"));
+            }
+            Err(e) => {
+                Err::<(), BError>(e.into())
+                    .warn_context(format!("Attempt {attempt}/{attempts} failed, retrying"));
+                if let Some(delay) = delays.next() {
+                    std::thread::sleep(delay);
+                }
+                attempt += 1;
+            }
+        }
+    }
+}
+
+///Tries `f` up to `attempts` times, sleeping for `delay` between attempts.
+///
+///Each failed attempt is written to `warn!`, apart from the last which is returned. An `attempts` of 0 is treated as 1.
+///
+/// # Errors
+/// If every attempt fails, the last error with the attempt count attached as context
+pub fn retry<T, E: Into<BError>>(
+    attempts: usize,
+    delay: Duration,
+    f: impl FnMut() -> Result<T, E>,
+) -> BResult<T> {
+    base_retry(attempts, std::iter::repeat(delay), f)
+}
+
+///The same as [`retry`], but the delay starts at `initial` and gets multiplied by `factor` after every attempt - eg. for network requests.
+///
+///The delay saturates at [`Duration::MAX`] rather than overflowing. A negative or NaN `factor` means no delay after the first one.
+///
+/// # Errors
+/// If every attempt fails, the last error with the attempt count attached as context
+pub fn retry_backoff<T, E: Into<BError>>(
+    attempts: usize,
+    initial: Duration,
+    factor: f64,
+    f: impl FnMut() -> Result<T, E>,
+) -> BResult<T> {
+    base_retry(attempts, base_backoff_delays(initial, factor), f)
+}
+
+///Base function for the delays used by [`retry_backoff`], starting at `initial` and multiplying by `factor` each time
+fn base_backoff_delays(initial: Duration, factor: f64) -> impl Iterator<Item = Duration> {
+    std::iter::successors(Some(initial), move |delay| {
+        let secs = delay.as_secs_f64() * factor;
+        Some(if secs.is_nan() || secs <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::ParseIntError;
+
+    #[test]
+    fn succeeds_after_failures() {
+        let mut calls = 0;
+        let res = retry(5, Duration::ZERO, || {
+            calls += 1;
+            if calls < 3 {
+                "nope".parse::<u8>()
+            } else {
+                "3".parse::<u8>()
+            }
+        });
+
+        assert_eq!(res.unwrap(), 3);
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn gives_back_last_error_with_count() {
+        let mut calls = 0;
+        let res = retry(3, Duration::ZERO, || -> Result<(), ParseIntError> {
+            calls += 1;
+            "nope".parse::<u8>().map(|_| ())
+        });
+
+        assert_eq!(calls, 3);
+        assert_eq!(
+            format!("{:#}", res.unwrap_err()),
+            "Failed after 3 attempts: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn zero_attempts_still_tries_once() {
+        let mut calls = 0;
+        let res = retry(0, Duration::ZERO, || {
+            calls += 1;
+            Ok::<_, BError>(calls)
+        });
+        assert_eq!(res.unwrap(), 1);
+    }
+
+    #[test]
+    fn backoff_grows() {
+        let delays: Vec<_> = base_backoff_delays(Duration::from_millis(5), 2.0)
+            .take(4)
+            .collect();
+        assert_eq!(delays, [5, 10, 20, 40].map(Duration::from_millis));
+
+        let mut saturated = base_backoff_delays(Duration::from_secs(u64::MAX / 2), 4.0);
+        assert_eq!(saturated.nth(1), Some(Duration::MAX));
+        assert_eq!(saturated.next(), Some(Duration::MAX));
+
+        let mut calls = 0;
+        let res = retry_backoff(4, Duration::from_nanos(1), 2.0, || {
+            calls += 1;
+            Err::<(), _>(BError::msg("still down"))
+        });
+        assert!(res.is_err());
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn backoff_with_a_bad_factor_stops_waiting() {
+        for factor in [-2.0, f64::NAN] {
+            let delays: Vec<_> = base_backoff_delays(Duration::from_millis(5), factor)
+                .take(3)
+                .collect();
+            assert_eq!(
+                delays,
+                [Duration::from_millis(5), Duration::ZERO, Duration::ZERO]
+            );
+        }
+
+        let mut calls = 0;
+        let res = retry_backoff(3, Duration::from_nanos(1), -1.0, || {
+            calls += 1;
+            Err::<(), _>(BError::msg("still down"))
+        });
+        assert!(res.is_err());
+        assert_eq!(calls, 3);
+    }
+}
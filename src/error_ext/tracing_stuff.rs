@@ -1,31 +1,35 @@
-use super::ErrorExt;
-use crate::error_types::BResult;
-use tracing::{error, warn};
+use super::{exit_strategy::fatal, ErrorExt, LogLevel};
+use crate::{
+    error_types::{BError, BResult},
+    time_based_structs::{clock::Clock, rate_limited_logger::RateLimitedLogger},
+};
+use std::fmt::Display;
+use tracing::{debug, error, info, trace, warn};
 
 impl<T> ErrorExt<T> for BResult<T> {
     fn warn(self) {
-        if let Err(e) = self {
-            warn!(?e);
-        }
+        self.log_at(LogLevel::Warn);
     }
 
     fn error(self) {
-        if let Err(e) = self {
-            error!(?e);
-        }
+        self.log_at(LogLevel::Error);
     }
 
     fn error_exit(self) {
         if let Err(e) = self {
+            #[cfg(feature = "console")]
+            crate::console::record(LogLevel::Error, || format!("{e:#}"));
             error!(?e, "Fatal Error");
-            panic!("Fatal Error: {e:?}");
+            fatal(format_args!("Fatal Error: {e:?}"));
         }
     }
 
     fn eprint_exit(self) {
         if let Err(e) = self {
+            #[cfg(feature = "console")]
+            crate::console::record(LogLevel::Error, || format!("{e:#}"));
             eprintln!("Fatal Error: {e:?}");
-            std::process::exit(1);
+            fatal(format_args!("Fatal Error: {e:?}"));
         }
     }
 
@@ -33,9 +37,102 @@ impl<T> ErrorExt<T> for BResult<T> {
         match self {
             Ok(o) => o,
             Err(e) => {
+                #[cfg(feature = "console")]
+                crate::console::record(LogLevel::Error, || format!("{e:#}"));
                 error!(?e, "Fatal Error on unwrap");
-                std::process::exit(1);
+                fatal(format_args!("Fatal Error unwrapping: {e:?}"));
             }
         }
     }
+
+    fn warn_and_continue(self) -> Option<T> {
+        self.unwrap_log_at(LogLevel::Warn)
+    }
+
+    fn error_and_continue(self) -> Option<T> {
+        self.unwrap_log_at(LogLevel::Error)
+    }
+
+    fn ok_logged(self) -> Option<T> {
+        self.error_and_continue()
+    }
+
+    fn ok_warned(self) -> Option<T> {
+        self.warn_and_continue()
+    }
+
+    fn log_err(self) -> Self {
+        if let Err(e) = &self {
+            base_log(LogLevel::Error, e);
+        }
+        self
+    }
+
+    fn info(self) {
+        self.log_at(LogLevel::Info);
+    }
+
+    fn debug(self) {
+        self.log_at(LogLevel::Debug);
+    }
+
+    fn log_at(self, level: LogLevel) {
+        if let Err(e) = self {
+            base_log(level, &e);
+        }
+    }
+
+    fn unwrap_log_at(self, level: LogLevel) -> Option<T> {
+        self.map_err(|e| base_log(level, &e)).ok()
+    }
+
+    fn warn_context(self, msg: impl Display) {
+        if let Err(e) = self {
+            base_log_context(LogLevel::Warn, &msg, &e);
+        }
+    }
+
+    fn error_context(self, msg: impl Display) {
+        if let Err(e) = self {
+            base_log_context(LogLevel::Error, &msg, &e);
+        }
+    }
+
+    fn warn_with_context<C: Display>(self, f: impl FnOnce() -> C) {
+        if let Err(e) = self {
+            base_log_context(LogLevel::Warn, &f(), &e);
+        }
+    }
+
+    fn warn_rate_limited<C: Clock + Clone>(self, limiter: &mut RateLimitedLogger<C>, key: &str) {
+        if let Err(e) = self {
+            limiter.rate_limited_warn(key, &e);
+        }
+    }
+}
+
+///Base function for logging an error at a given level
+fn base_log(level: LogLevel, e: &BError) {
+    #[cfg(feature = "console")]
+    crate::console::record(level, || format!("{e:#}"));
+    match level {
+        LogLevel::Trace => trace!(?e),
+        LogLevel::Debug => debug!(?e),
+        LogLevel::Info => info!(?e),
+        LogLevel::Warn => warn!(?e),
+        LogLevel::Error => error!(?e),
+    }
+}
+
+///Base function for logging an error at a given level, with a message saying where it came from
+fn base_log_context(level: LogLevel, msg: &dyn Display, e: &BError) {
+    #[cfg(feature = "console")]
+    crate::console::record(level, || format!("{msg}: {e:#}"));
+    match level {
+        LogLevel::Trace => trace!(context = %msg, ?e),
+        LogLevel::Debug => debug!(context = %msg, ?e),
+        LogLevel::Info => info!(context = %msg, ?e),
+        LogLevel::Warn => warn!(context = %msg, ?e),
+        LogLevel::Error => error!(context = %msg, ?e),
+    }
 }
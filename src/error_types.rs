@@ -1,9 +1,20 @@
 //! Library module for anyhow vs color-eyre
 //!
 //! The expectation is that you will use this for all error types.
+//!
+//! ## Backends
+//! Each enabled backend gets its own module (`eyre_backend` with `eyre`, `anyhow_backend` with `ah`), so enabling both features (eg. transitively) doesn't break anything. The top-level [`BResult`] and [`BError`] then pick one of them:
+//! 1. `color-eyre`, if `eyre` is enabled
+//! 2. `anyhow`, if `ah` is enabled
+//! 3. A minimal std-only error type, if neither is enabled
+//!
+//! Everything else in this crate (eg. [`crate::error_ext::ErrorExt`]) uses the top-level types.
 #![allow(clippy::use_self)]
 
-use std::fmt::Display;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
 
 ///Trait for providing context to an error
 pub trait Contextable<RES = Self> {
@@ -21,8 +32,8 @@ pub trait Contextable<RES = Self> {
 }
 
 #[cfg(feature = "eyre")]
-///Eyre stuff
-mod eyre_mod {
+///Eyre stuff - this is the top-level backend if enabled
+pub mod eyre_backend {
     use super::Contextable;
     use color_eyre::eyre::WrapErr;
     use std::fmt::Display;
@@ -56,8 +67,8 @@ mod eyre_mod {
 }
 
 #[cfg(feature = "ah")]
-///Anyhow stuff
-mod anyhow_mod {
+///Anyhow stuff - this is the top-level backend if enabled, and `eyre` isn't
+pub mod anyhow_backend {
     use super::Contextable;
     use anyhow::Context;
     use std::fmt::Display;
@@ -91,23 +102,146 @@ mod anyhow_mod {
 }
 
 #[cfg(not(any(feature = "ah", feature = "eyre")))]
+///Minimal std-only stuff, for when neither `anyhow` nor `color-eyre` is enabled
 mod std_mod {
-    use std::error::Error;
+    use super::Contextable;
+    use std::{
+        error::Error,
+        fmt::{Debug, Display, Formatter},
+    };
 
+    ///Boxed error type, which keeps a stack of contexts on top of the original error.
+    ///
+    ///Like `anyhow::Error`, this deliberately doesn't implement [`Error`] itself, so that any [`Error`] can be converted into it with `?`. If you need a [`Error`], convert it into a `Box<dyn Error + Send + Sync>`, which keeps the whole chain reachable through [`Error::source`].
+    ///
+    ///`{}` and `{:?}` print the outermost message followed by a "Caused by:" list, and `{:#}` prints the chain on one line separated by colons.
     pub struct BError {
-        inner: Box<dyn Error>,
+        ///The original error
+        inner: Box<dyn Error + Send + Sync + 'static>,
+        ///The contexts added to the error, innermost first
         contexts: Vec<String>,
     }
 
-    impl<T: Error> From<T> for BError {
-        fn from (e: T) -> Self {
+    ///Error for [`BError::msg`], which is just a message
+    struct MessageError(String);
+
+    impl Debug for MessageError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            Debug::fmt(&self.0, f)
+        }
+    }
+
+    impl Display for MessageError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            Display::fmt(&self.0, f)
+        }
+    }
+
+    impl Error for MessageError {}
+
+    ///Error for one layer of context, when converting a [`BError`] into a `Box<dyn Error>`
+    #[derive(Debug)]
+    struct ContextError {
+        ///The context
+        context: String,
+        ///What the context was added to
+        source: Box<dyn Error + Send + Sync + 'static>,
+    }
+
+    impl Display for ContextError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            Display::fmt(&self.context, f)
+        }
+    }
+
+    impl Error for ContextError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(self.source.as_ref())
+        }
+    }
+
+    impl BError {
+        ///Creates a new error from any [`Error`]
+        pub fn new<E: Error + Send + Sync + 'static>(e: E) -> Self {
             Self {
                 inner: Box::new(e),
-                contexts: Vec::new()
+                contexts: Vec::new(),
             }
         }
+
+        ///Creates a new ad-hoc error from a message
+        pub fn msg(msg: impl Display) -> Self {
+            Self::new(MessageError(msg.to_string()))
+        }
+
+        ///Gets the contexts added to this error, innermost first
+        #[must_use]
+        pub fn contexts(&self) -> &[String] {
+            &self.contexts
+        }
+
+        ///Gets the innermost error, following [`Error::source`] as far as it goes
+        #[must_use]
+        pub fn root_cause(&self) -> &(dyn Error + 'static) {
+            let mut current: &(dyn Error + 'static) = self.inner.as_ref();
+            while let Some(source) = current.source() {
+                current = source;
+            }
+            current
+        }
+
+        ///Gets all of the messages in the chain, outermost first
+        fn messages(&self) -> Vec<String> {
+            let mut messages: Vec<String> = self.contexts.iter().rev().cloned().collect();
+            let mut current: Option<&(dyn Error + 'static)> = Some(self.inner.as_ref());
+            while let Some(e) = current {
+                messages.push(e.to_string());
+                current = e.source();
+            }
+            messages
+        }
+    }
+
+    impl<E: Error + Send + Sync + 'static> From<E> for BError {
+        fn from(e: E) -> Self {
+            Self::new(e)
+        }
+    }
+
+    impl From<BError> for Box<dyn Error + Send + Sync + 'static> {
+        fn from(e: BError) -> Self {
+            e.contexts.into_iter().fold(e.inner, |source, context| {
+                Box::new(ContextError { context, source })
+            })
+        }
     }
 
+    impl Display for BError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            let messages = self.messages();
+            if f.alternate() {
+                return write!(f, "{}", messages.join(": "));
+            }
+
+            let (outer, causes) = messages.split_first().expect("always has the inner error");
+            write!(f, "{outer}")?;
+            if !causes.is_empty() {
+                write!(f, "\n\nCaused by:")?;
+                for (i, cause) in causes.iter().enumerate() {
+                    write!(f, "\n    {i}: {cause}")?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl Debug for BError {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{self}")
+        }
+    }
+
+    ///Std result type
     pub type BResult<T> = Result<T, BError>;
 
     impl<T> Contextable for BResult<T> {
@@ -115,25 +249,202 @@ mod std_mod {
         where
             C: Display + Send + Sync + 'static,
         {
-            let mut s = self;
-            s.contexts.push(context.to_string());
-            s
+            self.map_err(|mut e| {
+                e.contexts.push(context.to_string());
+                e
+            })
         }
 
-        ///NB: Not lazily evaluated
         fn with_context<C, F>(self, f: F) -> BResult<T>
         where
             C: Display + Send + Sync + 'static,
             F: FnOnce() -> C,
         {
-            self.context(f());
+            self.map_err(|mut e| {
+                e.contexts.push(f().to_string());
+                e
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn failing() -> BResult<u8> {
+            Ok("not a number".parse::<u8>()?)
+        }
+
+        #[test]
+        fn formats_chain() {
+            let e = failing()
+                .context("parsing the port")
+                .with_context(|| "loading config")
+                .unwrap_err();
+
+            assert_eq!(e.contexts(), &["parsing the port", "loading config"]);
+            assert_eq!(
+                format!("{e}"),
+                "loading config\n\nCaused by:\n    0: parsing the port\n    1: invalid digit found in string"
+            );
+            assert_eq!(
+                format!("{e:#}"),
+                "loading config: parsing the port: invalid digit found in string"
+            );
+            assert_eq!(format!("{e:?}"), format!("{e}"));
+            assert_eq!(e.root_cause().to_string(), "invalid digit found in string");
+        }
+
+        #[test]
+        fn msg_and_lazy_context() {
+            let e = BError::msg("something broke");
+            assert_eq!(format!("{e}"), "something broke");
+
+            let ok: BResult<u8> = Ok(1);
+            let ok = ok.with_context(|| -> &str { panic!("should be lazy") });
+            assert_eq!(ok.unwrap(), 1);
+        }
+
+        #[test]
+        fn converts_to_std_error_with_sources() {
+            let e = failing().context("parsing the port").unwrap_err();
+            let boxed: Box<dyn Error + Send + Sync> = e.into();
+
+            assert_eq!(boxed.to_string(), "parsing the port");
+            assert_eq!(
+                boxed.source().map(ToString::to_string).as_deref(),
+                Some("invalid digit found in string")
+            );
         }
     }
 }
 
-#[cfg(feature = "ah")]
-pub use anyhow_mod::*;
+#[cfg(all(feature = "ah", not(feature = "eyre")))]
+pub use anyhow_backend::*;
 #[cfg(feature = "eyre")]
-pub use eyre_mod::*;
+pub use eyre_backend::*;
 #[cfg(not(any(feature = "ah", feature = "eyre")))]
 pub use std_mod::*;
+
+///Collects multiple errors so they can all be reported at once, rather than stopping at the first `?` - eg. for validating every field of a config.
+///
+///This implements [`Error`], so it nests inside any of the backends. When displayed, it lists every error numbered on separate lines.
+#[derive(Debug, Default)]
+pub struct ErrorAccumulator {
+    ///The errors collected so far, in order
+    errors: Vec<BError>,
+}
+
+impl ErrorAccumulator {
+    ///Creates a new empty accumulator
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    ///Adds an error
+    pub fn push(&mut self, e: impl Into<BError>) {
+        self.errors.push(e.into());
+    }
+
+    ///Adds an error, with `ctx` attached to it
+    pub fn push_context<C: Display + Send + Sync + 'static>(
+        &mut self,
+        e: impl Into<BError>,
+        ctx: C,
+    ) {
+        if let Err(e) = Err::<(), BError>(e.into()).context(ctx) {
+            self.errors.push(e);
+        }
+    }
+
+    ///Whether or not any errors have been collected
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    ///How many errors have been collected
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    ///Turns the accumulator into a single error, or `Ok` if nothing was collected
+    ///
+    /// # Errors
+    /// If any errors were collected, with all of them listed
+    pub fn into_result(self) -> BResult<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(BError::new(self))
+        }
+    }
+}
+
+impl Display for ErrorAccumulator {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} errors occurred:", self.errors.len())?;
+        for (i, e) in self.errors.iter().enumerate() {
+            write!(f, "\n    {}: {e:#}", i + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ErrorAccumulator {}
+
+///Collects all of the `Ok` values, or if there were any errors, gives back all of them combined with an [`ErrorAccumulator`]
+///
+/// # Errors
+/// If any of the items are `Err`, with all of the errors listed
+pub fn collect_errors<T>(iter: impl IntoIterator<Item = BResult<T>>) -> BResult<Vec<T>> {
+    let mut oks = vec![];
+    let mut errors = ErrorAccumulator::new();
+    for res in iter {
+        match res {
+            Ok(t) => oks.push(t),
+            Err(e) => errors.push(e),
+        }
+    }
+    errors.into_result().map(|()| oks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_and_lists_every_error() {
+        let mut errors = ErrorAccumulator::new();
+        assert!(errors.is_empty());
+        assert!(ErrorAccumulator::new().into_result().is_ok());
+
+        errors.push(BError::msg("name is empty"));
+        errors.push_context(BError::msg("must be below 65536"), "port");
+        assert_eq!(errors.len(), 2);
+
+        let e = errors.into_result().unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "2 errors occurred:\n    1: name is empty\n    2: port: must be below 65536"
+        );
+    }
+
+    #[test]
+    fn collects_oks_or_all_errors() {
+        let parse = |s: &str| -> BResult<u8> { Ok(s.parse()?) };
+
+        assert_eq!(
+            collect_errors(["1", "2", "3"].into_iter().map(parse)).unwrap(),
+            vec![1, 2, 3]
+        );
+
+        let e = collect_errors(["1", "x", "300"].into_iter().map(parse)).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "2 errors occurred:\n    1: invalid digit found in string\n    2: number too large to fit in target type"
+        );
+    }
+}
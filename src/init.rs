@@ -0,0 +1,233 @@
+//! Every binary ends up starting with the same few lines, which this does all in one go.
+//!
+//! [`quick_init`] logs the version, the working directory etc., sets up a panic hook and chooses what happens on fatal errors.
+//!
+//! ## Use
+//! ```rust
+//! use burntnail_utils::init::{quick_init, InitOptions};
+//!
+//! let report = quick_init(InitOptions::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))).unwrap();
+//! assert!(report.panic_hook_installed);
+//! ```
+
+#[cfg(feature = "piston_cacher")]
+use crate::piston_cache::find_assets_folder;
+use crate::{
+    error_ext::{set_exit_strategy, ExitStrategy},
+    error_types::BResult,
+};
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Once,
+    },
+};
+
+///How many panics the hook installed by [`quick_init`] has seen
+static PANICS_SEEN: AtomicUsize = AtomicUsize::new(0);
+
+///Makes sure the panic hook only gets installed once
+static PANIC_HOOK: Once = Once::new();
+
+#[cfg(test)]
+thread_local! {
+    ///How many times the panic hook has run on this thread, to check it only runs once per panic
+    static HOOK_RUNS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+///Options for which parts of [`quick_init`] to run
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct InitOptions {
+    ///The name of the binary, for the summary
+    pub name: String,
+    ///The version of the binary, for the summary - normally `env!("CARGO_PKG_VERSION")`
+    pub version: String,
+    ///Whether or not to include the enabled features of this crate in the report
+    pub report_features: bool,
+    ///Whether or not to include the current working directory in the report
+    pub report_working_dir: bool,
+    ///Whether or not to install a panic hook which logs panics (via `tracing` if enabled) before running the existing hook
+    pub install_panic_hook: bool,
+    ///If [`Some`], then use this for what the fatal [`crate::error_ext::ErrorExt`] methods do - see [`set_exit_strategy`]
+    pub exit_strategy: Option<ExitStrategy>,
+    ///If [`Some`], then resolve the assets folder with this name the same way as `piston_cache::Cacher` does.
+    ///
+    ///NB: Only does anything with the `piston_cacher` feature
+    pub asset_folder: Option<String>,
+}
+
+impl InitOptions {
+    ///Creates a new set of options with everything turned on, apart from resolving the asset folder
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            report_features: true,
+            report_working_dir: true,
+            install_panic_hook: true,
+            exit_strategy: None,
+            asset_folder: None,
+        }
+    }
+}
+
+///Report on what [`quick_init`] found and did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitReport {
+    ///The name of the binary
+    pub name: String,
+    ///The version of the binary
+    pub version: String,
+    ///The enabled features of this crate, if asked for
+    pub features: Option<Vec<&'static str>>,
+    ///The current working directory, if asked for
+    pub working_dir: Option<PathBuf>,
+    ///The resolved assets folder, if asked for
+    pub asset_root: Option<PathBuf>,
+    ///Whether or not the panic hook got installed
+    pub panic_hook_installed: bool,
+    ///The exit strategy that got set, if any
+    pub exit_strategy: Option<ExitStrategy>,
+}
+
+///Gets all of the enabled features of this crate
+#[must_use]
+pub fn enabled_features() -> Vec<&'static str> {
+    [
+        ("tracing", cfg!(feature = "tracing")),
+        ("eyre", cfg!(feature = "eyre")),
+        ("ah", cfg!(feature = "ah")),
+        ("piston_cacher", cfg!(feature = "piston_cacher")),
+        ("serde", cfg!(feature = "serde")),
+        ("console", cfg!(feature = "console")),
+    ]
+    .into_iter()
+    .filter_map(|(name, on)| on.then_some(name))
+    .collect()
+}
+
+///Installs a panic hook which logs the panic (via `tracing` if enabled), and then runs whatever hook was there before.
+///
+///Only the first call does anything, so panics don't get logged twice if this is called again.
+pub fn install_panic_hook() {
+    PANIC_HOOK.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            PANICS_SEEN.fetch_add(1, Ordering::Relaxed);
+            #[cfg(test)]
+            HOOK_RUNS.with(|runs| runs.set(runs.get() + 1));
+
+            crate::bn_error!(%info, "Panic");
+
+            previous(info);
+        }));
+    });
+}
+
+///Runs all of the start-up bits asked for in the options, logs a one-line summary and returns a report.
+///
+/// # Errors
+/// - Unable to get the current working directory
+/// - Unable to find the asset folder (only with `piston_cacher`)
+pub fn quick_init(options: InitOptions) -> BResult<InitReport> {
+    let working_dir = if options.report_working_dir {
+        Some(std::env::current_dir()?)
+    } else {
+        None
+    };
+
+    #[cfg(feature = "piston_cacher")]
+    let asset_root = match &options.asset_folder {
+        Some(folder) => Some(find_assets_folder(folder)?),
+        None => None,
+    };
+    #[cfg(not(feature = "piston_cacher"))]
+    let asset_root = None;
+
+    if options.install_panic_hook {
+        install_panic_hook();
+    }
+    if let Some(strategy) = options.exit_strategy {
+        set_exit_strategy(strategy);
+    }
+
+    let report = InitReport {
+        name: options.name,
+        version: options.version,
+        features: options.report_features.then(enabled_features),
+        working_dir,
+        asset_root,
+        panic_hook_installed: options.install_panic_hook,
+        exit_strategy: options.exit_strategy,
+    };
+
+    crate::bn_info!(name = %report.name, version = %report.version, features = ?report.features, working_dir = ?report.working_dir, asset_root = ?report.asset_root, panic_hook = report.panic_hook_installed, exit_strategy = ?report.exit_strategy, "Starting up");
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_and_panic_hook() {
+        let report = quick_init(InitOptions::new("test", "1.2.3")).unwrap();
+
+        assert_eq!(report.name, "test");
+        assert_eq!(report.version, "1.2.3");
+        assert_eq!(report.features, Some(enabled_features()));
+        assert_eq!(report.working_dir, std::env::current_dir().ok());
+        assert!(report.panic_hook_installed);
+        assert_eq!(report.exit_strategy, None);
+
+        let before = PANICS_SEEN.load(Ordering::Relaxed);
+        assert!(std::thread::spawn(|| panic!("testing the hook"))
+            .join()
+            .is_err());
+        assert!(PANICS_SEEN.load(Ordering::Relaxed) > before);
+    }
+
+    #[test]
+    fn options_turn_things_off() {
+        let report = quick_init(InitOptions {
+            report_features: false,
+            report_working_dir: false,
+            install_panic_hook: false,
+            ..InitOptions::new("test", "1.2.3")
+        })
+        .unwrap();
+
+        assert_eq!(report.features, None);
+        assert_eq!(report.working_dir, None);
+        assert!(!report.panic_hook_installed);
+    }
+
+    #[test]
+    fn sets_the_exit_strategy() {
+        //panicking is what the exit strategy tests use too, so this can't change what they see
+        let report = quick_init(InitOptions {
+            exit_strategy: Some(ExitStrategy::Panic),
+            ..InitOptions::new("test", "1.2.3")
+        })
+        .unwrap();
+        assert_eq!(report.exit_strategy, Some(ExitStrategy::Panic));
+    }
+
+    #[test]
+    fn panic_hook_only_installed_once() {
+        install_panic_hook();
+        quick_init(InitOptions::new("test", "1.2.3")).unwrap();
+        install_panic_hook();
+
+        let runs = std::thread::spawn(|| {
+            assert!(std::panic::catch_unwind(|| panic!("testing the hook once")).is_err());
+            HOOK_RUNS.with(std::cell::Cell::get)
+        })
+        .join()
+        .unwrap();
+        assert_eq!(runs, 1);
+    }
+}
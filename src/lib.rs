@@ -6,7 +6,7 @@
 //! Also includes utilities for hard to handle error types, and unwrapping them with tracing logs, or converting them to anyhow/color-eyre results.
 //!
 //! ## Either
-//! An enumeration for representing an object with can be either A or B, with utility methods to convert to one or the other.
+//! An enumeration for representing an object with can be either A or B, with utility methods to convert to one or the other. There is also `Either3` for when there are three options.
 //!
 //! ## Memcache
 //! A circular queue structure backed by a [`Vec`] *(it used to be a [`std::mem::MaybeUninit`] array but a [`Vec`] added 10x speedups lol)*, which can optionally hold a `DoOnInterval` in order to only add things on an interval.
@@ -21,9 +21,15 @@
 //! ### Scoped Timer
 //! I love this for logging! It starts a timer when you make the object, and on [`std::ops::Drop`] it logs out the time since the timer started, making for very conventient logging.
 //!
+//! ### Checkpoint
+//! For when what you want to time doesn't fit in one scope - start a checkpoint, pass the token around, and finish it wherever the action ends.
+//!
 //! ## Coords
 //! Some nice 2D Coordinates, with support for maximum positions, and generic types using the `num_traits`.
 //!
+//! ## Resource Cache
+//! A generic cache which loads resources on a miss using a loader - eg. sounds, fonts or config blobs - with timing, stats and an optional LRU capacity.
+//!
 //! ## Piston Cache
 //! NB: Only enabled if you have the relevant feature enabled.
 //!
@@ -31,6 +37,14 @@
 //!
 //! ## 2D Array
 //! A struct for a grid array, which can be indexed using Coordinates or a usize pair.
+//!
+//! ## Console
+//! NB: Only enabled if you have the relevant feature enabled.
+//!
+//! A ring of the last few log lines, with timestamps, for an in-game console. If one gets registered, then all of the error extension logging goes to it too.
+//!
+//! ## Init
+//! A helper for all of the stuff that gets done at the start of every binary - logging the version and working directory, setting up a panic hook etc.
 #![warn(
     clippy::all,
     clippy::pedantic,
@@ -38,15 +52,16 @@
     clippy::missing_docs_in_private_items
 )]
 
-#[cfg(all(feature = "ah", feature = "eyre"))]
-compile_error!("Cannot Have Both Anyhow & Eyre activated at once");
-
 pub mod either;
 pub mod error_types;
+pub mod init;
 pub mod macros;
 pub mod memcache;
+pub mod resource_cache;
 pub mod time_based_structs;
 
+#[cfg(feature = "console")]
+pub mod console;
 pub mod coords;
 pub mod error_ext;
 #[cfg(feature = "piston_cacher")]
@@ -58,3 +73,8 @@ mod crate_private {
     ///Trait which cannot be externally implemented
     pub trait Sealed {}
 }
+
+///Re-export of `tracing` for the logging macros, so they work without users depending on it
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+pub use tracing as __tracing;
@@ -47,22 +47,635 @@
 //!    /* Rocket impls */
 //! }
 //! ```
+//!
+//! A visibility and a list of extra derives can also be passed in first, either together or on their own. The derives go on the unit structs (and the runtime enum, if there is one) as well as the normal `Copy`, `Clone` and `Debug`. The runtime enum already derives `PartialEq` and `Eq`, so leave those out when making one:
+//!```rust
+//! mod with_extras {
+//!    mod sealed {
+//!        pub trait SealedTrait {}
+//!    }
+//!    use burntnail_utils::generic_enum;
+//!    use sealed::SealedTrait;
+//!
+//!    generic_enum!(pub(crate), [Hash, PartialEq, Default], SealedTrait, (DoorMode -> "Trait for whether the door is open") => (Open -> "Open"), (Closed -> "Closed"));
+//!    generic_enum!(pub(crate), SealedTrait, (LightMode -> "Trait for whether the light is on") => (On -> "On"), (Off -> "Off"));
+//!    generic_enum!([Hash], SealedTrait, (LockMode -> "Trait for whether the lock is locked"), (LockModeKind -> "Whether the lock is locked, at runtime") => (Locked -> "Locked"), (Unlocked -> "Unlocked"));
+//!
+//!    pub(crate) fn check() {
+//!        use std::collections::HashSet;
+//!
+//!        assert_eq!(Open::default(), Open);
+//!        let kinds: HashSet<LockModeKind> = [Locked::KIND, Unlocked::KIND, Locked::KIND].into_iter().collect();
+//!        assert_eq!(kinds.len(), 2);
+//!        let _ = (On, Off);
+//!    }
+//! }
+//! with_extras::check();
+//! ```
 
 ///Provides any number of unit structs that implement a unit type
 ///
 ///Must pass in a `Sealed` trait for use in libraries, if you don't care use [`std::any::Any`] or [`std::ops::Drop`]
+///
+///Everything is `pub` and the unit structs derive `Copy`, `Clone` and `Debug` by default. A visibility and/or a list of extra derives can be passed in before the sealed trait - see the [module docs](self) for an example.
+///
+///If a second name and docs are passed in after the trait, a runtime enum with the same variant names is made too - eg. to store the current state in a save file. The trait then has a `KIND` constant with the variant for each unit struct, and the enum has a `name` method:
+///```rust
+/// use burntnail_utils::generic_enum;
+///
+/// trait SealedTrait {}
+/// generic_enum!(SealedTrait, (RocketMode -> "Trait for what state the rocket is in"), (RocketModeKind -> "What state the rocket is in, at runtime") => (InAssembly -> "Still being Assembled"), (OnLaunchPad -> "All assembled, and ready to be launched"), (Launched -> "In Space!!!"));
+///
+/// fn describe<MODE: RocketMode>() -> &'static str {
+///     MODE::KIND.name()
+/// }
+///
+/// assert_eq!(OnLaunchPad::KIND, RocketModeKind::OnLaunchPad);
+/// assert_eq!(describe::<Launched>(), "Launched");
+///```
 #[macro_export]
 macro_rules! generic_enum {
-    ($sealed_name:ident, ($trait_name:ident -> $trait_docs:literal) => $(($unit_struct_name:ident -> $docs:literal)),+) => {
+    (@structs $vis:vis, $derives:tt, $sealed_name:ident, ($trait_name:ident -> $trait_docs:literal) => $(($unit_struct_name:ident -> $docs:literal)),+) => {
         #[doc=$trait_docs]
-        pub trait $trait_name : $sealed_name {}
+        $vis trait $trait_name : $sealed_name {}
 
         $(
             #[doc=$docs]
-            #[derive(Copy, Clone, Debug)]
-            pub struct $unit_struct_name;
+            #[derive $derives]
+            $vis struct $unit_struct_name;
             impl $sealed_name for $unit_struct_name {}
             impl $trait_name for $unit_struct_name {}
         )+
     };
+    (@kinds $vis:vis, $derives:tt, $kind_derives:tt, $sealed_name:ident, ($trait_name:ident -> $trait_docs:literal), ($kind_name:ident -> $kind_docs:literal) => $(($unit_struct_name:ident -> $docs:literal)),+) => {
+        #[doc=$trait_docs]
+        $vis trait $trait_name : $sealed_name {
+            ///Which of the states this is, at runtime
+            const KIND: $kind_name;
+        }
+
+        #[doc=$kind_docs]
+        #[derive $kind_derives]
+        $vis enum $kind_name {
+            $(
+                #[doc=$docs]
+                $unit_struct_name,
+            )+
+        }
+
+        impl $kind_name {
+            ///Gets the name of the state
+            #[must_use]
+            pub const fn name(&self) -> &'static str {
+                match self {
+                    $(Self::$unit_struct_name => stringify!($unit_struct_name),)+
+                }
+            }
+        }
+
+        $(
+            #[doc=$docs]
+            #[derive $derives]
+            $vis struct $unit_struct_name;
+            impl $sealed_name for $unit_struct_name {}
+            impl $trait_name for $unit_struct_name {
+                const KIND: $kind_name = $kind_name::$unit_struct_name;
+            }
+        )+
+    };
+    ($vis:vis, [$($derive:path),* $(,)?], $sealed_name:ident, ($trait_name:ident -> $trait_docs:literal) => $($variants:tt)+) => {
+        $crate::generic_enum!(@structs $vis, (Copy, Clone, Debug $(, $derive)*), $sealed_name, ($trait_name -> $trait_docs) => $($variants)+);
+    };
+    ($vis:vis, [$($derive:path),* $(,)?], $sealed_name:ident, ($trait_name:ident -> $trait_docs:literal), ($kind_name:ident -> $kind_docs:literal) => $($variants:tt)+) => {
+        $crate::generic_enum!(@kinds $vis, (Copy, Clone, Debug $(, $derive)*), (Copy, Clone, Debug, PartialEq, Eq $(, $derive)*), $sealed_name, ($trait_name -> $trait_docs), ($kind_name -> $kind_docs) => $($variants)+);
+    };
+    ($vis:vis, $sealed_name:ident, $($rest:tt)+) => {
+        $crate::generic_enum!($vis, [], $sealed_name, $($rest)+);
+    };
+    ([$($derive:path),* $(,)?], $($rest:tt)+) => {
+        $crate::generic_enum!(pub, [$($derive),*], $($rest)+);
+    };
+    ($sealed_name:ident, $($rest:tt)+) => {
+        $crate::generic_enum!(pub, [], $sealed_name, $($rest)+);
+    };
+}
+
+///Provides the `is_x`, `to_x`, `ref_x`, `mut_ref_x`, `clone_x` and `expect_x` methods for an enum where each variant holds one value
+///
+///These are the same as the ones on [`crate::either::Either`], but for any number of variants. The method names for each variant are given in that order, as they can't be made up from the variant name. Generic parameters go after the `impl` like normal, with any bounds in a `where` clause. `clone_x` only needs that variant's type to be [`Clone`], and `expect_x` only needs the other variants' types to be [`std::fmt::Debug`], so those can be called even if the other types aren't.
+///
+///```rust
+/// use burntnail_utils::variant_accessors;
+///
+/// enum Shape<T> {
+///     Circle(T),
+///     Square(T),
+///     Named(String),
+/// }
+///
+/// variant_accessors!(impl<T> Shape<T> {
+///     Circle(T) => is_circle, to_circle, ref_circle, mut_ref_circle, clone_circle, expect_circle;
+///     Square(T) => is_square, to_square, ref_square, mut_ref_square, clone_square, expect_square;
+///     Named(String) => is_named, to_named, ref_named, mut_ref_named, clone_named, expect_named;
+/// });
+///
+/// let mut shape: Shape<f32> = Shape::Circle(1.5);
+/// assert!(shape.is_circle() && !shape.is_square());
+/// assert_eq!(shape.ref_circle(), Some(&1.5));
+/// if let Some(r) = shape.mut_ref_circle() {
+///     *r *= 2.0;
+/// }
+/// assert_eq!(shape.clone_circle(), Some(3.0));
+/// assert_eq!(shape.to_named(), None);
+///
+/// let named: Shape<f32> = Shape::Named("blob".to_string());
+/// assert_eq!(named.expect_named("should be named"), "blob");
+///```
+#[macro_export]
+macro_rules! variant_accessors {
+    (@each $header:tt [$($before:tt)*]) => {};
+    (@each $header:tt [$($before:tt)*] ($variant:ident, $inner:ty, $is:ident, $to:ident, $ref:ident, $mut_ref:ident, $clone:ident, $expect:ident) $($after:tt)*) => {
+        $crate::variant_accessors!(@one $header ($variant, $inner, $is, $to, $ref, $mut_ref, $clone, $expect) [$($before)* $($after)*]);
+        $crate::variant_accessors!(@each $header [$($before)* ($variant, $inner, $is, $to, $ref, $mut_ref, $clone, $expect)] $($after)*);
+    };
+    (@one ([$($generic:tt),*] $self_ty:ty; [$($bounded:ty: $bound:path),*]) ($variant:ident, $inner:ty, $is:ident, $to:ident, $ref:ident, $mut_ref:ident, $clone:ident, $expect:ident) [$(($other:ident, $other_inner:ty, $($_other_names:ident),*))*]) => {
+        impl<$($generic),*> $self_ty where $($bounded: $bound),* {
+            #[doc = concat!("Checks whether or not this is [`Self::", stringify!($variant), "`]")]
+            #[must_use]
+            pub const fn $is(&self) -> bool {
+                matches!(self, Self::$variant(_))
+            }
+
+            #[doc = concat!("Returns [`Some`] of the owned value if this is [`Self::", stringify!($variant), "`], else [`None`]")]
+            #[allow(clippy::missing_const_for_fn, clippy::wrong_self_convention)] //issue with destructors, and named to match `Either`
+            pub fn $to(self) -> Option<$inner> {
+                match self {
+                    Self::$variant(value) => Some(value),
+                    #[allow(unreachable_patterns)] //for single-variant enums
+                    _ => None,
+                }
+            }
+
+            #[doc = concat!("Returns [`Some`] of a reference to the value if this is [`Self::", stringify!($variant), "`], else [`None`]")]
+            pub const fn $ref(&self) -> Option<&$inner> {
+                match self {
+                    Self::$variant(value) => Some(value),
+                    #[allow(unreachable_patterns)] //for single-variant enums
+                    _ => None,
+                }
+            }
+
+            #[doc = concat!("Returns [`Some`] of a mutable reference to the value if this is [`Self::", stringify!($variant), "`], else [`None`]")]
+            pub const fn $mut_ref(&mut self) -> Option<&mut $inner> {
+                match self {
+                    Self::$variant(value) => Some(value),
+                    #[allow(unreachable_patterns)] //for single-variant enums
+                    _ => None,
+                }
+            }
+
+            #[doc = concat!("Returns [`Some`] of a clone of the value if this is [`Self::", stringify!($variant), "`], else [`None`]")]
+            #[must_use]
+            pub fn $clone(&self) -> Option<$inner>
+            where
+                //the `for` stops this being a trivial bound, which would fail to compile for concrete types that aren't `Clone`
+                for<'__variant_accessors> $inner: Clone,
+            {
+                self.$ref().cloned()
+            }
+
+            #[doc = concat!("Returns the value inside a [`Self::", stringify!($variant), "`]")]
+            ///
+            /// # Panics
+            /// If this is any other variant, with `msg` and the value inside
+            #[track_caller]
+            pub fn $expect(self, msg: &str) -> $inner
+            where
+                $(for<'__variant_accessors> $other_inner: std::fmt::Debug,)*
+            {
+                match self {
+                    Self::$variant(value) => value,
+                    $(Self::$other(other) => panic!("{msg}: {}({other:?})", stringify!($other)),)*
+                }
+            }
+        }
+    };
+    (@start $header:tt { $($variant:ident($inner:ty) => $is:ident, $to:ident, $ref:ident, $mut_ref:ident, $clone:ident, $expect:ident);+ $(;)? }) => {
+        $crate::variant_accessors!(@each $header [] $(($variant, $inner, $is, $to, $ref, $mut_ref, $clone, $expect))+);
+    };
+    (impl<$($generic:tt),+> $self_ty:ty $(where $($bounded:ty: $bound:path),+ $(,)?)? { $($body:tt)* }) => {
+        $crate::variant_accessors!(@start ([$($generic),+] $self_ty; [$($($bounded: $bound),+)?]) { $($body)* });
+    };
+    (impl $self_ty:ty $(where $($bounded:ty: $bound:path),+ $(,)?)? { $($body:tt)* }) => {
+        $crate::variant_accessors!(@start ([] $self_ty; [$($($bounded: $bound),+)?]) { $($body)* });
+    };
+}
+
+///Logs at the trace level - via [`tracing::trace!`](https://docs.rs/tracing/latest/tracing/macro.trace.html) with the `tracing` feature, or [`println!`] without it.
+///
+///Takes the same `key = value`, `key = ?debug`, `key = %display`, `?ident` and `%ident` fields as `tracing`, followed by an optional format string and arguments. Without `tracing`, the fields are printed after the message like `message [key=value other=value]`.
+///
+///```rust
+/// use burntnail_utils::{bn_info, bn_warn};
+///
+/// let path = "assets/ship.png";
+/// let size = (16, 16);
+/// bn_info!(?size, "Loaded {path}");
+/// bn_warn!(attempts = 3, reason = %"timed out", "Giving up on {path}");
+///```
+#[macro_export]
+macro_rules! bn_trace {
+    ($($t:tt)*) => {
+        $crate::__bn_log!(Trace, trace, $($t)*)
+    };
+}
+
+///Logs at the debug level - see [`bn_trace!`] for the syntax
+#[macro_export]
+macro_rules! bn_debug {
+    ($($t:tt)*) => {
+        $crate::__bn_log!(Debug, debug, $($t)*)
+    };
+}
+
+///Logs at the info level - see [`bn_trace!`] for the syntax
+#[macro_export]
+macro_rules! bn_info {
+    ($($t:tt)*) => {
+        $crate::__bn_log!(Info, info, $($t)*)
+    };
+}
+
+///Logs at the warn level - see [`bn_trace!`] for the syntax. Without `tracing`, this goes to stderr with a `Warning: ` prefix.
+#[macro_export]
+macro_rules! bn_warn {
+    ($($t:tt)*) => {
+        $crate::__bn_log!(Warn, warn, $($t)*)
+    };
+}
+
+///Logs at the error level - see [`bn_trace!`] for the syntax. Without `tracing`, this goes to stderr with an `Error: ` prefix.
+#[macro_export]
+macro_rules! bn_error {
+    ($($t:tt)*) => {
+        $crate::__bn_log!(Error, error, $($t)*)
+    };
+}
+
+///Backend for the `bn_*` logging macros, which passes everything straight through to `tracing`
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bn_log {
+    ($level:ident, $tracing_macro:ident, $($t:tt)*) => {
+        $crate::__tracing::$tracing_macro!($($t)*)
+    };
+}
+
+///Backend for the `bn_*` logging macros, which formats the fields and message and prints them
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bn_log {
+    (@fields $level:ident [$($field:expr),*] $key:ident = ?$value:expr, $($rest:tt)*) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{:?}", $value))] $($rest)*)
+    };
+    (@fields $level:ident [$($field:expr),*] $key:ident = ?$value:expr) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{:?}", $value))])
+    };
+    (@fields $level:ident [$($field:expr),*] $key:ident = %$value:expr, $($rest:tt)*) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{}", $value))] $($rest)*)
+    };
+    (@fields $level:ident [$($field:expr),*] $key:ident = %$value:expr) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{}", $value))])
+    };
+    (@fields $level:ident [$($field:expr),*] $key:ident = $value:expr, $($rest:tt)*) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{:?}", $value))] $($rest)*)
+    };
+    (@fields $level:ident [$($field:expr),*] $key:ident = $value:expr) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{:?}", $value))])
+    };
+    (@fields $level:ident [$($field:expr),*] ?$key:ident $(, $($rest:tt)*)?) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{:?}", $key))] $($($rest)*)?)
+    };
+    (@fields $level:ident [$($field:expr),*] %$key:ident $(, $($rest:tt)*)?) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{}", $key))] $($($rest)*)?)
+    };
+    (@fields $level:ident [$($field:expr),*] $key:ident $(, $($rest:tt)*)?) => {
+        $crate::__bn_log!(@fields $level [$($field,)* (stringify!($key), format!("{:?}", $key))] $($($rest)*)?)
+    };
+    (@fields $level:ident [$($field:expr),*] $($message:tt)+) => {
+        $crate::macros::__bn_print($crate::error_ext::LogLevel::$level, &format!($($message)+), &[$($field),*])
+    };
+    (@fields $level:ident [$($field:expr),*]) => {
+        $crate::macros::__bn_print($crate::error_ext::LogLevel::$level, "", &[$($field),*])
+    };
+    ($level:ident, $tracing_macro:ident, $($t:tt)*) => {
+        $crate::__bn_log!(@fields $level [] $($t)*)
+    };
+}
+
+#[cfg(all(test, not(feature = "tracing")))]
+thread_local! {
+    ///Every line printed by [`__bn_print`] on this thread, and whether it went to stderr
+    static PRINTED: std::cell::RefCell<Vec<(bool, String)>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+///Prints a log line for the `bn_*` macros without `tracing` - trace, debug and info go to stdout, and warn and error go to stderr with a prefix
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+pub fn __bn_print(level: crate::error_ext::LogLevel, message: &str, fields: &[(&str, String)]) {
+    let (to_stderr, line) = base_log_line(level, message, fields);
+
+    #[cfg(test)]
+    PRINTED.with(|printed| printed.borrow_mut().push((to_stderr, line.clone())));
+
+    if to_stderr {
+        eprintln!("{line}");
+    } else {
+        println!("{line}");
+    }
+}
+
+///Formats a log line for [`__bn_print`], and works out whether it goes to stderr
+#[cfg(not(feature = "tracing"))]
+fn base_log_line(
+    level: crate::error_ext::LogLevel,
+    message: &str,
+    fields: &[(&str, String)],
+) -> (bool, String) {
+    use crate::error_ext::LogLevel;
+
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect();
+    let line = match (message.is_empty(), fields.is_empty()) {
+        (_, true) => message.to_string(),
+        (true, false) => fields.join(" "),
+        (false, false) => format!("{message} [{}]", fields.join(" ")),
+    };
+
+    match level {
+        LogLevel::Trace | LogLevel::Debug | LogLevel::Info => (false, line),
+        LogLevel::Warn => (true, format!("Warning: {line}")),
+        LogLevel::Error => (true, format!("Error: {line}")),
+    }
+}
+
+#[cfg(test)]
+#[allow(dead_code)] //not all of the generated accessors are used
+mod tests {
+    use crate::crate_private::Sealed;
+
+    generic_enum!(Sealed, (LightMode -> "What the light is doing"), (LightModeKind -> "What the light is doing, at runtime") => (Off -> "Off"), (On -> "On"), (Flashing -> "Flashing"));
+
+    fn kind_of<MODE: LightMode>(_: MODE) -> LightModeKind {
+        MODE::KIND
+    }
+
+    #[test]
+    fn runtime_kinds() {
+        assert_eq!(kind_of(Off), LightModeKind::Off);
+        assert_eq!(kind_of(On), LightModeKind::On);
+        assert_eq!(Flashing::KIND, LightModeKind::Flashing);
+        assert_ne!(On::KIND, Off::KIND);
+
+        assert_eq!(LightModeKind::Off.name(), "Off");
+        assert_eq!(On::KIND.name(), "On");
+        assert_eq!(<Flashing as LightMode>::KIND.name(), "Flashing");
+    }
+
+    ///Not `Clone` or `Debug`, to check the accessors don't need them
+    struct Opaque(u8);
+
+    enum Three<T> {
+        First(T),
+        Second(Opaque),
+        Third(&'static str),
+    }
+
+    variant_accessors!(impl<T> Three<T> where T: Copy {
+        First(T) => is_first, to_first, ref_first, mut_ref_first, clone_first, expect_first;
+        Second(Opaque) => is_second, to_second, ref_second, mut_ref_second, clone_second, expect_second;
+        Third(&'static str) => is_third, to_third, ref_third, mut_ref_third, clone_third, expect_third;
+    });
+
+    enum Number {
+        Small(u8),
+        Big(u64),
+    }
+
+    variant_accessors!(impl Number {
+        Small(u8) => is_small, to_small, ref_small, mut_ref_small, clone_small, expect_small;
+        Big(u64) => is_big, to_big, ref_big, mut_ref_big, clone_big, expect_big;
+    });
+
+    #[test]
+    fn accessors() {
+        let number = Number::Big(1 << 40);
+        assert!(number.is_big() && !number.is_small());
+        assert_eq!(number.expect_big("should be big"), 1 << 40);
+
+        let mut first: Three<u32> = Three::First(5);
+        assert!(first.is_first());
+        assert!(!first.is_second() && !first.is_third());
+        if let Some(x) = first.mut_ref_first() {
+            *x += 1;
+        }
+        assert_eq!(first.clone_first(), Some(6));
+        assert_eq!(first.ref_third(), None);
+        assert_eq!(first.to_first(), Some(6));
+
+        let second: Three<u32> = Three::Second(Opaque(3));
+        assert_eq!(second.ref_second().map(|o| o.0), Some(3));
+        assert!(second.ref_first().is_none());
+        assert_eq!(second.to_second().map(|o| o.0), Some(3));
+
+        let third: Three<u32> = Three::Third("three");
+        assert_eq!(third.clone_third(), Some("three"));
+        assert_eq!(
+            Three::<u32>::Second(Opaque(4))
+                .expect_second("should be second")
+                .0,
+            4
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "wanted the second: Third(\"three\")")]
+    fn accessor_expect_panic_message() {
+        Three::<u32>::Third("three").expect_second("wanted the second");
+    }
+
+    ///Runs `f` with a subscriber that records the level and fields of every event
+    #[cfg(feature = "tracing")]
+    fn traced(f: impl FnOnce()) -> Vec<(tracing::Level, String)> {
+        use std::{
+            fmt::Debug,
+            sync::{Arc, Mutex},
+        };
+        use tracing::{
+            field::{Field, Visit},
+            span, Event, Metadata, Subscriber,
+        };
+
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Vec<(tracing::Level, String)>>>);
+
+        struct Fields(Vec<String>);
+
+        impl Visit for Fields {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                self.0.push(format!("{}={value:?}", field.name()));
+            }
+        }
+
+        impl Subscriber for Recorder {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+                span::Id::from_u64(1)
+            }
+            fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+            fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = Fields(vec![]);
+                event.record(&mut fields);
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((*event.metadata().level(), fields.0.join(" ")));
+            }
+            fn enter(&self, _: &span::Id) {}
+            fn exit(&self, _: &span::Id) {}
+        }
+
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), f);
+        let events = recorder.0.lock().unwrap().clone();
+        events
+    }
+
+    ///Runs `f`, and gets every line it printed and whether that went to stderr
+    #[cfg(not(feature = "tracing"))]
+    fn printed(f: impl FnOnce()) -> Vec<(bool, String)> {
+        super::PRINTED.with(|printed| printed.borrow_mut().clear());
+        f();
+        super::PRINTED.with(std::cell::RefCell::take)
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn trace_goes_to_tracing() {
+        let path = "ship.png";
+        assert_eq!(
+            traced(|| bn_trace!("Loading {path}")),
+            vec![(
+                tracing::Level::TRACE,
+                "message=Loading ship.png".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn debug_goes_to_tracing() {
+        let size = (16, 16);
+        assert_eq!(
+            traced(|| bn_debug!(?size)),
+            vec![(tracing::Level::DEBUG, "size=(16, 16)".to_string())]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn info_goes_to_tracing() {
+        let size = (16, 16);
+        assert_eq!(
+            traced(|| bn_info!(?size, "Loaded")),
+            vec![(
+                tracing::Level::INFO,
+                "message=Loaded size=(16, 16)".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn warn_goes_to_tracing() {
+        assert_eq!(
+            traced(|| bn_warn!(attempts = 3, reason = %"timed out", "Giving up")),
+            vec![(
+                tracing::Level::WARN,
+                "message=Giving up attempts=3 reason=timed out".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "tracing")]
+    fn error_goes_to_tracing() {
+        let path = "ship.png";
+        assert_eq!(
+            traced(|| bn_error!(%path, "Failed")),
+            vec![(
+                tracing::Level::ERROR,
+                "message=Failed path=ship.png".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "tracing"))]
+    fn trace_prints_to_stdout() {
+        let path = "ship.png";
+        assert_eq!(
+            printed(|| bn_trace!("Loading {path}")),
+            vec![(false, "Loading ship.png".to_string())]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "tracing"))]
+    fn debug_prints_to_stdout() {
+        let size = (16, 16);
+        assert_eq!(
+            printed(|| bn_debug!(?size)),
+            vec![(false, "size=(16, 16)".to_string())]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "tracing"))]
+    fn info_prints_to_stdout() {
+        let size = (16, 16);
+        assert_eq!(
+            printed(|| bn_info!(?size, "Loaded")),
+            vec![(false, "Loaded [size=(16, 16)]".to_string())]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "tracing"))]
+    fn warn_prints_to_stderr() {
+        assert_eq!(
+            printed(|| bn_warn!(attempts = 3, reason = %"timed out", "Giving up")),
+            vec![(
+                true,
+                "Warning: Giving up [attempts=3 reason=timed out]".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "tracing"))]
+    fn error_prints_to_stderr() {
+        let path = "ship.png";
+        assert_eq!(
+            printed(|| bn_error!(%path, "Failed")),
+            vec![(true, "Error: Failed [path=ship.png]".to_string())]
+        );
+    }
 }
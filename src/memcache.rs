@@ -1,6 +1,6 @@
 //! This is a circular queue of fixed size, backed by a Vector, optionally using a [`crate::time_based_structs::do_on_interval::DoOnInterval`] to determine on what interval to add items.
 //!
-//! Also includes facilities for getting averages of `T`s that support it.
+//! Also includes facilities for getting averages of `T`s that support it - see [`MemoryCacher::average`] and [`DivCount`].
 //!
 //! For a [`crate::time_based_structs::do_on_interval::DoOnInterval`] example, see the [`crate::time_based_structs`] module-level docs
 //!
@@ -11,27 +11,34 @@
 //!
 //! let mut memcache: MemoryCacher<_, 5> = MemoryCacher::new(None);
 //! (0..3).for_each(|i| memcache.push(i));
-//! assert_eq!(memcache.get_all_copy(), (0..3).collect::<Vec<_>>()); //now, the list has 3 `i32`s, but isn't full
+//! assert_eq!(memcache.get_all_cloned(), (0..3).collect::<Vec<_>>()); //now, the list has 3 `i32`s, but isn't full
 //!
 //! memcache.push(3); memcache.push(4);
-//! assert_eq!(memcache.get_all_copy(), (0..5).collect::<Vec<_>>()); //now, the list has 5 `i32`s, but is full, so if we add more it starts to overwrite from the beginning
+//! assert_eq!(memcache.get_all_cloned(), (0..5).collect::<Vec<_>>()); //now, the list has 5 `i32`s, but is full, so if we add more it starts to overwrite from the beginning
 //!
 //! memcache.push(10); memcache.push(11);
-//! assert_eq!(memcache.get_all_copy(), vec![10, 11, 2, 3, 4]); //now, the list has some of our original items, but has overwritten the first few as it went over N
+//! assert_eq!(memcache.get_all_cloned(), vec![10, 11, 2, 3, 4]); //now, the list has some of our original items, but has overwritten the first few as it went over N
 //! ```
 
-use crate::time_based_structs::do_on_interval::{DoOnInterval, UpdateOnCheck};
+use crate::time_based_structs::{
+    clock::{Clock, SystemClock},
+    do_on_interval::{DoOnInterval, UpdateOnCheck},
+};
+use num_traits::{NumCast, ToPrimitive};
 use std::{
+    cmp::Ordering,
     fmt::Debug,
-    ops::{AddAssign, Div},
+    iter::Sum,
+    ops::{Add, AddAssign, Div},
+    time::Duration,
     vec::IntoIter,
 };
 
 ///Struct to hold a list of items that only get updated on a [`DoOnInterval`], with a circular cache that overwrites the oldest items if there isn't any free space.
 ///
-///Has 2 generic properties - `T` for the type stored, and `N` for the size of the backing array
+///Has 3 generic properties - `T` for the type stored, `N` for the size of the backing array, and `C` for the [`Clock`] the timer gets the time from
 #[derive(Debug)]
-pub struct MemoryCacher<T, const N: usize> {
+pub struct MemoryCacher<T, const N: usize, C: Clock = SystemClock> {
     ///Holds all the data
     data: Vec<T>,
     ///Marks whether or not the array is full of data - useful for after it wraps around
@@ -42,10 +49,10 @@ pub struct MemoryCacher<T, const N: usize> {
     index: usize,
 
     ///Holds a timer in case we only want to write data on intervals rather than whenever `add` is called
-    timer: Option<DoOnInterval<UpdateOnCheck>>,
+    timer: Option<DoOnInterval<UpdateOnCheck, C>>,
 }
 
-impl<T: Copy, const N: usize> Default for MemoryCacher<T, N> {
+impl<T, const N: usize, C: Clock> Default for MemoryCacher<T, N, C> {
     fn default() -> Self {
         Self {
             data: Vec::with_capacity(N),
@@ -56,7 +63,7 @@ impl<T: Copy, const N: usize> Default for MemoryCacher<T, N> {
     }
 }
 
-impl<T: Copy, const N: usize> MemoryCacher<T, N> {
+impl<T, const N: usize> MemoryCacher<T, N> {
     ///Creates a blank Memory Cacher
     #[must_use]
     pub fn new(t: Option<DoOnInterval<UpdateOnCheck>>) -> Self {
@@ -65,30 +72,107 @@ impl<T: Copy, const N: usize> MemoryCacher<T, N> {
             ..Default::default()
         }
     }
+}
+
+impl<T, const N: usize, C: Clock> MemoryCacher<T, N, C> {
+    ///Same as [`MemoryCacher::new`], but with a timer getting the time from any [`Clock`] - eg. a [`crate::time_based_structs::clock::MockClock`] for testing
+    #[must_use]
+    pub fn with_timer(t: DoOnInterval<UpdateOnCheck, C>) -> Self {
+        Self {
+            timer: Some(t),
+            ..Default::default()
+        }
+    }
 
     ///Adds an element to the list on the following conditions:
-    /// - there are no elements
+    /// - there is no [`DoOnInterval`] timer
     /// - there is a [`DoOnInterval`] timer, and we can use it
+    ///
+    ///Otherwise, the element is silently dropped - use [`MemoryCacher::try_push`] to find out whether or not it was stored.
     pub fn push(&mut self, t: T) {
-        let can = self.timer.as_mut().map_or(true, DoOnInterval::can_do);
+        self.push_with_eviction(t, |_| {});
+    }
 
-        if can {
-            if self.full {
-                self.data[self.index] = t;
-            } else {
-                self.data.push(t);
-            }
+    ///Same as [`MemoryCacher::push`], but returns whether or not the element was actually stored
+    pub fn try_push(&mut self, t: T) -> bool {
+        self.push_with_eviction(t, |_| {})
+    }
 
-            if self.index == N - 1 {
-                self.full = true;
-            }
+    ///Same as [`MemoryCacher::push`], but if adding the element overwrites the oldest one, then `on_evict` gets called with the old element.
+    ///
+    ///Returns whether or not the element was added
+    pub fn push_with_eviction(&mut self, t: T, on_evict: impl FnOnce(T)) -> bool {
+        let can = self
+            .timer
+            .as_mut()
+            .map_or(true, DoOnInterval::<UpdateOnCheck, C>::can_do);
 
-            self.index = (self.index + 1) % N;
+        if can {
+            self.base_push(t, on_evict);
 
             if let Some(t) = &mut self.timer {
                 t.update_timer();
             }
         }
+
+        can
+    }
+
+    ///Adds an element to the list, ignoring the timer
+    pub fn push_unconditional(&mut self, t: T) {
+        self.base_push(t, |_| {});
+    }
+
+    ///Adds all of the elements to the list in order, ignoring the timer - eg. for loading historical samples. Same as [`Extend::extend`].
+    pub fn push_many(&mut self, iter: impl IntoIterator<Item = T>) {
+        for t in iter {
+            self.base_push(t, |_| {});
+        }
+    }
+
+    ///Base function for adding an element, overwriting the oldest if full and calling `on_evict` with it
+    fn base_push(&mut self, t: T, on_evict: impl FnOnce(T)) {
+        if self.full {
+            on_evict(std::mem::replace(&mut self.data[self.index], t));
+        } else {
+            self.data.push(t);
+            //go off the actual length rather than the index, so it can't get out of sync
+            self.full = self.data.len() == N;
+        }
+        debug_assert!(self.data.len() <= N, "MemoryCacher grew past N");
+
+        self.index = (self.index + 1) % N;
+    }
+
+    ///Iterates over the elements in chronological order, from the oldest to the newest
+    pub fn iter_chronological(&self) -> impl Iterator<Item = &T> {
+        let split = if self.full { self.index } else { 0 };
+        self.data[split..].iter().chain(self.data[..split].iter())
+    }
+
+    ///Gets the most recently added element, or [`None`] if the list is empty
+    #[must_use]
+    pub fn latest(&self) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        //index is always one past the latest, either because it's the length when not full, or because it wraps round
+        self.data.get((self.index + N - 1) % N)
+    }
+
+    ///Gets the oldest element, or [`None`] if the list is empty
+    #[must_use]
+    pub fn oldest(&self) -> Option<&T> {
+        if self.full {
+            self.data.get(self.index)
+        } else {
+            self.data.first()
+        }
+    }
+
+    ///Folds over all of the elements, from the oldest to the newest, without allocating
+    pub fn fold<U>(&self, init: U, f: impl FnMut(U, &T) -> U) -> U {
+        self.iter_chronological().fold(init, f)
     }
 
     ///Returns whether or not the list is empty
@@ -97,21 +181,131 @@ impl<T: Copy, const N: usize> MemoryCacher<T, N> {
         self.data.is_empty()
     }
 
-    ///Gets all of the elements, with order unimportant
+    ///Returns how many elements are in the list - this stays at `N` once the list is full
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    ///Returns the maximum number of elements in the list, `N`
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    ///Returns whether or not the list is full, so new elements will overwrite the oldest ones
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.full
+    }
+
+    ///Rearranges the backing so that the oldest element is first
+    fn make_chronological(&mut self) {
+        if self.full {
+            self.data.rotate_left(self.index);
+            self.index = 0;
+        }
+    }
+
+    ///Removes and returns all of the elements in chronological order, from the oldest to the newest.
+    ///
+    ///The list is left empty, keeping the timer and the allocated space.
+    pub fn drain(&mut self) -> Vec<T> {
+        self.make_chronological();
+        let all = self.data.drain(..).collect();
+        self.full = false;
+        self.index = 0;
+        all
+    }
+
+    ///Removes and returns the oldest element, or [`None`] if the list is empty.
+    ///
+    ///NB: This is `O(N)`, as it has to shift the rest of the elements along
+    pub fn pop_oldest(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        self.make_chronological();
+        let oldest = self.data.remove(0);
+        self.full = false;
+        self.index = self.data.len();
+        Some(oldest)
+    }
+
+    ///Gets the timer, if there is one
+    #[must_use]
+    pub const fn timer(&self) -> Option<&DoOnInterval<UpdateOnCheck, C>> {
+        self.timer.as_ref()
+    }
+
+    ///Gets the timer mutably, if there is one - eg. to change the interval with [`DoOnInterval::set_interval`]
+    pub const fn timer_mut(&mut self) -> Option<&mut DoOnInterval<UpdateOnCheck, C>> {
+        self.timer.as_mut()
+    }
+
+    ///Replaces the timer, keeping all of the elements
+    pub fn set_timer(&mut self, t: Option<DoOnInterval<UpdateOnCheck, C>>) {
+        self.timer = t;
+    }
+
+    ///Removes all of the elements, keeping the timer and the allocated space
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.full = false;
+        self.index = 0;
+    }
+
+    ///Gets all of the elements, in the order they are stored.
+    ///
+    ///NB: This is **unordered** - once the list has wrapped around, the oldest element isn't first. For that, use [`MemoryCacher::get_all_ordered`] or [`MemoryCacher::iter_chronological`].
     #[must_use]
     #[allow(clippy::missing_const_for_fn)] //destructor issues
     pub fn get_all(self) -> Vec<T> {
         self.data
     }
+}
 
-    ///Gets all of the elements, copying all elements to avoid ownership issues
+impl<T: Clone, const N: usize, C: Clock> MemoryCacher<T, N, C> {
+    ///Gets all of the elements, cloning all elements to avoid ownership issues.
+    ///
+    ///NB: Like [`MemoryCacher::get_all`], this is **unordered**
     #[must_use]
-    pub fn get_all_copy(&self) -> Vec<T> {
+    pub fn get_all_cloned(&self) -> Vec<T> {
         self.data.clone()
     }
+
+    ///Old name for [`MemoryCacher::get_all_cloned`]
+    #[must_use]
+    #[deprecated(since = "0.8.0", note = "renamed to `get_all_cloned`")]
+    pub fn get_all_copy(&self) -> Vec<T> {
+        self.get_all_cloned()
+    }
+
+    ///Gets all of the elements in chronological order, from the oldest to the newest
+    #[must_use]
+    pub fn get_all_ordered(&self) -> Vec<T> {
+        self.iter_chronological().cloned().collect()
+    }
+
+    ///Gets up to the `n` most recently added elements, in chronological order
+    #[must_use]
+    pub fn last_n(&self, n: usize) -> Vec<T> {
+        self.iter_chronological()
+            .skip(self.len().saturating_sub(n))
+            .cloned()
+            .collect()
+    }
 }
 
-impl<T: Copy, const N: usize> IntoIterator for MemoryCacher<T, N> {
+impl<T, const N: usize, C: Clock> Extend<T> for MemoryCacher<T, N, C> {
+    ///Adds all of the elements, ignoring the timer - see [`MemoryCacher::push_many`]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.push_many(iter);
+    }
+}
+
+impl<T, const N: usize, C: Clock> IntoIterator for MemoryCacher<T, N, C> {
     type Item = T;
     type IntoIter = IntoIter<T>;
 
@@ -120,17 +314,182 @@ impl<T: Copy, const N: usize> IntoIterator for MemoryCacher<T, N> {
     }
 }
 
+///A [`MemoryCacher`] which keeps a running aggregate of its elements, updating it on every push rather than rescanning everything.
+///
+///Only works for invertible operations (like sums and counts), as `remove` has to be able to take an element back out of the aggregate when it gets evicted.
+///
+///```rust
+/// use burntnail_utils::memcache::WindowedReducer;
+///
+/// let mut sum = WindowedReducer::<i32, i32, _, _, 3>::new(0, |acc, el| *acc += el, |acc, el| *acc -= el, None);
+/// (1..=5).for_each(|i| sum.push(i));
+/// assert_eq!(*sum.value(), 3 + 4 + 5);
+///```
+pub struct WindowedReducer<T, U, A, R, const N: usize>
+where
+    A: FnMut(&mut U, &T),
+    R: FnMut(&mut U, &T),
+{
+    ///The elements currently in the window
+    cacher: MemoryCacher<T, N>,
+    ///The running aggregate
+    value: U,
+    ///Adds an element into the aggregate
+    add: A,
+    ///Takes an element back out of the aggregate
+    remove: R,
+}
+
+impl<T, U, A, R, const N: usize> WindowedReducer<T, U, A, R, N>
+where
+    A: FnMut(&mut U, &T),
+    R: FnMut(&mut U, &T),
+{
+    ///Creates a new empty `WindowedReducer`, with the aggregate starting at `init`
+    pub fn new(init: U, add: A, remove: R, timer: Option<DoOnInterval<UpdateOnCheck>>) -> Self {
+        Self {
+            cacher: MemoryCacher::new(timer),
+            value: init,
+            add,
+            remove,
+        }
+    }
+
+    ///Pushes an element into the window (see [`MemoryCacher::push`]), updating the aggregate and taking out any evicted element
+    pub fn push(&mut self, t: T) {
+        let Self {
+            cacher,
+            value,
+            add,
+            remove,
+        } = self;
+
+        if cacher.push_with_eviction(t, |old| remove(value, &old)) {
+            if let Some(t) = cacher.latest() {
+                add(value, t);
+            }
+        }
+    }
+
+    ///Gets the current aggregate
+    #[must_use]
+    pub const fn value(&self) -> &U {
+        &self.value
+    }
+
+    ///Gets the underlying [`MemoryCacher`]
+    #[must_use]
+    pub const fn cacher(&self) -> &MemoryCacher<T, N> {
+        &self.cacher
+    }
+}
+
+///Trait for types which can be divided by a count of elements, for [`MemoryCacher::average`]
+pub trait DivCount: Sized {
+    ///Divides by `count`, or returns [`None`] if `count` is zero or doesn't fit in the type being divided by.
+    ///
+    ///Integers truncate towards zero.
+    fn div_count(self, count: usize) -> Option<Self>;
+}
+
+///Trait for types which can be turned into an [`f64`] for statistics like [`MemoryCacher::std_dev`]. [`Duration`]s are turned into seconds.
+pub trait AsF64 {
+    ///Gets the value as an [`f64`], or [`None`] if that isn't possible
+    fn as_f64(&self) -> Option<f64>;
+}
+
+///Implements [`DivCount`] and [`AsF64`] for primitive number types
+macro_rules! primitive_impl {
+    ($($t:ty),+) => {
+        $(
+            impl DivCount for $t {
+                fn div_count(self, count: usize) -> Option<Self> {
+                    if count == 0 {
+                        return None;
+                    }
+                    <$t as NumCast>::from(count).map(|count| self / count)
+                }
+            }
+
+            impl AsF64 for $t {
+                fn as_f64(&self) -> Option<f64> {
+                    self.to_f64()
+                }
+            }
+        )+
+    };
+}
+
+primitive_impl!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+impl DivCount for Duration {
+    fn div_count(self, count: usize) -> Option<Self> {
+        if count == 0 {
+            return None;
+        }
+        u32::try_from(count).ok().map(|count| self / count)
+    }
+}
+
+impl AsF64 for Duration {
+    fn as_f64(&self) -> Option<f64> {
+        Some(self.as_secs_f64())
+    }
+}
+
+impl<T: for<'a> Sum<&'a T>, const N: usize, C: Clock> MemoryCacher<T, N, C> {
+    ///Adds up all of the elements. This is the zero value of `T` if the list is empty.
+    #[must_use]
+    pub fn sum(&self) -> T {
+        self.data.iter().sum()
+    }
+
+    ///Gets the mean of the elements, or [`None`] if the list is empty. Works for any primitive number, and for [`Duration`].
+    ///
+    ///NB: For integers, this uses integer division, so truncates towards zero. For rounding, there are the `average_rounded_*` functions, and [`MemoryCacher::average_as_f64`].
+    #[must_use]
+    pub fn average(&self) -> Option<T>
+    where
+        T: DivCount,
+    {
+        if self.is_empty() {
+            return None;
+        }
+        self.sum().div_count(self.len())
+    }
+
+    ///Gets the mean of the newest `k` elements (or all of them if there are fewer than `k`), without allocating. Returns [`None`] if the list is empty or `k` is zero.
+    #[must_use]
+    pub fn window_average(&self, k: usize) -> Option<T>
+    where
+        T: DivCount,
+    {
+        let count = k.min(self.len());
+        if count == 0 {
+            return None;
+        }
+
+        self.iter_chronological()
+            .skip(self.len() - count)
+            .sum::<T>()
+            .div_count(count)
+    }
+}
+
 ///Creates an average function for an {integer} type
 macro_rules! average_impl {
     ($($t:ty => $name:ident),+) => {
         $(
-            impl<T, const N: usize> MemoryCacher<T, N>
+            impl<T, const N: usize, C: Clock> MemoryCacher<T, N, C>
             where
                 T: Div<$t> + AddAssign + Default + Clone + Copy + Debug,
                 T::Output: Default,
             {
                 ///Function to get the average of the items in the list
+                ///
+                ///NB: This uses integer division, so truncates towards zero. For rounding, there are the `average_rounded_*` functions, and [`MemoryCacher::average_as_f64`].
                 #[must_use]
+                #[deprecated(since = "0.8.0", note = "use the generic `average`, which returns `None` when empty")]
                 pub fn $name(&self) -> T::Output {
                     if self.is_empty() {
                         return T::Output::default();
@@ -139,7 +498,7 @@ macro_rules! average_impl {
                     let mut total = T::default();
                     let mut count = 0;
 
-                    for el in self.get_all_copy().into_iter() {
+                    for el in self.get_all_cloned().into_iter() {
                         total += el;
                         count += 1;
                     }
@@ -150,17 +509,50 @@ macro_rules! average_impl {
         )+
     };
 }
+///Creates an average function for an {integer} type which rounds halves up rather than truncating
+macro_rules! average_rounded_impl {
+    ($($t:ty => $name:ident),+) => {
+        $(
+            impl<T, const N: usize, C: Clock> MemoryCacher<T, N, C>
+            where
+                T: Div<$t> + Add<$t, Output = T> + AddAssign + Default + Clone + Copy + Debug,
+                <T as Div<$t>>::Output: Default,
+            {
+                ///Function to get the average of the items in the list, rounding halves up.
+                ///
+                ///NB: Only rounds correctly if the total is non-negative, as the division still truncates towards zero
+                #[must_use]
+                pub fn $name(&self) -> <T as Div<$t>>::Output {
+                    if self.is_empty() {
+                        return <T as Div<$t>>::Output::default();
+                    }
+
+                    let mut total = T::default();
+                    let mut count: $t = 0;
+
+                    for el in &self.data {
+                        total += *el;
+                        count += 1;
+                    }
+
+                    (total + count / 2) / count
+                }
+            }
+        )+
+    };
+}
 ///Creates an average function for a {float} type
 macro_rules! average_fp_impl {
     ($($t:ty => $name:ident),+) => {
         $(
-            impl<T, const N: usize> MemoryCacher<T, N>
+            impl<T, const N: usize, C: Clock> MemoryCacher<T, N, C>
             where
                 T: Div<$t> + AddAssign + Default + Clone + Copy + Debug + Default,
                 T::Output: Default
             {
                 ///Function to get the average of the items in the list
                 #[must_use]
+                #[deprecated(since = "0.8.0", note = "use the generic `average`, which returns `None` when empty")]
                 pub fn $name(&self) -> T::Output {
                     if self.is_empty() {
                         return T::Output::default();
@@ -169,7 +561,7 @@ macro_rules! average_fp_impl {
                     let mut total = T::default();
                     let mut count = 0.0;
 
-                    for el in self.get_all_copy().into_iter() {
+                    for el in self.get_all_cloned().into_iter() {
                         total += el;
                         count += 1.0;
                     }
@@ -182,11 +574,211 @@ macro_rules! average_fp_impl {
 }
 
 average_impl!(u8 => average_u8, u16 => average_u16, u32 => average_u32, u64 => average_u64, u128 => average_u128, i8 => average_i8, i16 => average_i16, i32 => average_i32, i64 => average_i64, i128 => average_i128);
+average_rounded_impl!(u8 => average_rounded_u8, u16 => average_rounded_u16, u32 => average_rounded_u32, u64 => average_rounded_u64, u128 => average_rounded_u128, i8 => average_rounded_i8, i16 => average_rounded_i16, i32 => average_rounded_i32, i64 => average_rounded_i64, i128 => average_rounded_i128);
 average_fp_impl!(f32 => average_f32, f64 => average_f64);
 
+impl<T: ToPrimitive + Copy, const N: usize, C: Clock> MemoryCacher<T, N, C> {
+    ///Function to get the average of the items in the list as an [`f64`], regardless of the type of `T`.
+    ///
+    ///The total is accumulated as an [`f64`], so this won't overflow for big integer types like [`u128`].
+    ///
+    ///Returns [`None`] if the list is empty, or if any of the elements can't be represented as an [`f64`]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] //lists won't get big enough for this to matter
+    pub fn average_as_f64(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let mut total = 0.0;
+        for el in &self.data {
+            total += el.to_f64()?;
+        }
+
+        Some(total / self.data.len() as f64)
+    }
+}
+
+///A bundle of statistics about the elements in a [`MemoryCacher`], from [`MemoryCacher::stats`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats<T> {
+    ///How many elements there were
+    pub count: usize,
+    ///The smallest element
+    pub min: T,
+    ///The largest element
+    pub max: T,
+    ///The mean of the elements (see [`MemoryCacher::average`])
+    pub mean: T,
+    ///The median of the elements (see [`MemoryCacher::median`])
+    pub median: T,
+    ///The population standard deviation of the elements, as an [`f64`] (see [`MemoryCacher::std_dev`])
+    pub std_dev: f64,
+}
+
+impl<T: PartialOrd, const N: usize, C: Clock> MemoryCacher<T, N, C> {
+    ///Gets the smallest element. Elements which can't be compared (like `NaN`) are skipped over, so this is [`None`] if the list is empty or only has those.
+    #[must_use]
+    pub fn min(&self) -> Option<&T> {
+        self.base_comparable()
+            .reduce(|a, b| if b < a { b } else { a })
+    }
+
+    ///Gets the largest element. Elements which can't be compared (like `NaN`) are skipped over, so this is [`None`] if the list is empty or only has those.
+    #[must_use]
+    pub fn max(&self) -> Option<&T> {
+        self.base_comparable()
+            .reduce(|a, b| if b > a { b } else { a })
+    }
+
+    ///Base function to iterate over the elements which can be compared, skipping ones like `NaN` which aren't even equal to themselves
+    fn base_comparable(&self) -> impl Iterator<Item = &T> {
+        self.data.iter().filter(|x| x.partial_cmp(x).is_some())
+    }
+}
+
+impl<T: PartialOrd + Clone, const N: usize, C: Clock> MemoryCacher<T, N, C> {
+    ///Gets a sorted copy of the elements, leaving the list alone
+    fn sorted(&self) -> Vec<T> {
+        let mut sorted = self.data.clone();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        sorted
+    }
+
+    ///Base function for getting a percentile out of a sorted list, using the nearest-rank method
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )] //clamped, and lists won't get big enough for precision to matter
+    fn base_percentile(sorted: &[T], p: f64) -> Option<T> {
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+        sorted.get(rank.max(1) - 1).cloned()
+    }
+
+    ///Gets the `p`th percentile (from `0.0` to `100.0`) of the elements using the nearest-rank method, or [`None`] if the list is empty.
+    ///
+    ///This sorts a copy of the elements, so the list itself isn't changed.
+    #[must_use]
+    pub fn percentile(&self, p: f64) -> Option<T> {
+        Self::base_percentile(&self.sorted(), p)
+    }
+
+    ///Gets the median of the elements, or [`None`] if the list is empty. With an even number of elements, this is the lower of the middle two.
+    #[must_use]
+    pub fn median(&self) -> Option<T> {
+        self.percentile(50.0)
+    }
+}
+
+impl<T: AsF64, const N: usize, C: Clock> MemoryCacher<T, N, C> {
+    ///Gets the population standard deviation of the elements as an [`f64`] (in seconds for [`Duration`]s), or [`None`] if the list is empty or any of the elements can't be turned into an [`f64`]
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] //lists won't get big enough for this to matter
+    pub fn std_dev(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+
+        //Welford's algorithm, so it's one pass and doesn't lose precision with big values
+        let mut mean = 0.0;
+        let mut m2 = 0.0;
+        for (i, el) in self.data.iter().enumerate() {
+            let x = el.as_f64()?;
+            let delta = x - mean;
+            mean += delta / (i + 1) as f64;
+            m2 += delta * (x - mean);
+        }
+
+        Some((m2 / self.data.len() as f64).sqrt())
+    }
+}
+
+impl<T, const N: usize, C: Clock> MemoryCacher<T, N, C>
+where
+    T: PartialOrd + Clone + AsF64 + DivCount + for<'a> Sum<&'a T>,
+{
+    ///Gets all of the [`Stats`] at once, only sorting once. Returns [`None`] if the list is empty.
+    #[must_use]
+    pub fn stats(&self) -> Option<Stats<T>> {
+        let sorted = self.sorted();
+        Some(Stats {
+            count: sorted.len(),
+            min: sorted.first()?.clone(),
+            max: sorted.last()?.clone(),
+            mean: self.average()?,
+            median: Self::base_percentile(&sorted, 50.0)?,
+            std_dev: self.std_dev()?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+///`serde` implementations for [`MemoryCacher`]
+mod serde_impls {
+    use super::{Clock, MemoryCacher};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    impl<T: Serialize, const N: usize, C: Clock> Serialize for MemoryCacher<T, N, C> {
+        ///Serializes the elements as a sequence in chronological order. The timer is skipped.
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_seq(self.iter_chronological())
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>, const N: usize, C: Clock> Deserialize<'de>
+        for MemoryCacher<T, N, C>
+    {
+        ///Deserializes a sequence of elements in chronological order, keeping only the newest `N` if there are too many. There is no timer.
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let mut cacher = Self::default();
+            cacher.push_many(Vec::<T>::deserialize(deserializer)?);
+            Ok(cacher)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn round_trip(pushes: std::ops::Range<i32>, json: &str) {
+            let mut list = MemoryCacher::<i32, 4>::new(None);
+            list.push_many(pushes);
+
+            assert_eq!(serde_json::to_string(&list).unwrap(), json);
+
+            let mut back: MemoryCacher<i32, 4> = serde_json::from_str(json).unwrap();
+            assert_eq!(back.get_all_ordered(), list.get_all_ordered());
+            assert_eq!(back.is_full(), list.is_full());
+            back.push(100);
+            list.push(100);
+            assert_eq!(back.get_all_ordered(), list.get_all_ordered());
+        }
+
+        #[test]
+        fn round_trips() {
+            round_trip(0..0, "[]");
+            round_trip(0..3, "[0,1,2]");
+            round_trip(0..4, "[0,1,2,3]");
+            round_trip(0..7, "[3,4,5,6]");
+        }
+
+        #[test]
+        fn too_many_keeps_newest() {
+            let list: MemoryCacher<i32, 3> = serde_json::from_str("[1,2,3,4,5]").unwrap();
+            assert_eq!(list.get_all_ordered(), vec![3, 4, 5]);
+            assert_eq!(list.latest(), Some(&5));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::memcache::MemoryCacher;
+    use crate::{
+        memcache::{MemoryCacher, WindowedReducer},
+        time_based_structs::{clock::MockClock, do_on_interval::DoOnInterval},
+    };
+    use std::time::Duration;
 
     #[test]
     pub fn hand_constructed_get_all() {
@@ -206,12 +798,367 @@ mod tests {
         let mut full_list = MemoryCacher::<_, 10>::new(None);
         let mut half_full_list = MemoryCacher::<_, 20>::new(None);
 
-        let base_10 = (0..10).into_iter().collect::<Vec<i32>>();
-        for i in base_10.clone() {
+        let base_10 = (0..10).collect::<Vec<i32>>();
+        for &i in &base_10 {
             full_list.push(i);
             half_full_list.push(i);
         }
-        assert_eq!(full_list.get_all_copy(), base_10.clone());
-        assert_eq!(half_full_list.get_all_copy(), base_10.clone());
+        assert_eq!(full_list.get_all_cloned(), base_10);
+        assert_eq!(half_full_list.get_all_cloned(), base_10);
+    }
+
+    #[test]
+    pub fn wraps_instead_of_growing() {
+        fn check<const N: usize>() {
+            let mut list = MemoryCacher::<usize, N>::new(None);
+            for i in 0..2 * N {
+                list.push(i);
+                assert!(list.len() <= N);
+            }
+            assert!(list.is_full());
+            assert_eq!(list.get_all_cloned().len(), N);
+            assert_eq!(list.get_all_ordered(), (N..2 * N).collect::<Vec<_>>());
+
+            //and again after clearing it part-way through
+            list.clear();
+            for i in 0..=2 * N {
+                list.push(i);
+            }
+            assert_eq!(list.get_all().len(), N);
+        }
+
+        check::<1>();
+        check::<3>();
+        check::<10>();
+    }
+
+    #[test]
+    pub fn fold_is_oldest_first() {
+        let mut list = MemoryCacher::<i32, 3>::new(None);
+        for i in 0..5 {
+            list.push(i);
+        }
+
+        let order = list.fold(Vec::new(), |mut v, el| {
+            v.push(*el);
+            v
+        });
+        assert_eq!(order, vec![2, 3, 4]);
+    }
+
+    #[test]
+    pub fn chronological_order() {
+        let mut list = MemoryCacher::<i32, 10>::new(None);
+        for i in 0..5 {
+            list.push(i);
+        }
+        assert_eq!(list.get_all_ordered(), (0..5).collect::<Vec<_>>());
+
+        for i in 5..15 {
+            list.push(i);
+        }
+        assert_eq!(list.get_all_ordered(), (5..15).collect::<Vec<_>>());
+        assert_eq!(
+            list.iter_chronological().copied().collect::<Vec<_>>(),
+            (5..15).collect::<Vec<_>>()
+        );
+        assert_ne!(list.get_all_cloned(), list.get_all_ordered());
+    }
+
+    #[test]
+    pub fn len_capacity_and_clear() {
+        let mut list = MemoryCacher::<i32, 4>::new(None);
+        assert_eq!((list.len(), list.capacity(), list.is_full()), (0, 4, false));
+
+        for i in 0..3 {
+            list.push(i);
+        }
+        assert_eq!((list.len(), list.is_full()), (3, false));
+
+        for i in 3..6 {
+            list.push(i);
+        }
+        assert_eq!((list.len(), list.is_full()), (4, true));
+
+        list.clear();
+        assert!(list.is_empty());
+        assert!(!list.is_full());
+        assert!(list.data.capacity() >= 4);
+
+        for i in 10..15 {
+            list.push(i);
+        }
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.get_all_ordered(), vec![11, 12, 13, 14]);
+    }
+
+    #[test]
+    pub fn latest_and_oldest_in_every_state() {
+        let mut list = MemoryCacher::<i32, 4>::new(None);
+        assert_eq!((list.latest(), list.oldest()), (None, None));
+        assert!(list.last_n(3).is_empty());
+
+        list.push(0);
+        assert_eq!((list.latest(), list.oldest()), (Some(&0), Some(&0)));
+
+        list.push(1);
+        list.push(2);
+        assert_eq!((list.latest(), list.oldest()), (Some(&2), Some(&0)));
+        assert_eq!(list.last_n(2), vec![1, 2]);
+        assert_eq!(list.last_n(10), vec![0, 1, 2]);
+
+        list.push(3);
+        assert!(list.is_full());
+        assert_eq!((list.latest(), list.oldest()), (Some(&3), Some(&0)));
+        assert_eq!(list.last_n(4), vec![0, 1, 2, 3]);
+
+        list.push(4);
+        assert_eq!((list.latest(), list.oldest()), (Some(&4), Some(&1)));
+        assert_eq!(list.last_n(3), vec![2, 3, 4]);
+
+        for i in 5..8 {
+            list.push(i);
+        }
+        assert_eq!((list.latest(), list.oldest()), (Some(&7), Some(&4)));
+        assert_eq!(list.last_n(0), Vec::<i32>::new());
+        assert_eq!(list.last_n(1), vec![7]);
+    }
+
+    #[test]
+    pub fn non_copy_elements() {
+        let mut list = MemoryCacher::<String, 2>::new(None);
+        let mut evicted = vec![];
+        for word in ["one", "two", "three"] {
+            list.push_with_eviction(word.to_string(), |old| evicted.push(old));
+        }
+
+        assert_eq!(evicted, vec!["one".to_string()]);
+        assert_eq!(list.latest().map(String::as_str), Some("three"));
+        assert_eq!(list.get_all_ordered(), vec!["two", "three"]);
+        assert_eq!(list.into_iter().count(), 2);
+    }
+
+    #[test]
+    pub fn stats_bundle() {
+        let mut list = MemoryCacher::<u32, 5>::new(None);
+        assert_eq!(list.stats(), None);
+        assert_eq!((list.min(), list.max(), list.median()), (None, None, None));
+        assert_eq!(list.std_dev(), None);
+
+        for i in [9, 2, 4, 4, 5, 7] {
+            list.push(i);
+        }
+        //9 has been overwritten, so it's 2, 4, 4, 5, 7
+        let stats = list.stats().unwrap();
+        assert_eq!(
+            (stats.count, stats.min, stats.max, stats.mean, stats.median),
+            (5, 2, 7, 4, 4)
+        );
+        assert!((stats.std_dev - 2.64_f64.sqrt()).abs() < 1e-9);
+
+        assert_eq!(list.percentile(0.0), Some(2));
+        assert_eq!(list.percentile(80.0), Some(5));
+        assert_eq!(list.percentile(100.0), Some(7));
+        assert_eq!(list.get_all_ordered(), vec![2, 4, 4, 5, 7]);
+    }
+
+    #[test]
+    pub fn min_max_skip_nan() {
+        let mut list = MemoryCacher::<f64, 4>::new(None);
+        for f in [f64::NAN, 3.0, f64::NAN, 1.5] {
+            list.push(f);
+        }
+        assert_eq!(list.min(), Some(&1.5));
+        assert_eq!(list.max(), Some(&3.0));
+
+        let mut only_nan = MemoryCacher::<f64, 2>::new(None);
+        only_nan.push(f64::NAN);
+        assert_eq!(only_nan.min(), None);
+        assert_eq!(only_nan.max(), None);
+    }
+
+    #[test]
+    pub fn duration_stats() {
+        let mut times = MemoryCacher::<Duration, 10>::new(None);
+        for ms in [16, 17, 15, 33, 16] {
+            times.push(Duration::from_millis(ms));
+        }
+
+        let stats = times.stats().unwrap();
+        assert_eq!(stats.min, Duration::from_millis(15));
+        assert_eq!(stats.max, Duration::from_millis(33));
+        assert_eq!(stats.median, Duration::from_millis(16));
+        assert_eq!(times.percentile(99.0), Some(Duration::from_millis(33)));
+        assert!(stats.std_dev > 0.006 && stats.std_dev < 0.007);
+    }
+
+    #[test]
+    pub fn drain_keeps_collecting() {
+        let mut list = MemoryCacher::<i32, 4>::new(None);
+        for i in 0..6 {
+            list.push(i);
+        }
+
+        assert_eq!(list.drain(), vec![2, 3, 4, 5]);
+        assert!(list.is_empty());
+        assert_eq!(list.drain(), Vec::<i32>::new());
+
+        for i in 10..13 {
+            list.push(i);
+        }
+        assert_eq!(list.get_all_ordered(), vec![10, 11, 12]);
+    }
+
+    #[test]
+    pub fn pop_oldest_across_wrap() {
+        let mut list = MemoryCacher::<i32, 4>::new(None);
+        assert_eq!(list.pop_oldest(), None);
+
+        for i in 0..6 {
+            list.push(i);
+        }
+        //backing is now [4, 5, 2, 3]
+        assert_eq!(list.pop_oldest(), Some(2));
+        assert_eq!(list.pop_oldest(), Some(3));
+        assert!(!list.is_full());
+        assert_eq!((list.oldest(), list.latest()), (Some(&4), Some(&5)));
+
+        for i in 6..9 {
+            list.push(i);
+        }
+        assert_eq!(list.get_all_ordered(), vec![5, 6, 7, 8]);
+        assert_eq!(list.latest(), Some(&8));
+
+        let mut popped = vec![];
+        while let Some(el) = list.pop_oldest() {
+            popped.push(el);
+        }
+        assert_eq!(popped, vec![5, 6, 7, 8]);
+        assert!(list.is_empty());
+
+        list.push(100);
+        assert_eq!((list.oldest(), list.latest()), (Some(&100), Some(&100)));
+    }
+
+    #[test]
+    pub fn bulk_and_conditional_pushes() {
+        let timer = DoOnInterval::new_dont_initial_update(Duration::from_secs(1000));
+        let mut list = MemoryCacher::<i32, 5>::new(Some(timer));
+
+        assert!(!list.try_push(0));
+        list.push(1);
+        assert!(list.is_empty());
+
+        list.push_unconditional(2);
+        list.push_many([3, 4]);
+        list.extend(5..8);
+        assert_eq!(list.get_all_ordered(), vec![3, 4, 5, 6, 7]);
+
+        let mut untimed = MemoryCacher::<i32, 5>::new(None);
+        assert!(untimed.try_push(0));
+        assert_eq!(untimed.len(), 1);
+    }
+
+    #[test]
+    pub fn window_averages() {
+        let mut frame_times = MemoryCacher::<Duration, 600>::new(None);
+        assert_eq!(frame_times.window_average(60), None);
+
+        for i in 0..700_u64 {
+            frame_times.push(Duration::from_millis(if i < 640 { 10 } else { 20 }));
+        }
+        assert_eq!(
+            frame_times.window_average(60),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(
+            frame_times.window_average(120),
+            Some(Duration::from_millis(15))
+        );
+        assert_eq!(frame_times.window_average(0), None);
+
+        let mut list = MemoryCacher::<i32, 10>::new(None);
+        list.push_many([1, 2, 3]);
+        assert_eq!(list.window_average(100), Some(2));
+        assert_eq!(list.window_average(1), Some(3));
+    }
+
+    #[test]
+    pub fn changing_the_timer_keeps_data() {
+        let clock = MockClock::new();
+        let mut list = MemoryCacher::<i32, 5, MockClock>::default();
+        list.push_many([1, 2, 3]);
+        assert!(list.timer().is_none());
+
+        list.set_timer(Some(DoOnInterval::with_clock_dont_initial_update(
+            Duration::from_secs(1000),
+            clock.clone(),
+        )));
+        assert!(!list.try_push(4));
+        assert_eq!(list.get_all_ordered(), vec![1, 2, 3]);
+
+        list.timer_mut().unwrap().set_interval(Duration::ZERO);
+        assert_eq!(list.timer().unwrap().interval(), Duration::ZERO);
+        clock.advance(Duration::from_nanos(1));
+        assert!(list.try_push(4));
+        assert!(!list.try_push(5));
+
+        list.set_timer(None);
+        list.push(5);
+        assert_eq!(list.get_all_ordered(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    pub fn windowed_sum_matches_recomputation() {
+        let mut sum = WindowedReducer::<i64, i64, _, _, 7>::new(
+            0,
+            |acc, el| *acc += el,
+            |acc, el| *acc -= el,
+            None,
+        );
+
+        for i in 0..100_i64 {
+            sum.push(i * i - 50 * i);
+            assert_eq!(*sum.value(), sum.cacher().fold(0, |acc, el| acc + el));
+        }
+    }
+
+    #[test]
+    pub fn averages_truncate_round_and_float() {
+        let mut list = MemoryCacher::<u32, 5>::new(None);
+        for i in [1, 2, 2] {
+            list.push(i);
+        }
+
+        assert_eq!(list.average(), Some(1));
+        assert_eq!(list.sum(), 5);
+        assert_eq!(list.average_rounded_u32(), 2);
+        assert!((list.average_as_f64().unwrap() - 5.0 / 3.0).abs() < f64::EPSILON);
+
+        let mut big = MemoryCacher::<u128, 5>::new(None);
+        big.push(u128::MAX);
+        big.push(u128::MAX);
+        assert!(big.average_as_f64().unwrap() > 3.0e38);
+
+        assert_eq!(MemoryCacher::<u32, 5>::new(None).average_as_f64(), None);
+        assert_eq!(MemoryCacher::<u32, 5>::new(None).average(), None);
+    }
+
+    #[test]
+    pub fn generic_average_of_durations_and_floats() {
+        let mut times = MemoryCacher::<Duration, 4>::new(None);
+        assert_eq!(times.average(), None);
+        assert_eq!(times.sum(), Duration::ZERO);
+
+        for ms in [10, 20, 30, 40, 50] {
+            times.push(Duration::from_millis(ms));
+        }
+        assert_eq!(times.sum(), Duration::from_millis(140));
+        assert_eq!(times.average(), Some(Duration::from_millis(35)));
+
+        let mut floats = MemoryCacher::<f64, 4>::new(None);
+        floats.push(1.0);
+        floats.push(2.0);
+        assert!((floats.average().unwrap() - 1.5).abs() < f64::EPSILON);
     }
 }
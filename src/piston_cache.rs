@@ -1,114 +1,645 @@
 //! This functions as a basic cacher for `Piston2D` images
 //!
+//! The textures are made by a [`TextureBackend`] - usually the [`G2dTextureContext`] from a window using [`Cacher::new`], but the [`HeadlessBackend`] can be used to get all of the same bookkeeping without a display, eg. in tests.
+//!
 //! ## Usage
 //! ```rust
+//! use burntnail_utils::piston_cache::{Cacher, HeadlessBackend};
+//! use image::RgbaImage;
+//! use piston_window::{Filter, Flip, ImageSize, TextureSettings};
 //!
-//! use burntnail_utils::piston_cache::Cacher;
-//! let mut cacher = Cacher::new(&mut get_anything_for_docs(), Some("assets"));
+//! //with a window, this would be `Cacher::new(&mut window, Some("assets"))`, which finds the assets folder and uses the window's texture context
+//! let mut cacher = Cacher::with_backend(HeadlessBackend, std::env::temp_dir());
 //!
-//! //then, we can either insert a bunch of textures on start
-//! cacher.insert("sprite.png")?;
-//! cacher.insert("bg.png")?;
+//! //textures can be inserted from files relative to the base path (failures say which file), or from memory
+//! let err = cacher.insert("definitely-not-a-sprite.png").unwrap_err();
+//! assert!(err.to_string().contains("definitely-not-a-sprite.png"));
+//! cacher.insert_from_image("generated", RgbaImage::new(4, 2)).unwrap();
 //!
-//! //or, just grab them as and when we need them
-//! cacher.get("highly-specific-level-thingie.png");
+//! //then we can grab them as and when we need them
+//! assert_eq!(cacher.get("generated").unwrap().get_size(), (4, 2));
 //!
+//! //textures use nearest filtering and no flip by default, which can be changed for the whole cacher with `Cacher::builder`, or for one texture
+//! cacher.get_with("generated", &TextureSettings::new().filter(Filter::Linear), Flip::Vertical).unwrap();
+//! assert_eq!(cacher.len(), 1);
 //! ```
 
 use crate::time_based_structs::scoped_timers::ScopedTimer;
 use find_folder::Search::ParentsThenKids;
+use image::{imageops, RgbaImage};
 use piston_window::{
-    Filter, Flip, G2dTexture, G2dTextureContext, PistonWindow, Texture, TextureSettings,
+    Filter, Flip, G2dTexture, G2dTextureContext, ImageSize, PistonWindow, Texture, TextureSettings,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    result::Result as SResult,
+    time::{Duration, SystemTime},
 };
-use std::{collections::HashMap, path::PathBuf, result::Result as SResult};
 
 use crate::{
-    error_ext::ToErr,
-    error_types::{BError, BResult},
+    error_types::{BError, BResult, Contextable, ErrorAccumulator},
+    resource_cache::{ResourceCache, ResourceLoader},
 };
 
-///Struct to hold a cache of [`G2dTexture`]s
-pub struct Cacher {
+pub use crate::resource_cache::CacheStats;
+
+///The file extensions that [`Cacher::insert_dir`] treats as images by default
+pub const DEFAULT_IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "bmp"];
+
+///How many folders up and down to search for the assets folder
+const SEARCH_DEPTH: u8 = 2;
+
+///Something which can make textures for a [`Cacher`] - usually the [`G2dTextureContext`] from a window, but can be swapped out (eg. for a [`HeadlessBackend`]) so the cache can be used without a display
+pub trait TextureBackend {
+    ///The type of texture made
+    type Texture: ImageSize;
+
+    ///Makes a texture from an image already in memory
+    ///
+    /// # Errors
+    /// Can fail if the backend is unable to make the texture
+    fn texture_from_image(
+        &mut self,
+        img: &RgbaImage,
+        settings: &TextureSettings,
+    ) -> SResult<Self::Texture, String>;
+
+    ///Makes a texture from an image file, flipped
+    ///
+    /// # Errors
+    /// Can fail if the file can't be read, or the backend is unable to make the texture
+    fn texture_from_path(
+        &mut self,
+        path: &Path,
+        flip: Flip,
+        settings: &TextureSettings,
+    ) -> SResult<Self::Texture, String>;
+}
+
+impl TextureBackend for G2dTextureContext {
+    type Texture = G2dTexture;
+
+    fn texture_from_image(
+        &mut self,
+        img: &RgbaImage,
+        settings: &TextureSettings,
+    ) -> SResult<G2dTexture, String> {
+        Texture::from_image(self, img, settings)
+    }
+
+    fn texture_from_path(
+        &mut self,
+        path: &Path,
+        flip: Flip,
+        settings: &TextureSettings,
+    ) -> SResult<G2dTexture, String> {
+        Texture::from_path(self, path, flip, settings)
+    }
+}
+
+///A [`TextureBackend`] which just keeps the decoded images in memory without a GPU - eg. for tests, or tools which want the bookkeeping of a [`Cacher`] but never draw anything
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeadlessBackend;
+
+///A texture from a [`HeadlessBackend`], which is just the decoded image
+#[derive(Debug, Clone)]
+pub struct HeadlessTexture {
+    ///The decoded image, already flipped
+    pub image: RgbaImage,
+}
+
+impl ImageSize for HeadlessTexture {
+    fn get_size(&self) -> (u32, u32) {
+        self.image.dimensions()
+    }
+}
+
+impl TextureBackend for HeadlessBackend {
+    type Texture = HeadlessTexture;
+
+    fn texture_from_image(
+        &mut self,
+        img: &RgbaImage,
+        _settings: &TextureSettings,
+    ) -> SResult<HeadlessTexture, String> {
+        Ok(HeadlessTexture { image: img.clone() })
+    }
+
+    fn texture_from_path(
+        &mut self,
+        path: &Path,
+        flip: Flip,
+        _settings: &TextureSettings,
+    ) -> SResult<HeadlessTexture, String> {
+        let img = image::open(path).map_err(|e| e.to_string())?.to_rgba8();
+        Ok(HeadlessTexture {
+            image: flipped(img, flip),
+        })
+    }
+}
+
+///Struct to hold a cache of textures - [`G2dTexture`]s unless another [`TextureBackend`] is used
+pub struct Cacher<B: TextureBackend = G2dTextureContext> {
+    ///The textures, along with the loader which knows about the files behind them
+    cache: ResourceCache<String, B::Texture, TextureLoader<B>>,
+    ///The file extensions that [`Cacher::insert_dir`] treats as images
+    image_extensions: Vec<String>,
+    ///The path of the texture to use when one can't be loaded, if set
+    fallback: Option<String>,
+    ///The paths which have already been warned about using the fallback, so each is only warned about once
+    warned_missing: HashSet<String>,
+    ///How many times a path has been warned about using the fallback
+    #[cfg(test)]
+    fallback_warnings: usize,
+}
+
+///Loads the textures for a [`Cacher`], and keeps track of everything about them apart from the textures themselves
+struct TextureLoader<B> {
     ///Base path for the assets
     base_path: PathBuf,
-    ///HashMap of paths to textures
-    assets: HashMap<String, G2dTexture>,
-    ///Context for textures from window
-    tc: G2dTextureContext,
+    ///What makes the textures, eg. the texture context from a window
+    backend: B,
+    ///Whether or not to keep the decoded images around after uploading them as textures
+    keep_cpu_copies: bool,
+    ///HashMap of paths to decoded images, only filled if `keep_cpu_copies` is on
+    cpu_copies: HashMap<String, RgbaImage>,
+    ///`HashMap` of paths to the settings and flip each texture was (or is about to be) loaded with
+    loaded_with: HashMap<String, (TextureSettings, Flip)>,
+    ///The settings to use for textures when none are given
+    settings: TextureSettings,
+    ///The flip to use for textures when none is given
+    flip: Flip,
+    ///The unflipped images for textures inserted from memory, which can't be reloaded from the disk
+    in_memory: HashMap<String, RgbaImage>,
+    ///If hot reloading is on, when each file was last modified as of loading it - [`None`] if it couldn't be read
+    modified: Option<HashMap<String, Option<SystemTime>>>,
 }
 
-impl Cacher {
-    ///Function to create a new empty cache.
+///Builder for a [`Cacher`] with different defaults - made with [`Cacher::builder`]
+#[must_use]
+pub struct CacherBuilder<'a> {
+    ///The assets folder to look for
+    path: Option<&'a str>,
+    ///The settings to use for textures when none are given
+    settings: TextureSettings,
+    ///The flip to use for textures when none is given
+    flip: Flip,
+    ///Whether or not to keep the decoded images around
+    keep_cpu_copies: bool,
+    ///The file extensions that [`Cacher::insert_dir`] treats as images, if not the defaults
+    image_extensions: Option<Vec<String>>,
+    ///The most textures to keep, if limited
+    capacity: Option<usize>,
+}
+
+impl<'a> CacherBuilder<'a> {
+    ///Sets the assets folder to look for, rather than `assets`
+    pub const fn path(mut self, path: &'a str) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    ///Sets the settings to use for textures when none are given
+    pub const fn settings(mut self, settings: TextureSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    ///Sets the filter to use for textures when no settings are given, rather than [`Filter::Nearest`]
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.settings = self.settings.filter(filter);
+        self
+    }
+
+    ///Sets the flip to use for textures when none is given, rather than [`Flip::None`]
+    pub const fn flip(mut self, flip: Flip) -> Self {
+        self.flip = flip;
+        self
+    }
+
+    ///Sets whether or not to keep the decoded images around - see [`Cacher::keep_cpu_copies`]
+    pub const fn keep_cpu_copies(mut self, keep: bool) -> Self {
+        self.keep_cpu_copies = keep;
+        self
+    }
+
+    ///Sets the file extensions that [`Cacher::insert_dir`] treats as images, rather than [`DEFAULT_IMAGE_EXTENSIONS`]
+    pub fn image_extensions(
+        mut self,
+        extensions: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.image_extensions = Some(extensions.into_iter().map(Into::into).collect());
+        self
+    }
+
+    ///Limits the cache to `max_entries` textures - see [`Cacher::with_capacity`]
+    pub const fn capacity(mut self, max_entries: usize) -> Self {
+        self.capacity = Some(max_entries);
+        self
+    }
+
+    ///Makes the [`Cacher`], using the texture context from the window
     ///
     /// # Errors
     /// Can fail if it can't find the assets folder
-    fn base_new(win: &mut PistonWindow, path: Option<&str>) -> SResult<Self, find_folder::Error> {
-        let path = ParentsThenKids(2, 2).for_folder(path.unwrap_or("assets"))?;
+    pub fn build(self, win: &mut PistonWindow) -> BResult<Cacher> {
+        self.build_with_backend(win.create_texture_context())
+    }
+
+    ///Makes the [`Cacher`] with a different [`TextureBackend`], eg. a [`HeadlessBackend`]
+    ///
+    /// # Errors
+    /// Can fail if it can't find the assets folder
+    pub fn build_with_backend<B: TextureBackend>(self, backend: B) -> BResult<Cacher<B>> {
+        let base_path = find_assets_folder(self.path.unwrap_or("assets"))?;
+        let mut cacher = Cacher::with_backend(backend, base_path);
+        let loader = cacher.cache.loader_mut();
+        loader.settings = self.settings;
+        loader.flip = self.flip;
+        loader.keep_cpu_copies = self.keep_cpu_copies;
+        if let Some(extensions) = self.image_extensions {
+            cacher.set_image_extensions(extensions);
+        }
+        cacher.cache.set_capacity(self.capacity);
+        Ok(cacher)
+    }
+}
+
+///The result of [`Cacher::get_classified`] - the texture, along with whether it was already in the cache
+#[derive(Debug)]
+pub struct CacheOutcome<'a, T = G2dTexture> {
+    ///The texture that was asked for
+    pub texture: &'a T,
+    ///How long it took to load the texture, or [`None`] if it was already in the cache
+    pub load_time: Option<Duration>,
+}
+
+impl<T> CacheOutcome<'_, T> {
+    ///Whether or not the texture had to be loaded just now, rather than already being in the cache
+    #[must_use]
+    pub const fn was_loaded(&self) -> bool {
+        self.load_time.is_some()
+    }
+}
+
+///Report on what happened during [`Cacher::recreate_all`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RecreateReport {
+    ///How many textures were rebuilt from the retained CPU copies
+    pub from_cpu: usize,
+    ///How many textures had no CPU copy, and so had to be re-read from disk
+    pub from_disk: usize,
+}
 
-        Ok(Self {
-            base_path: path,
-            assets: HashMap::new(),
-            tc: win.create_texture_context(),
+impl<B: TextureBackend> TextureLoader<B> {
+    ///Gets the settings and flip that `p` was (or is about to be) loaded with, or the defaults
+    fn base_wanted(&self, p: &str) -> (TextureSettings, Flip) {
+        self.loaded_with
+            .get(p)
+            .copied()
+            .unwrap_or((self.settings, self.flip))
+    }
+
+    ///Base function for loading a texture from the disk (or from memory if it was inserted from there), keeping a CPU copy if needed
+    fn base_load(
+        &mut self,
+        p: &str,
+        settings: &TextureSettings,
+        flip: Flip,
+    ) -> SResult<B::Texture, String> {
+        if let Some(img) = self.in_memory.get(p) {
+            let img = flipped(img.clone(), flip);
+            //a CPU copy from when it was loaded from the disk would be out of date
+            self.cpu_copies.remove(p);
+            return self
+                .backend
+                .texture_from_image(&img, settings)
+                .map_err(|e| format!("{p}: {e}"));
+        }
+
+        let path = self.base_path.join(p);
+        let with_path = |e: String| format!("{}: {e}", path.display());
+
+        Ok(if self.keep_cpu_copies {
+            let img = image::open(&path)
+                .map_err(|e| with_path(e.to_string()))?
+                .to_rgba8();
+            let img = flipped(img, flip);
+            let tex = self
+                .backend
+                .texture_from_image(&img, settings)
+                .map_err(with_path)?;
+            self.cpu_copies.insert(p.to_string(), img);
+            tex
+        } else {
+            let tex = self
+                .backend
+                .texture_from_path(&path, flip, settings)
+                .map_err(with_path)?;
+            //a CPU copy from an earlier load would have the old flip
+            self.cpu_copies.remove(p);
+            tex
         })
     }
 
+    ///Base function for remembering when the file for `p` was last modified, if hot reloading is on and it came from the disk
+    fn base_record_modified(&mut self, p: &str) {
+        let Some(times) = &mut self.modified else {
+            return;
+        };
+        if self.in_memory.contains_key(p) {
+            times.remove(p);
+        } else {
+            times.insert(p.to_string(), modified_time(&self.base_path.join(p)));
+        }
+    }
+
+    ///Base function for rebuilding a texture with the current texture context, using the CPU copy if one exists, else going back to the disk
+    fn base_recreate(
+        &mut self,
+        p: &str,
+        report: &mut RecreateReport,
+    ) -> SResult<B::Texture, String> {
+        let (ts, flip) = self.base_wanted(p);
+        if let Some(img) = self.in_memory.get(p) {
+            report.from_cpu += 1;
+            self.backend
+                .texture_from_image(&flipped(img.clone(), flip), &ts)
+        } else if let Some(img) = self.cpu_copies.get(p) {
+            report.from_cpu += 1;
+            self.backend.texture_from_image(img, &ts)
+        } else {
+            report.from_disk += 1;
+            self.backend
+                .texture_from_path(&self.base_path.join(p), flip, &ts)
+        }
+    }
+}
+
+impl<B: TextureBackend> ResourceLoader<String, B::Texture> for TextureLoader<B> {
+    fn load(&mut self, p: &String) -> BResult<B::Texture> {
+        let (settings, flip) = self.base_wanted(p);
+        let tex = self.base_load(p, &settings, flip).map_err(BError::msg)?;
+        self.base_record_modified(p);
+        Ok(tex)
+    }
+
+    fn unload(&mut self, p: &String, _tex: B::Texture) {
+        self.cpu_copies.remove(p);
+        self.loaded_with.remove(p);
+        self.in_memory.remove(p);
+        if let Some(times) = &mut self.modified {
+            times.remove(p);
+        }
+    }
+}
+
+impl<B: TextureBackend> Cacher<B> {
+    ///Creates a new empty cache which loads files relative to `base_path` as-is, making the textures with `backend` - eg. a [`HeadlessBackend`] for tests.
+    ///
+    ///Unlike [`Cacher::new`], this doesn't go looking for the folder, and it doesn't need to exist yet.
+    pub fn with_backend(backend: B, base_path: impl Into<PathBuf>) -> Self {
+        let loader = TextureLoader {
+            base_path: base_path.into(),
+            backend,
+            keep_cpu_copies: false,
+            cpu_copies: HashMap::new(),
+            loaded_with: HashMap::new(),
+            settings: TextureSettings::new().filter(Filter::Nearest),
+            flip: Flip::None,
+            in_memory: HashMap::new(),
+            modified: None,
+        };
+        Self {
+            cache: ResourceCache::new(loader),
+            image_extensions: DEFAULT_IMAGE_EXTENSIONS.map(String::from).to_vec(),
+            fallback: None,
+            warned_missing: HashSet::new(),
+            #[cfg(test)]
+            fallback_warnings: 0,
+        }
+    }
+
+    ///Gets the settings and flip to use for textures when none are given
+    const fn base_defaults(&self) -> (TextureSettings, Flip) {
+        let loader = self.cache.loader();
+        (loader.settings, loader.flip)
+    }
+
     ///Base function for getting something
     ///
-    ///Takes a relative path, returns either `Err(String)` from insertion, or an `Ok(G2DTexture)` with the result from the hashmap if insertion had no errors
-    fn base_get(&mut self, p: &str) -> SResult<&G2dTexture, String> {
-        self.base_insert(p)
-            .map(|_| {
-                self.assets
-                    .get(p)
-                    .ok_or_else(|| "Asset missing in internal storage".into())
-            })
-            .and_then(std::convert::identity) //Taken from the unstable code, issue: 70142, nice code: `.flatten()`
+    ///Takes a relative path, returns either an `Err` from insertion, or an `Ok(CacheOutcome)` with the result from the cache if insertion had no errors
+    fn base_get(
+        &mut self,
+        p: &str,
+        settings: &TextureSettings,
+        flip: Flip,
+    ) -> BResult<CacheOutcome<'_, B::Texture>> {
+        let load_time = self.base_insert(p, settings, flip)?;
+        let texture = self
+            .cache
+            .peek(p)
+            .ok_or_else(|| BError::msg("Asset missing in internal storage"))?;
+
+        Ok(CacheOutcome { texture, load_time })
     }
 
-    ///Base function for inserting something.
+    ///Base function for inserting something with the given settings and flip, reloading it if it was loaded with different ones.
     ///
-    ///Takes a relative path, returns `Ok` if all worked or element already existed, else returns `Err(String)` if failure
-    fn base_insert(&mut self, p: &str) -> SResult<(), String> {
-        if self.assets.contains_key(p) {
-            return Ok(());
+    ///Returns how long it took to load, or [`None`] if it was already in the cache
+    fn base_insert(
+        &mut self,
+        p: &str,
+        settings: &TextureSettings,
+        flip: Flip,
+    ) -> BResult<Option<Duration>> {
+        let key = p.to_string();
+        let wanted = (*settings, flip);
+
+        match self.cache.loader().loaded_with.get(p).copied() {
+            Some((old_settings, old_flip))
+                if old_flip == flip && same_settings(&old_settings, settings) =>
+            {
+                self.cache.insert(&key)
+            }
+            Some(old) => {
+                crate::bn_trace!("Reloading {p} with different settings");
+                self.cache
+                    .loader_mut()
+                    .loaded_with
+                    .insert(key.clone(), wanted);
+                self.cache.reload(&key).map(Some).inspect_err(|_| {
+                    self.cache.loader_mut().loaded_with.insert(key.clone(), old);
+                })
+            }
+            None => {
+                self.cache
+                    .loader_mut()
+                    .loaded_with
+                    .insert(key.clone(), wanted);
+                self.cache.insert(&key).inspect_err(|_| {
+                    self.cache.loader_mut().loaded_with.remove(p);
+                })
+            }
         }
+    }
+
+    ///Base function for finding all of the images in a directory, as keys relative to the base path.
+    ///
+    ///Adds any errors from reading the directory to `errors` rather than stopping.
+    fn base_find_images(
+        &self,
+        dir: &Path,
+        recursive: bool,
+        found: &mut Vec<String>,
+        errors: &mut ErrorAccumulator,
+    ) {
+        let entries = match std::fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) => {
+                errors.push_context(e, format!("Reading {}", dir.display()));
+                return;
+            }
+        };
 
-        #[cfg(feature = "tracing")]
-        tracing::trace!("Inserting {p}");
-        #[cfg(not(feature = "tracing"))]
-        println!("Inserting {p}");
+        for entry in entries {
+            let path = match entry {
+                Ok(entry) => entry.path(),
+                Err(e) => {
+                    errors.push_context(e, format!("Reading {}", dir.display()));
+                    continue;
+                }
+            };
 
-        let _st = ScopedTimer::new(format!("Geting {p}"));
+            if path.is_dir() {
+                if recursive {
+                    self.base_find_images(&path, recursive, found, errors);
+                }
+                continue;
+            }
 
-        let path = self.base_path.join(p);
-        let ts = TextureSettings::new().filter(Filter::Nearest);
+            let is_image = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| {
+                    self.image_extensions
+                        .iter()
+                        .any(|wanted| wanted.eq_ignore_ascii_case(ext))
+                });
+            if !is_image {
+                continue;
+            }
 
-        match Texture::from_path(&mut self.tc, path, Flip::None, &ts) {
-            Ok(tex) => {
-                self.assets.insert(p.to_string(), tex);
-                Ok(())
+            let key = path
+                .strip_prefix(&self.cache.loader().base_path)
+                .ok()
+                .and_then(|rel| {
+                    rel.components()
+                        .map(|c| c.as_os_str().to_str())
+                        .collect::<Option<Vec<_>>>()
+                });
+            match key {
+                Some(parts) => found.push(parts.join("/")),
+                None => errors.push(BError::msg(format!(
+                    "{} isn't a UTF-8 path inside the assets folder",
+                    path.display()
+                ))),
             }
-            Err(e) => Err(e),
         }
     }
+
+    ///Base function for rebuilding every texture with a fresh texture context.
+    ///
+    ///Uses the CPU copy if one exists, else goes back to the disk. Returns `Err(String)` on the first failure
+    fn base_recreate_all(&mut self) -> SResult<RecreateReport, String> {
+        let _st = ScopedTimer::new("Recreating all textures");
+
+        let mut report = RecreateReport::default();
+
+        let keys: Vec<String> = self.cache.keys().cloned().collect();
+        for p in keys {
+            let tex = self
+                .cache
+                .loader_mut()
+                .base_recreate(&p, &mut report)
+                .map_err(|e| format!("{p}: {e}"))?;
+            self.cache.insert_value(p, tex);
+        }
+
+        Ok(report)
+    }
 }
 
 impl Cacher {
-    ///Function to create a new empty cache.
+    ///Function to create a new empty cache, using the texture context from the window.
+    ///
+    ///Looks for the assets folder (`assets` if not given) in the current directory, a couple of folders above it, and a couple of levels of subfolders.
     ///
     /// # Errors
-    /// Can fail if it can't find the assets folder
+    /// Can fail if it can't find the assets folder - the error says where it looked
     pub fn new(win: &mut PistonWindow, path: Option<&str>) -> BResult<Self> {
-        Self::base_new(win, path).ae()
+        let base_path = find_assets_folder(path.unwrap_or("assets"))?;
+        Ok(Self::with_backend(win.create_texture_context(), base_path))
+    }
+
+    ///Same as [`Cacher::new`], but only keeps up to `max_entries` textures (at least 1).
+    ///
+    ///Getting or inserting a texture marks it as used, and when a new one is loaded past the limit the least recently used ones are evicted.
+    ///
+    /// # Errors
+    /// Can fail if it can't find the assets folder
+    pub fn with_capacity(
+        win: &mut PistonWindow,
+        path: Option<&str>,
+        max_entries: usize,
+    ) -> BResult<Self> {
+        let mut cacher = Self::new(win, path)?;
+        cacher.cache.set_capacity(Some(max_entries));
+        Ok(cacher)
+    }
+
+    ///Makes a builder for a cache with different defaults, eg. linear filtering or a vertical flip for every texture
+    pub fn builder<'a>() -> CacherBuilder<'a> {
+        CacherBuilder {
+            path: None,
+            settings: TextureSettings::new().filter(Filter::Nearest),
+            flip: Flip::None,
+            keep_cpu_copies: false,
+            image_extensions: None,
+            capacity: None,
+        }
+    }
+
+    ///Rebuilds every texture using a new texture context from the window - useful for when the window/device gets recreated and all of the old textures are invalid.
+    ///
+    ///Textures with a CPU copy (see [`Cacher::keep_cpu_copies`]) are rebuilt from that, and the rest are read from the disk again.
+    ///
+    /// # Errors
+    /// - Unable to rebuild a texture using [`Texture::from_image`] or [`Texture::from_path`]
+    pub fn recreate_all(&mut self, win: &mut PistonWindow) -> BResult<RecreateReport> {
+        self.recreate_all_with(win.create_texture_context())
     }
+}
 
-    ///Gets a [`G2dTexture`] from the cache. Returns [`None`] if there is no asset with that path.
+impl<B: TextureBackend> Cacher<B> {
+    ///Gets a texture from the cache. Returns [`None`] if there is no asset with that path.
     ///
     /// # Errors
     /// - Unable to find the texture using [`Texture::from_path`]
-    pub fn get(&mut self, p: &str) -> BResult<&G2dTexture> {
-        match self.base_get(p) {
-            Ok(tex) => Ok(tex),
+    pub fn get(&mut self, p: &str) -> BResult<&B::Texture> {
+        self.get_classified(p).map(|outcome| outcome.texture)
+    }
+
+    ///Same as [`Cacher::get`], but also says whether the texture was already in the cache or had to be loaded just now (and how long that took).
+    ///
+    /// # Errors
+    /// - Unable to find the texture using [`Texture::from_path`]
+    pub fn get_classified(&mut self, p: &str) -> BResult<CacheOutcome<'_, B::Texture>> {
+        let (settings, flip) = self.base_defaults();
+        match self.base_get(p, &settings, flip) {
+            Ok(outcome) => Ok(outcome),
             Err(e) => Err(BError::msg(format!("Texture Get Error: {e}"))),
         }
     }
@@ -118,7 +649,846 @@ impl Cacher {
     /// # Errors
     /// - Unable to find the texture using [`Texture::from_path`]
     pub fn insert(&mut self, p: &str) -> BResult<()> {
-        self.base_insert(p)
-            .map_err(|s| BError::msg(format!("Texture Insert Error: {s}")))
+        let (settings, flip) = self.base_defaults();
+        self.insert_with(p, &settings, flip)
+    }
+
+    ///Same as [`Cacher::insert`], but with the given settings and flip rather than the cacher-wide ones.
+    ///
+    ///The cache holds one texture per path - if it is already cached with different settings or a different flip, it is reloaded and replaced rather than giving back the old one.
+    ///
+    /// # Errors
+    /// - Unable to find the texture using [`Texture::from_path`]
+    pub fn insert_with(&mut self, p: &str, settings: &TextureSettings, flip: Flip) -> BResult<()> {
+        self.base_insert(p, settings, flip)
+            .map(|_| ())
+            .map_err(|e| BError::msg(format!("Texture Insert Error: {e}")))
+    }
+
+    ///Inserts a texture decoded from an image file's bytes, eg. from [`include_bytes!`], under `key` - which shares the same namespace as paths, so it can be got with [`Cacher::get`] like any other texture.
+    ///
+    /// # Errors
+    /// - Unable to decode the image
+    /// - Unable to make the texture using [`Texture::from_image`]
+    pub fn insert_from_bytes(&mut self, key: &str, bytes: &[u8]) -> BResult<()> {
+        let img = image::load_from_memory(bytes)
+            .map_err(|e| BError::msg(format!("Texture Insert Error: {key}: {e}")))?
+            .to_rgba8();
+        self.insert_from_image(key, img)
+    }
+
+    ///Inserts a texture from an image already in memory, eg. one generated at runtime, under `key`. Replaces anything already cached under `key`.
+    ///
+    ///The image is kept around so the texture can be rebuilt by [`Cacher::recreate_all`] or with different settings, as there is no file to go back to.
+    ///
+    /// # Errors
+    /// - Unable to make the texture using [`Texture::from_image`]
+    pub fn insert_from_image(&mut self, key: &str, img: RgbaImage) -> BResult<()> {
+        let key = key.to_string();
+        let loader = self.cache.loader_mut();
+        let defaults = (loader.settings, loader.flip);
+        let old_img = loader.in_memory.insert(key.clone(), img);
+        let old_with = loader.loaded_with.insert(key.clone(), defaults);
+
+        if let Err(e) = self.cache.reload(&key) {
+            //put back whatever was there before, which is still in the cache
+            let loader = self.cache.loader_mut();
+            match old_img {
+                Some(img) => loader.in_memory.insert(key.clone(), img),
+                None => loader.in_memory.remove(&key),
+            };
+            match old_with {
+                Some(with) => loader.loaded_with.insert(key, with),
+                None => loader.loaded_with.remove(&key),
+            };
+            return Err(BError::msg(format!("Texture Insert Error: {e}")));
+        }
+        Ok(())
+    }
+
+    ///Same as [`Cacher::get`], but with the given settings and flip rather than the cacher-wide ones. Reloads the texture if it was cached with different ones - see [`Cacher::insert_with`].
+    ///
+    /// # Errors
+    /// - Unable to find the texture using [`Texture::from_path`]
+    pub fn get_with(
+        &mut self,
+        p: &str,
+        settings: &TextureSettings,
+        flip: Flip,
+    ) -> BResult<&B::Texture> {
+        match self.base_get(p, settings, flip) {
+            Ok(outcome) => Ok(outcome.texture),
+            Err(e) => Err(BError::msg(format!("Texture Get Error: {e}"))),
+        }
+    }
+
+    ///Loads the texture at `p` and uses it in [`Cacher::get_or_fallback`] for any textures that can't be loaded - eg. a bright pink square, so missing assets are obvious.
+    ///
+    ///The fallback texture is never evicted by [`Cacher::with_capacity`], and stops being the fallback if it is removed.
+    ///
+    /// # Errors
+    /// - Unable to find the texture using [`Texture::from_path`]
+    pub fn set_fallback(&mut self, p: &str) -> BResult<()> {
+        self.insert(p)?;
+        if let Some(old) = self.fallback.replace(p.to_string()) {
+            self.cache.unpin(old.as_str());
+        }
+        self.cache.pin(p.to_string());
+        Ok(())
+    }
+
+    ///Same as [`Cacher::get`], but if the texture can't be loaded then the one from [`Cacher::set_fallback`] is given back instead. A warning is logged the first time each path falls back, rather than every time.
+    ///
+    /// # Errors
+    /// - Unable to find the texture using [`Texture::from_path`], and there is no fallback set
+    pub fn get_or_fallback(&mut self, p: &str) -> BResult<&B::Texture> {
+        let (settings, flip) = self.base_defaults();
+        let key = match self.base_insert(p, &settings, flip) {
+            Ok(_) => {
+                self.warned_missing.remove(p);
+                p
+            }
+            Err(e) => {
+                let Some(fallback) = &self.fallback else {
+                    return Err(BError::msg(format!("Texture Get Error: {e}")));
+                };
+                if self.warned_missing.insert(p.to_string()) {
+                    crate::bn_warn!(error = %e, "Couldn't load {p}, using the fallback texture");
+                    #[cfg(test)]
+                    {
+                        self.fallback_warnings += 1;
+                    }
+                }
+                fallback
+            }
+        };
+
+        self.cache
+            .peek(key)
+            .ok_or_else(|| BError::msg("Texture Get Error: Asset missing in internal storage"))
+    }
+
+    ///Inserts every image in the directory given (relative to the assets folder), keyed by their paths relative to the assets folder like `sprites/ship.png`. Returns how many images are now in the cache from that directory.
+    ///
+    ///Images are files with one of the extensions from [`Cacher::set_image_extensions`], which defaults to [`DEFAULT_IMAGE_EXTENSIONS`]. If `recursive` is on, then subdirectories are searched too.
+    ///
+    /// # Errors
+    /// If any files or directories fail, the rest are still loaded and then all of the failures are returned together
+    pub fn insert_dir(&mut self, rel_dir: &str, recursive: bool) -> BResult<usize> {
+        let _st = ScopedTimer::new(format!("Inserting everything in {rel_dir}"));
+
+        let mut errors = ErrorAccumulator::new();
+        let mut found = vec![];
+        self.base_find_images(
+            &self.cache.loader().base_path.join(rel_dir),
+            recursive,
+            &mut found,
+            &mut errors,
+        );
+        found.sort();
+
+        let (settings, flip) = self.base_defaults();
+        let mut loaded = 0;
+        for p in found {
+            match self.base_insert(&p, &settings, flip) {
+                Ok(_) => loaded += 1,
+                Err(e) => errors.push_context(e, p),
+            }
+        }
+
+        errors
+            .into_result()
+            .with_context(|| format!("Inserted {loaded} images from {rel_dir}, but some failed"))?;
+        Ok(loaded)
+    }
+
+    ///Sets the file extensions that [`Cacher::insert_dir`] treats as images, eg. `["png", "gif"]`. Case doesn't matter.
+    pub fn set_image_extensions(
+        &mut self,
+        extensions: impl IntoIterator<Item = impl Into<String>>,
+    ) {
+        self.image_extensions = extensions.into_iter().map(Into::into).collect();
+    }
+
+    ///Sets whether or not to keep the decoded [`RgbaImage`]s around after they've been uploaded, so that [`Cacher::recreate_all`] doesn't need to hit the disk again.
+    ///
+    ///Only affects textures inserted after this is called. Turning it off drops all of the current CPU copies, apart from the images for textures inserted from memory.
+    pub fn keep_cpu_copies(&mut self, keep: bool) {
+        let loader = self.cache.loader_mut();
+        loader.keep_cpu_copies = keep;
+        if !keep {
+            loader.cpu_copies.clear();
+        }
+    }
+
+    ///Turns on hot reloading for development - from now on, when each file was last modified is remembered when it gets loaded, so [`Cacher::poll_reload`] can pick up any changes.
+    ///
+    ///Textures already in the cache start being watched from now.
+    pub fn enable_hot_reload(&mut self) {
+        if self.cache.loader().modified.is_some() {
+            return;
+        }
+        let keys: Vec<String> = self.cache.keys().cloned().collect();
+        let loader = self.cache.loader_mut();
+        loader.modified = Some(HashMap::new());
+        for p in keys {
+            loader.base_record_modified(&p);
+        }
+    }
+
+    ///Checks every watched file, and reloads any which have been modified since they were loaded - returning their paths. Meant to be called every so often, eg. using a [`crate::time_based_structs::do_on_interval::DoOnInterval`].
+    ///
+    ///Textures inserted from memory are never reloaded. If a file can't be read or reloaded, the old texture is kept and a warning is logged - it'll be tried again once the file is modified again.
+    ///
+    /// # Errors
+    /// - Hot reloading hasn't been turned on with [`Cacher::enable_hot_reload`]
+    pub fn poll_reload(&mut self) -> BResult<Vec<String>> {
+        let loader = self.cache.loader();
+        let Some(times) = &loader.modified else {
+            return Err(BError::msg(
+                "Texture Reload Error: hot reloading isn't enabled",
+            ));
+        };
+
+        let changed: Vec<(String, Option<SystemTime>)> = times
+            .iter()
+            .filter_map(|(p, old)| {
+                let new = modified_time(&loader.base_path.join(p));
+                (new != *old).then(|| (p.clone(), new))
+            })
+            .collect();
+
+        let mut reloaded = vec![];
+        for (p, new) in changed {
+            if let Some(times) = &mut self.cache.loader_mut().modified {
+                times.insert(p.clone(), new);
+            }
+            if new.is_none() {
+                crate::bn_warn!(path = %p, "Unable to read texture to reload, keeping the old one");
+                continue;
+            }
+
+            match self.cache.reload(&p) {
+                Ok(_) => {
+                    crate::bn_debug!("Reloaded {p}");
+                    reloaded.push(p);
+                }
+                Err(e) => {
+                    crate::bn_warn!(path = %p, %e, "Unable to reload texture, keeping the old one");
+                }
+            }
+        }
+
+        Ok(reloaded)
+    }
+
+    ///Whether or not a texture is in the cache
+    #[must_use]
+    pub fn contains(&self, p: &str) -> bool {
+        self.cache.contains(p)
+    }
+
+    ///Gets the paths of all of the textures in the cache, in no particular order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.cache.keys().map(String::as_str)
+    }
+
+    ///Gets how the cache has been used since it was made, or since [`Cacher::reset_stats`]
+    #[must_use]
+    pub const fn stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    ///Resets all of the [`CacheStats`] back to zero
+    pub fn reset_stats(&mut self) {
+        self.cache.reset_stats();
+    }
+
+    ///Removes a texture (and its CPU copy) from the cache, returning whether or not it was there
+    pub fn remove(&mut self, p: &str) -> bool {
+        if self.fallback.as_deref() == Some(p) {
+            self.fallback = None;
+        }
+        self.cache.remove(p)
+    }
+
+    ///Removes every texture (and CPU copy) from the cache
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.fallback = None;
+        self.warned_missing.clear();
+    }
+
+    ///Gets how many textures are in the cache
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    ///Whether or not the cache is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    ///Gets roughly how many bytes the cache is taking up - 4 bytes per pixel for each texture (ignoring mipmaps), plus the CPU copies from [`Cacher::cpu_bytes`]
+    #[must_use]
+    pub fn approx_bytes(&self) -> usize {
+        let texture_bytes: usize = self
+            .cache
+            .iter()
+            .map(|(_, tex)| {
+                let (w, h) = tex.get_size();
+                w as usize * h as usize * 4
+            })
+            .sum();
+        texture_bytes + self.cpu_bytes()
+    }
+
+    ///Gets how many bytes the CPU copies are currently taking up, including the images for textures inserted from memory.
+    ///
+    ///This is only for reporting - nothing gets evicted based on it, as [`Cacher::with_capacity`] limits how many textures there are rather than how many bytes.
+    #[must_use]
+    pub fn cpu_bytes(&self) -> usize {
+        let loader = self.cache.loader();
+        loader
+            .cpu_copies
+            .values()
+            .chain(loader.in_memory.values())
+            .map(|img| img.as_raw().len())
+            .sum()
+    }
+
+    ///Rebuilds every texture using a new backend - useful for when the window/device gets recreated and all of the old textures are invalid. See [`Cacher::recreate_all`].
+    ///
+    /// # Errors
+    /// - Unable to rebuild a texture using the backend
+    pub fn recreate_all_with(&mut self, backend: B) -> BResult<RecreateReport> {
+        self.cache.loader_mut().backend = backend;
+        self.base_recreate_all()
+            .map_err(|s| BError::msg(format!("Texture Recreate Error: {s}")))
+    }
+}
+
+///Finds the assets folder the same way as [`Cacher::new`] - in the current directory, a couple of folders above it, and a couple of levels of subfolders.
+///
+/// # Errors
+/// Can fail if it can't find the folder - the error says where it looked
+pub(crate) fn find_assets_folder(folder: &str) -> BResult<PathBuf> {
+    ParentsThenKids(SEARCH_DEPTH, SEARCH_DEPTH)
+        .for_folder(folder)
+        .map_err(|e| {
+            let searched = std::env::current_dir().map_or_else(
+                |_| "around the current directory".to_string(),
+                |cwd| searched_locations(&cwd),
+            );
+            BError::msg(format!(
+                "Unable to find the {folder} folder ({e}) - searched {searched}"
+            ))
+        })
+}
+
+///Describes where [`find_assets_folder`] looks when starting from `cwd`
+fn searched_locations(cwd: &Path) -> String {
+    let parents = cwd
+        .ancestors()
+        .take(usize::from(SEARCH_DEPTH) + 1)
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{parents} and up to {SEARCH_DEPTH} levels of subfolders of {}",
+        cwd.display()
+    )
+}
+
+///Gets when the file at `path` was last modified, or [`None`] if that can't be read
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+///Checks whether two [`TextureSettings`] would make the same texture
+fn same_settings(a: &TextureSettings, b: &TextureSettings) -> bool {
+    a.get_convert_gamma() == b.get_convert_gamma()
+        && a.get_compress() == b.get_compress()
+        && a.get_generate_mipmap() == b.get_generate_mipmap()
+        && a.get_min() == b.get_min()
+        && a.get_mag() == b.get_mag()
+        && a.get_mipmap() == b.get_mipmap()
+        && a.get_wrap_u() == b.get_wrap_u()
+        && a.get_wrap_v() == b.get_wrap_v()
+        && a.get_border_color().map(f32::to_bits) == b.get_border_color().map(f32::to_bits)
+}
+
+///Flips a decoded image in the same way that [`Texture::from_path`] would
+fn flipped(img: RgbaImage, flip: Flip) -> RgbaImage {
+    match flip {
+        Flip::None => img,
+        Flip::Vertical => imageops::flip_vertical(&img),
+        Flip::Horizontal => imageops::flip_horizontal(&img),
+        #[allow(unreachable_patterns)] //only some versions have `Flip::Both`
+        _ => imageops::rotate180(&img),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///A texture from a [`CountingBackend`], which knows which load it came from
+    #[derive(Debug)]
+    struct CountedTexture(usize);
+
+    impl ImageSize for CountedTexture {
+        fn get_size(&self) -> (u32, u32) {
+            (1, 1)
+        }
+    }
+
+    ///A backend which doesn't need the files to exist - paths with `missing` in them fail, files which do exist have to decode, images from memory can't be empty, and everything else works
+    #[derive(Default)]
+    struct CountingBackend {
+        ///How many textures have been made
+        made: usize,
+    }
+
+    impl TextureBackend for CountingBackend {
+        type Texture = CountedTexture;
+
+        fn texture_from_image(
+            &mut self,
+            img: &RgbaImage,
+            _settings: &TextureSettings,
+        ) -> SResult<CountedTexture, String> {
+            if img.width() == 0 || img.height() == 0 {
+                return Err("empty image".to_string());
+            }
+            self.made += 1;
+            Ok(CountedTexture(self.made))
+        }
+
+        fn texture_from_path(
+            &mut self,
+            path: &Path,
+            _flip: Flip,
+            _settings: &TextureSettings,
+        ) -> SResult<CountedTexture, String> {
+            if path.to_string_lossy().contains("missing") {
+                return Err("no such file".to_string());
+            }
+            if path.exists() {
+                image::open(path).map_err(|e| e.to_string())?;
+            }
+            self.made += 1;
+            Ok(CountedTexture(self.made))
+        }
+    }
+
+    ///A [`Cacher`] over a [`CountingBackend`], with a base path that doesn't need to exist
+    fn headless() -> Cacher<CountingBackend> {
+        Cacher::with_backend(CountingBackend::default(), "headless-assets")
+    }
+
+    ///Makes a new empty folder in the temp directory for a test to put images in
+    fn temp_assets(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "burntnail_utils_piston_{name}_{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    ///Sets when the file at `path` was last modified to `secs` seconds after the epoch, so tests don't have to wait for the clock to tick over
+    fn set_modified(path: &Path, secs: u64) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn duplicate_insert_is_a_no_op() {
+        let mut cacher = headless();
+
+        cacher.insert("sprite.png").unwrap();
+        cacher.insert("sprite.png").unwrap();
+        assert_eq!(cacher.cache.loader().backend.made, 1);
+        assert_eq!(cacher.len(), 1);
+        assert_eq!((cacher.stats().hits, cacher.stats().misses), (1, 1));
+
+        let outcome = cacher.get_classified("sprite.png").unwrap();
+        assert!(!outcome.was_loaded());
+        assert_eq!(outcome.texture.0, 1);
+
+        //the same settings as the defaults is still a hit, but different ones reload it
+        let (settings, flip) = cacher.base_defaults();
+        cacher.insert_with("sprite.png", &settings, flip).unwrap();
+        assert_eq!(cacher.cache.loader().backend.made, 1);
+        cacher
+            .insert_with("sprite.png", &settings, Flip::Vertical)
+            .unwrap();
+        assert_eq!(cacher.cache.loader().backend.made, 2);
+        assert_eq!(cacher.len(), 1);
+    }
+
+    #[test]
+    fn get_classified_says_when_it_loaded() {
+        let mut cacher = headless();
+
+        let first = cacher.get_classified("sprite.png").unwrap();
+        assert!(first.was_loaded());
+        assert!(first.load_time.is_some());
+        assert_eq!(first.texture.0, 1);
+
+        let second = cacher.get_classified("sprite.png").unwrap();
+        assert!(!second.was_loaded());
+        assert_eq!(second.load_time, None);
+        assert_eq!(second.texture.0, 1);
+    }
+
+    #[test]
+    fn errors_include_the_path() {
+        let mut cacher = headless();
+
+        let insert = cacher
+            .insert("sprites/missing.png")
+            .unwrap_err()
+            .to_string();
+        assert!(insert.starts_with("Texture Insert Error"), "{insert}");
+        assert!(
+            insert.contains(
+                &Path::new("headless-assets")
+                    .join("sprites/missing.png")
+                    .display()
+                    .to_string()
+            ),
+            "{insert}"
+        );
+        assert!(insert.contains("no such file"), "{insert}");
+
+        let get = cacher.get("missing.png").unwrap_err().to_string();
+        assert!(get.contains("missing.png"), "{get}");
+        assert!(cacher.is_empty());
+        assert!(cacher.cache.loader().loaded_with.is_empty());
+        assert_eq!(cacher.stats().insert_failures, 2);
+
+        //with a fallback, the missing texture gives that back instead
+        cacher.set_fallback("fallback.png").unwrap();
+        assert_eq!(cacher.get_or_fallback("missing.png").unwrap().0, 1);
+    }
+
+    #[test]
+    fn find_folder_failures_say_where_they_looked() {
+        assert_eq!(
+            searched_locations(Path::new("/a/b/c")),
+            "/a/b/c, /a/b, /a and up to 2 levels of subfolders of /a/b/c"
+        );
+
+        let err = find_assets_folder("burntnail_utils_definitely_not_a_folder")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Unable to find the burntnail_utils_definitely_not_a_folder folder"),
+            "{err}"
+        );
+        assert!(
+            err.contains(&std::env::current_dir().unwrap().display().to_string()),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn modified_times() {
+        let path = std::env::temp_dir().join("burntnail_utils_piston_cache_modified.png");
+        fs::write(&path, b"not really a png").unwrap();
+        let first = modified_time(&path);
+        assert!(first.is_some());
+        assert_eq!(modified_time(&path), first);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(modified_time(&path), None);
+    }
+
+    #[test]
+    fn recreate_all_counts_where_textures_came_from() {
+        let dir = temp_assets("recreate");
+        for name in ["disk.png", "kept.png"] {
+            RgbaImage::new(2, 2).save(dir.join(name)).unwrap();
+        }
+        let mut cacher = Cacher::with_backend(CountingBackend::default(), &dir);
+
+        cacher.insert("disk.png").unwrap();
+        cacher.keep_cpu_copies(true);
+        cacher.insert("kept.png").unwrap();
+        cacher
+            .insert_from_image("generated", RgbaImage::new(1, 3))
+            .unwrap();
+        assert_eq!(cacher.cpu_bytes(), 2 * 2 * 4 + 3 * 4);
+
+        let report = cacher
+            .recreate_all_with(CountingBackend::default())
+            .unwrap();
+        assert_eq!(
+            report,
+            RecreateReport {
+                from_cpu: 2,
+                from_disk: 1
+            }
+        );
+        assert_eq!(cacher.cache.loader().backend.made, 3);
+        assert_eq!(cacher.len(), 3);
+
+        //without the CPU copies, only the image from memory can be rebuilt without going back to the disk
+        cacher.keep_cpu_copies(false);
+        assert_eq!(cacher.cpu_bytes(), 3 * 4);
+        let report = cacher
+            .recreate_all_with(CountingBackend::default())
+            .unwrap();
+        assert_eq!(
+            report,
+            RecreateReport {
+                from_cpu: 1,
+                from_disk: 2
+            }
+        );
+        assert_eq!(cacher.cache.loader().backend.made, 3);
+
+        //and if the file is no good any more, it says which one
+        fs::write(dir.join("disk.png"), b"not really a png").unwrap();
+        let err = cacher
+            .recreate_all_with(CountingBackend::default())
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Texture Recreate Error"), "{err}");
+        assert!(err.contains("disk.png"), "{err}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn poll_reload_picks_up_modified_files() {
+        let dir = temp_assets("hot_reload");
+        let path = dir.join("sprite.png");
+        RgbaImage::new(2, 2).save(&path).unwrap();
+        let mut cacher = Cacher::with_backend(CountingBackend::default(), &dir);
+        assert!(cacher.poll_reload().is_err());
+
+        cacher.insert("sprite.png").unwrap();
+        cacher
+            .insert_from_image("generated", RgbaImage::new(1, 1))
+            .unwrap();
+        cacher.enable_hot_reload();
+        assert!(cacher.poll_reload().unwrap().is_empty());
+
+        set_modified(&path, 1);
+        assert_eq!(cacher.poll_reload().unwrap(), ["sprite.png"]);
+        assert_eq!(cacher.get("sprite.png").unwrap().0, 3);
+        assert!(cacher.poll_reload().unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn poll_reload_keeps_the_old_texture_if_the_new_file_is_broken() {
+        let dir = temp_assets("hot_reload_broken");
+        let path = dir.join("sprite.png");
+        RgbaImage::new(2, 2).save(&path).unwrap();
+        let mut cacher = Cacher::with_backend(CountingBackend::default(), &dir);
+        cacher.enable_hot_reload();
+        cacher.insert("sprite.png").unwrap();
+
+        //eg. caught halfway through the file being saved
+        fs::write(&path, b"not really a png").unwrap();
+        set_modified(&path, 1);
+        assert!(cacher.poll_reload().unwrap().is_empty());
+        assert_eq!(cacher.get("sprite.png").unwrap().0, 1);
+
+        //it isn't tried again until the file changes again
+        assert!(cacher.poll_reload().unwrap().is_empty());
+        RgbaImage::new(2, 2).save(&path).unwrap();
+        set_modified(&path, 2);
+        assert_eq!(cacher.poll_reload().unwrap(), ["sprite.png"]);
+        assert_eq!(cacher.get("sprite.png").unwrap().0, 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn insert_dir_only_takes_images() {
+        let dir = temp_assets("insert_dir");
+        fs::create_dir_all(dir.join("sprites/sub")).unwrap();
+        for name in ["a.png", "B.PNG", "anim.gif", "sub/c.png"] {
+            RgbaImage::new(1, 1)
+                .save(dir.join("sprites").join(name))
+                .unwrap();
+        }
+        fs::write(dir.join("sprites/readme.txt"), b"not an image").unwrap();
+        let mut cacher = Cacher::with_backend(CountingBackend::default(), &dir);
+
+        //extensions are matched regardless of case, and subfolders are only searched if asked
+        assert_eq!(cacher.insert_dir("sprites", false).unwrap(), 2);
+        assert!(cacher.contains("sprites/a.png"));
+        assert!(cacher.contains("sprites/B.PNG"));
+        assert!(!cacher.contains("sprites/readme.txt"));
+        assert!(!cacher.contains("sprites/sub/c.png"));
+
+        assert_eq!(cacher.insert_dir("sprites", true).unwrap(), 3);
+        assert!(cacher.contains("sprites/sub/c.png"));
+        assert_eq!(cacher.cache.loader().backend.made, 3);
+
+        cacher.set_image_extensions(["GIF"]);
+        assert_eq!(cacher.insert_dir("sprites", true).unwrap(), 1);
+        assert!(cacher.contains("sprites/anim.gif"));
+        assert_eq!(cacher.len(), 4);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn insert_dir_collects_failures() {
+        let dir = temp_assets("insert_dir_failures");
+        fs::create_dir_all(dir.join("sprites")).unwrap();
+        RgbaImage::new(1, 1)
+            .save(dir.join("sprites/good.png"))
+            .unwrap();
+        fs::write(dir.join("sprites/broken.png"), b"not really a png").unwrap();
+        let mut cacher = Cacher::with_backend(CountingBackend::default(), &dir);
+
+        let err = format!("{:#}", cacher.insert_dir("sprites", false).unwrap_err());
+        assert!(
+            err.contains("Inserted 1 images from sprites, but some failed"),
+            "{err}"
+        );
+        assert!(err.contains("sprites/broken.png"), "{err}");
+        assert!(!err.contains("good.png"), "{err}");
+        assert!(cacher.contains("sprites/good.png"));
+        assert!(!cacher.contains("sprites/broken.png"));
+
+        let err = format!("{:#}", cacher.insert_dir("nowhere", true).unwrap_err());
+        assert!(err.contains("Inserted 0 images from nowhere"), "{err}");
+        assert!(err.contains("Reading"), "{err}");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn fallback_only_warns_once_per_path() {
+        let mut cacher = headless();
+        cacher.set_fallback("fallback.png").unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(cacher.get_or_fallback("missing.png").unwrap().0, 1);
+        }
+        assert_eq!(cacher.fallback_warnings, 1);
+
+        cacher.get_or_fallback("also-missing.png").unwrap();
+        assert_eq!(cacher.fallback_warnings, 2);
+    }
+
+    #[test]
+    fn failed_inserts_from_memory_change_nothing() {
+        let mut cacher = headless();
+
+        let err = cacher
+            .insert_from_bytes("logo", b"not really a png")
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("Texture Insert Error: logo"), "{err}");
+        assert!(!cacher.contains("logo"));
+        assert_eq!(cacher.cache.loader().backend.made, 0);
+
+        assert!(cacher
+            .insert_from_image("logo", RgbaImage::new(0, 0))
+            .is_err());
+        assert!(!cacher.contains("logo"));
+        assert!(cacher.cache.loader().in_memory.is_empty());
+        assert!(cacher.cache.loader().loaded_with.is_empty());
+
+        //replacing one which is already there keeps the old texture and image if it fails
+        cacher
+            .insert_from_image("logo", RgbaImage::new(2, 2))
+            .unwrap();
+        assert!(cacher
+            .insert_from_image("logo", RgbaImage::new(0, 2))
+            .is_err());
+        assert_eq!(cacher.get("logo").unwrap().0, 1);
+        assert_eq!(cacher.cpu_bytes(), 2 * 2 * 4);
+        let report = cacher
+            .recreate_all_with(CountingBackend::default())
+            .unwrap();
+        assert_eq!(report.from_cpu, 1);
+    }
+
+    #[test]
+    fn different_settings_reload_the_texture() {
+        let dir = temp_assets("settings");
+        let path = dir.join("sprite.png");
+        RgbaImage::new(2, 2).save(&path).unwrap();
+        let mut cacher = Cacher::with_backend(CountingBackend::default(), &dir);
+        let nearest = TextureSettings::new().filter(Filter::Nearest);
+        let linear = TextureSettings::new().filter(Filter::Linear);
+        let loaded_with = |cacher: &Cacher<CountingBackend>| {
+            let (settings, flip) = cacher.cache.loader().loaded_with["sprite.png"];
+            (settings.get_mag(), flip)
+        };
+
+        assert_eq!(
+            cacher
+                .get_with("sprite.png", &nearest, Flip::None)
+                .unwrap()
+                .0,
+            1
+        );
+        assert_eq!(
+            cacher
+                .get_with("sprite.png", &nearest, Flip::None)
+                .unwrap()
+                .0,
+            1
+        );
+
+        assert_eq!(
+            cacher
+                .get_with("sprite.png", &linear, Flip::None)
+                .unwrap()
+                .0,
+            2
+        );
+        assert_eq!(loaded_with(&cacher), (Filter::Linear, Flip::None));
+        assert_eq!(
+            cacher
+                .get_with("sprite.png", &linear, Flip::Vertical)
+                .unwrap()
+                .0,
+            3
+        );
+        assert_eq!(loaded_with(&cacher), (Filter::Linear, Flip::Vertical));
+        assert_eq!(
+            cacher
+                .get_with("sprite.png", &linear, Flip::Vertical)
+                .unwrap()
+                .0,
+            3
+        );
+        assert_eq!(cacher.len(), 1);
+
+        //if the reload fails, the old texture is kept along with the settings it was made with
+        fs::write(&path, b"not really a png").unwrap();
+        assert!(cacher.get_with("sprite.png", &nearest, Flip::None).is_err());
+        assert_eq!(loaded_with(&cacher), (Filter::Linear, Flip::Vertical));
+        assert_eq!(
+            cacher
+                .get_with("sprite.png", &linear, Flip::Vertical)
+                .unwrap()
+                .0,
+            3
+        );
+        assert_eq!(cacher.cache.loader().backend.made, 3);
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
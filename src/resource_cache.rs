@@ -0,0 +1,554 @@
+//! A generic cache of resources - textures, sounds, fonts, config blobs etc. - which loads them on a miss using a [`ResourceLoader`], and times how long each load takes.
+//!
+//! Can be limited to a number of resources, after which the least recently used ones are evicted. The piston `Cacher` (behind the `piston_cacher` feature) is a thin wrapper over this.
+//!
+//! ## Usage
+//! ```rust
+//! use burntnail_utils::resource_cache::ResourceCache;
+//! use burntnail_utils::error_types::BResult;
+//!
+//! //any closure taking a key and giving back a result works as a loader
+//! let mut cache = ResourceCache::new(|key: &String| -> BResult<String> { Ok(key.to_uppercase()) });
+//!
+//! assert_eq!(cache.get(&"hello".to_string()).unwrap(), "HELLO"); //loaded on a miss
+//! assert_eq!(cache.get(&"hello".to_string()).unwrap(), "HELLO"); //then got from the cache
+//! assert_eq!((cache.stats().hits, cache.stats().misses), (1, 1));
+//!
+//! //with a capacity, the least recently used resources get evicted
+//! cache.set_capacity(Some(1));
+//! cache.insert(&"world".to_string()).unwrap();
+//! assert!(!cache.contains("hello"));
+//! ```
+
+use crate::{
+    error_types::{BError, BResult},
+    time_based_structs::{human_duration::HumanDuration, scoped_timers::ScopedTimer},
+};
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::Entry, HashMap, HashSet},
+    fmt::{Debug, Display, Formatter},
+    hash::Hash,
+    time::Duration,
+};
+
+///Trait for something which can load resources for a [`ResourceCache`] when they aren't already in it.
+///
+///Implemented for any `FnMut(&K) -> BResult<V>`, for loaders which don't need to keep any state about what they've loaded.
+pub trait ResourceLoader<K, V> {
+    ///Loads the resource for `key`, eg. from the disk
+    ///
+    /// # Errors
+    /// Whatever stops the resource from being loaded
+    fn load(&mut self, key: &K) -> BResult<V>;
+
+    ///Called with every resource that leaves the cache - whether it was evicted, removed or cleared - so the loader can drop anything it keeps about it. Does nothing by default.
+    fn unload(&mut self, _key: &K, _value: V) {}
+}
+
+impl<K, V, F: FnMut(&K) -> BResult<V>> ResourceLoader<K, V> for F {
+    fn load(&mut self, key: &K) -> BResult<V> {
+        self(key)
+    }
+}
+
+///Struct to hold a cache of resources of type `V` keyed by `K`, which get loaded by `L` when they aren't already in the cache
+pub struct ResourceCache<K, V, L> {
+    ///The loader for resources which aren't in the cache
+    loader: L,
+    ///`HashMap` of keys to resources
+    resources: HashMap<K, V>,
+    ///If [`Some`], the most resources to keep before evicting the least recently used ones
+    capacity: Option<usize>,
+    ///When each resource was last used, for eviction
+    recency: Recency<K>,
+    ///The resources which are never evicted
+    pinned: HashSet<K>,
+    ///How the cache has been used so far
+    stats: CacheStats,
+}
+
+///Tracks which resources were used least recently, for [`ResourceCache::set_capacity`]
+#[derive(Debug)]
+struct Recency<K> {
+    ///When each key was last used, from `counter`
+    last_used: HashMap<K, u64>,
+    ///Goes up by one for every use
+    counter: u64,
+}
+
+impl<K> Default for Recency<K> {
+    fn default() -> Self {
+        Self {
+            last_used: HashMap::new(),
+            counter: 0,
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> Recency<K> {
+    ///Marks `key` as just used
+    fn touch(&mut self, key: &K) {
+        self.counter += 1;
+        self.last_used.insert(key.clone(), self.counter);
+    }
+
+    ///Stops tracking `key`
+    fn remove<Q: Eq + Hash + ?Sized>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+    {
+        self.last_used.remove(key);
+    }
+
+    ///Gets the keys to evict to get down to `capacity`, least recently used first. Never includes any keys that `keep` says to keep, even if that means staying over.
+    fn over_capacity(&self, capacity: usize, keep: impl Fn(&K) -> bool) -> Vec<K> {
+        let Some(excess) = self.last_used.len().checked_sub(capacity) else {
+            return vec![];
+        };
+        let mut by_use: Vec<(&K, &u64)> = self.last_used.iter().collect();
+        by_use.sort_unstable_by_key(|(_, used)| **used);
+        by_use
+            .into_iter()
+            .filter(|(key, _)| !keep(key))
+            .take(excess)
+            .map(|(key, _)| key.clone())
+            .collect()
+    }
+}
+
+///Counters for how a cache has been used, from [`ResourceCache::stats`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    ///How many times a resource was asked for and was already in the cache
+    pub hits: usize,
+    ///How many times a resource was asked for and had to be loaded
+    pub misses: usize,
+    ///How many times a resource failed to load
+    pub insert_failures: usize,
+    ///The total time spent loading resources
+    pub total_load_time: Duration,
+}
+
+impl CacheStats {
+    ///Gets the fraction of requests which were already in the cache, from 0.0 to 1.0. Returns 0.0 if there haven't been any requests.
+    #[must_use]
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)] //we won't have 2^52 requests
+        {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+impl Display for CacheStats {
+    ///Writes a one-line summary, eg. `40 hits, 10 misses (80% hit rate), 0 failures, 152ms loading`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.0}% hit rate), {} failures, {} loading",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.insert_failures,
+            HumanDuration(self.total_load_time)
+        )
+    }
+}
+
+impl<K: Eq + Hash + Clone + Debug, V, L: ResourceLoader<K, V>> ResourceCache<K, V, L> {
+    ///Function to create a new empty cache, which loads resources using `loader`
+    pub fn new(loader: L) -> Self {
+        Self {
+            loader,
+            resources: HashMap::new(),
+            capacity: None,
+            recency: Recency::default(),
+            pinned: HashSet::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    ///Same as [`ResourceCache::new`], but keeping at most `max_entries` resources - see [`ResourceCache::set_capacity`]
+    pub fn with_capacity(loader: L, max_entries: usize) -> Self {
+        let mut cache = Self::new(loader);
+        cache.set_capacity(Some(max_entries));
+        cache
+    }
+
+    ///Limits the cache to `max_entries` resources (at least 1), or takes the limit off with [`None`]. Once there are more, the least recently used ones are evicted - getting or inserting a resource counts as using it.
+    ///
+    ///Resources which are pinned with [`ResourceCache::pin`] are never evicted.
+    pub fn set_capacity(&mut self, max_entries: Option<usize>) {
+        self.capacity = max_entries.map(|max| max.max(1));
+        self.base_evict(None);
+    }
+
+    ///Gets the most resources to keep, if limited
+    #[must_use]
+    pub const fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    ///Gets the loader
+    #[must_use]
+    pub const fn loader(&self) -> &L {
+        &self.loader
+    }
+
+    ///Gets the loader mutably, eg. to change how future resources get loaded
+    pub const fn loader_mut(&mut self) -> &mut L {
+        &mut self.loader
+    }
+
+    ///Base function for loading a resource and putting it in the cache, replacing anything already there. Only replaces it if the load worked.
+    fn base_load(&mut self, key: &K) -> BResult<Duration> {
+        let timer = ScopedTimer::new(format!("Loading {key:?}"));
+        let value = match self.loader.load(key) {
+            Ok(value) => value,
+            Err(e) => {
+                self.stats.insert_failures += 1;
+                return Err(e);
+            }
+        };
+        let load_time = timer.finish();
+        self.stats.misses += 1;
+        self.stats.total_load_time += load_time;
+
+        self.resources.insert(key.clone(), value);
+        self.recency.touch(key);
+        self.base_evict(Some(key));
+        Ok(load_time)
+    }
+
+    ///Base function for evicting the least recently used resources until we're within the capacity, if there is one. Never evicts the pinned resources, or `just_used`.
+    fn base_evict(&mut self, just_used: Option<&K>) {
+        let Some(capacity) = self.capacity else {
+            return;
+        };
+        for key in self.recency.over_capacity(capacity, |key| {
+            self.pinned.contains(key) || Some(key) == just_used
+        }) {
+            crate::bn_trace!("Evicting {key:?}");
+            self.remove(&key);
+        }
+    }
+
+    ///Makes sure the resource for `key` is in the cache, loading it if not. Returns how long that took, or [`None`] if it was already in the cache.
+    ///
+    /// # Errors
+    /// - Unable to load the resource using the [`ResourceLoader`]
+    pub fn insert(&mut self, key: &K) -> BResult<Option<Duration>> {
+        if self.resources.contains_key(key) {
+            self.stats.hits += 1;
+            self.recency.touch(key);
+            return Ok(None);
+        }
+        self.base_load(key).map(Some)
+    }
+
+    ///Gets the resource for `key`, loading it if it isn't in the cache.
+    ///
+    /// # Errors
+    /// - Unable to load the resource using the [`ResourceLoader`]
+    pub fn get(&mut self, key: &K) -> BResult<&V> {
+        self.insert(key)?;
+        self.resources
+            .get(key)
+            .ok_or_else(|| BError::msg("Resource missing in internal storage"))
+    }
+
+    ///Loads the resource for `key` again, even if it is already in the cache - eg. if the file has changed. If the load fails, then the old resource is kept.
+    ///
+    /// # Errors
+    /// - Unable to load the resource using the [`ResourceLoader`]
+    pub fn reload(&mut self, key: &K) -> BResult<Duration> {
+        self.base_load(key)
+    }
+
+    ///Puts a resource straight into the cache without using the loader or counting towards the stats, returning the old one if there was one.
+    ///
+    ///Replacing a resource doesn't count as using it, so this can be used to rebuild resources in place without changing which get evicted first.
+    pub fn insert_value(&mut self, key: K, value: V) -> Option<V> {
+        match self.resources.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                let key = entry.key().clone();
+                entry.insert(value);
+                self.recency.touch(&key);
+                self.base_evict(Some(&key));
+                None
+            }
+        }
+    }
+
+    ///Gets the resource for `key` if it is already in the cache, without loading it or counting as a use
+    #[must_use]
+    pub fn peek<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+    {
+        self.resources.get(key)
+    }
+
+    ///Whether or not a resource is in the cache
+    #[must_use]
+    pub fn contains<Q: Eq + Hash + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.resources.contains_key(key)
+    }
+
+    ///Gets the keys of all of the resources in the cache, in no particular order
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.resources.keys()
+    }
+
+    ///Gets all of the resources in the cache along with their keys, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.resources.iter()
+    }
+
+    ///Stops the resource for `key` from ever being evicted, until it is removed or unpinned
+    pub fn pin(&mut self, key: K) {
+        self.pinned.insert(key);
+    }
+
+    ///Lets the resource for `key` be evicted again
+    pub fn unpin<Q: Eq + Hash + ?Sized>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+    {
+        self.pinned.remove(key);
+    }
+
+    ///Removes a resource from the cache, handing it to [`ResourceLoader::unload`], and returns whether or not it was there
+    pub fn remove<Q: Eq + Hash + ?Sized>(&mut self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.recency.remove(key);
+        self.pinned.remove(key);
+        match self.resources.remove_entry(key) {
+            Some((key, value)) => {
+                self.loader.unload(&key, value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    ///Removes every resource from the cache, handing each to [`ResourceLoader::unload`]
+    pub fn clear(&mut self) {
+        self.recency = Recency::default();
+        self.pinned.clear();
+        for (key, value) in self.resources.drain() {
+            self.loader.unload(&key, value);
+        }
+    }
+
+    ///Gets how many resources are in the cache
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+
+    ///Whether or not the cache is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    ///Gets how the cache has been used since it was made, or since [`ResourceCache::reset_stats`]
+    #[must_use]
+    pub const fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    ///Resets all of the [`CacheStats`] back to zero
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///Loader which upper-cases keys, failing for any in `failing`, and remembers what it was asked to do
+    #[derive(Default)]
+    struct MockLoader {
+        ///Keys which fail to load
+        failing: HashSet<String>,
+        ///Every key which was loaded, in order
+        loaded: Vec<String>,
+        ///Every key which was unloaded, in order
+        unloaded: Vec<String>,
+    }
+
+    impl ResourceLoader<String, String> for MockLoader {
+        fn load(&mut self, key: &String) -> BResult<String> {
+            if self.failing.contains(key) {
+                return Err(BError::msg(format!("{key} is broken")));
+            }
+            self.loaded.push(key.clone());
+            Ok(key.to_uppercase())
+        }
+
+        fn unload(&mut self, key: &String, _value: String) {
+            self.unloaded.push(key.clone());
+        }
+    }
+
+    fn key(s: &str) -> String {
+        s.to_string()
+    }
+
+    #[test]
+    fn loads_on_miss_only() {
+        let mut cache = ResourceCache::new(MockLoader::default());
+        assert!(cache.is_empty());
+
+        assert_eq!(cache.get(&key("a")).unwrap(), "A");
+        assert!(cache.insert(&key("a")).unwrap().is_none());
+        assert!(cache.insert(&key("b")).unwrap().is_some());
+
+        assert_eq!(cache.loader().loaded, vec!["a", "b"]);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains("a"));
+        assert_eq!(cache.peek("b").map(String::as_str), Some("B"));
+        assert_eq!(cache.peek("c"), None);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.insert_failures), (1, 2, 0));
+        cache.reset_stats();
+        assert_eq!(cache.stats(), CacheStats::default());
+    }
+
+    #[test]
+    fn failures_keep_old_values() {
+        let mut cache = ResourceCache::new(MockLoader::default());
+        cache.insert(&key("a")).unwrap();
+
+        cache.loader_mut().failing.insert(key("a"));
+        cache.loader_mut().failing.insert(key("b"));
+        assert!(cache.get(&key("b")).is_err());
+        assert!(!cache.contains("b"));
+
+        assert!(cache.reload(&key("a")).is_err());
+        assert_eq!(cache.peek("a").map(String::as_str), Some("A"));
+        assert_eq!(cache.stats().insert_failures, 2);
+
+        cache.loader_mut().failing.clear();
+        cache.reload(&key("a")).unwrap();
+        assert_eq!(cache.loader().loaded, vec!["a", "a"]);
+    }
+
+    #[test]
+    fn remove_and_clear_unload() {
+        let mut cache = ResourceCache::new(MockLoader::default());
+        for k in ["a", "b", "c"] {
+            cache.insert(&key(k)).unwrap();
+        }
+
+        assert!(cache.remove("b"));
+        assert!(!cache.remove("b"));
+        assert_eq!(cache.loader().unloaded, vec!["b"]);
+
+        assert_eq!(cache.insert_value(key("a"), key("x")), Some(key("A")));
+        assert_eq!(cache.insert_value(key("b"), key("y")), None);
+        assert_eq!(cache.peek("a").map(String::as_str), Some("x"));
+
+        cache.clear();
+        assert!(cache.is_empty());
+        let mut unloaded = cache.loader().unloaded.clone();
+        unloaded.sort();
+        assert_eq!(unloaded, vec!["a", "b", "b", "c"]);
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = ResourceCache::with_capacity(MockLoader::default(), 2);
+        cache.insert(&key("a")).unwrap();
+        cache.insert(&key("b")).unwrap();
+
+        //getting a makes b the oldest
+        cache.get(&key("a")).unwrap();
+        cache.insert(&key("c")).unwrap();
+        assert!(!cache.contains("b"));
+        assert_eq!(cache.loader().unloaded, vec!["b"]);
+
+        //pinned resources are never evicted
+        cache.pin(key("a"));
+        cache.insert(&key("d")).unwrap();
+        assert!(cache.contains("a") && cache.contains("d"));
+        assert!(!cache.contains("c"));
+
+        //even if everything else is pinned, the one just loaded stays
+        cache.set_capacity(Some(1));
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("d"));
+        cache.insert(&key("e")).unwrap();
+        assert!(cache.contains("a") && cache.contains("e"));
+
+        cache.unpin("a");
+        cache.set_capacity(None);
+        cache.insert(&key("f")).unwrap();
+        assert_eq!(cache.len(), 3);
+    }
+
+    #[test]
+    fn recency_order() {
+        let mut recency = Recency::default();
+        for k in ["a", "b", "c"] {
+            recency.touch(&key(k));
+        }
+        let none = |_: &String| false;
+        assert_eq!(recency.over_capacity(3, none), Vec::<String>::new());
+        assert_eq!(recency.over_capacity(2, none), vec!["a"]);
+
+        recency.touch(&key("a"));
+        recency.touch(&key("d"));
+        assert_eq!(recency.over_capacity(2, none), vec!["b", "c"]);
+
+        recency.remove("c");
+        assert_eq!(recency.over_capacity(2, none), vec!["b"]);
+        assert_eq!(
+            recency.over_capacity(1, |k: &String| k == "b"),
+            vec!["a", "d"]
+        );
+    }
+
+    #[test]
+    fn closure_loaders() {
+        let mut calls = 0;
+        let mut cache = ResourceCache::new(|k: &u32| -> BResult<u32> {
+            calls += 1;
+            Ok(k * 2)
+        });
+        assert_eq!(*cache.get(&2).unwrap(), 4);
+        assert_eq!(*cache.get(&2).unwrap(), 4);
+        drop(cache);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn stats_summary() {
+        let mut stats = CacheStats::default();
+        assert!(stats.hit_rate().abs() < f64::EPSILON);
+
+        stats.hits = 3;
+        stats.misses = 1;
+        stats.total_load_time = Duration::from_micros(1_500);
+        assert!((stats.hit_rate() - 0.75).abs() < f64::EPSILON);
+        assert_eq!(
+            stats.to_string(),
+            "3 hits, 1 misses (75% hit rate), 0 failures, 1.5ms loading"
+        );
+    }
+}
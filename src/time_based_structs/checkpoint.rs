@@ -0,0 +1,187 @@
+//! Checkpoints for timing between two manual points, when the thing being measured doesn't fit into one lexical scope (eg. from a button click to a frame rendering).
+//!
+//! ## Use
+//! ```rust
+//! use burntnail_utils::time_based_structs::checkpoint::Checkpoint;
+//!
+//! let token = Checkpoint::start("Loading level");
+//! /* pass the token around through several functions/frames */
+//! let took = token.finish(); //logs out the time, and gives it back
+//! assert!(took.as_secs() < 1);
+//! ```
+
+use super::clock::{Clock, SystemClock};
+use crate::{error_ext::MutexExt, memcache::MemoryCacher};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+///The id to give to the next [`CheckpointToken`]
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+///How many [`CheckpointToken`]s have been dropped without being finished. Only counted with `debug_assertions`.
+static LEAKED: AtomicUsize = AtomicUsize::new(0);
+///Global table of the labels of all [`CheckpointToken`]s which haven't been finished yet
+static LABELS: OnceLock<Mutex<HashMap<u64, String>>> = OnceLock::new();
+
+///Gets the global table of labels, creating it if need be
+fn labels() -> &'static Mutex<HashMap<u64, String>> {
+    LABELS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+///Unit struct for starting [`CheckpointToken`]s, and checking on the global table of outstanding ones.
+pub struct Checkpoint;
+
+impl Checkpoint {
+    ///Starts a new checkpoint, registering the label in the global table
+    pub fn start(label: impl Display) -> CheckpointToken {
+        Self::start_with_clock(label, SystemClock)
+    }
+
+    ///Same as [`Checkpoint::start`], but getting the time from `clock` - eg. a [`super::clock::MockClock`] for testing
+    pub fn start_with_clock<C: Clock>(label: impl Display, clock: C) -> CheckpointToken<C> {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        labels()
+            .lock_panic("locking checkpoint labels to start")
+            .insert(id, label.to_string());
+
+        CheckpointToken {
+            id,
+            start: clock.now(),
+            clock,
+        }
+    }
+
+    ///Gets the labels of all of the checkpoints which have been started, but not yet finished or dropped
+    #[must_use]
+    pub fn outstanding() -> Vec<String> {
+        labels()
+            .lock_panic("locking checkpoint labels to list")
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    ///Gets how many tokens have been dropped without being finished.
+    ///
+    ///NB: Always `0` without `debug_assertions`
+    #[must_use]
+    pub fn leaked() -> usize {
+        LEAKED.load(Ordering::Relaxed)
+    }
+}
+
+///Token for a started [`Checkpoint`]. Cheap to pass around, as it is just an id and an [`Instant`] (along with the [`Clock`], which takes no space for the real one) - the label lives in a global table.
+///
+///NB: This is deliberately not [`Copy`], so that tokens which are dropped without being finished can be detected and warned about (with `debug_assertions`).
+#[must_use = "dropping a CheckpointToken without finishing it doesn't log the time"]
+#[derive(Debug)]
+pub struct CheckpointToken<C: Clock = SystemClock> {
+    ///The id of the label in the global table
+    id: u64,
+    ///When the checkpoint was started
+    start: Instant,
+    ///Where to get the time from
+    clock: C,
+}
+
+impl<C: Clock> CheckpointToken<C> {
+    ///Gets the time since the checkpoint was started, without finishing it
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed(self.start)
+    }
+
+    ///Removes the label from the global table, returning it if it was still there
+    fn take_label(&self) -> Option<String> {
+        labels()
+            .lock_panic("locking checkpoint labels to finish")
+            .remove(&self.id)
+    }
+
+    ///Finishes the checkpoint, logging out the time taken with the label in the same way as [`super::scoped_timers::ScopedTimer`], and returning the time taken
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)] //consumed so it can't be finished twice
+    pub fn finish(self) -> Duration {
+        let elapsed = self.elapsed();
+        let msg = self.take_label().unwrap_or_default();
+
+        crate::bn_info!(time_taken = %super::human_duration::HumanDuration(elapsed), "{msg}");
+
+        elapsed
+    }
+
+    ///Finishes the checkpoint, pushing the time taken into a [`MemoryCacher`] rather than logging it
+    #[allow(clippy::needless_pass_by_value)] //consumed so it can't be finished twice
+    pub fn finish_into<const N: usize>(self, cache: &mut MemoryCacher<Duration, N>) {
+        let elapsed = self.elapsed();
+        self.take_label();
+        cache.push(elapsed);
+    }
+}
+
+impl<C: Clock> Drop for CheckpointToken<C> {
+    fn drop(&mut self) {
+        //if the label is still there, then we were never finished
+        let Some(msg) = self.take_label() else {
+            return;
+        };
+
+        if cfg!(debug_assertions) {
+            LEAKED.fetch_add(1, Ordering::Relaxed);
+
+            crate::bn_warn!(%msg, "Checkpoint dropped without being finished");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_based_structs::clock::MockClock;
+
+    #[test]
+    fn finish_removes_label() {
+        let clock = MockClock::new();
+        let token = Checkpoint::start_with_clock("finish_removes_label", clock.clone());
+        assert!(Checkpoint::outstanding().contains(&"finish_removes_label".to_string()));
+
+        clock.advance(Duration::from_millis(5));
+        assert_eq!(token.elapsed(), Duration::from_millis(5));
+        let took = token.finish();
+
+        assert_eq!(took, Duration::from_millis(5));
+        assert!(!Checkpoint::outstanding().contains(&"finish_removes_label".to_string()));
+    }
+
+    #[test]
+    fn finish_into_pushes() {
+        let mut cache = MemoryCacher::<Duration, 5>::new(None);
+        let token = Checkpoint::start("finish_into_pushes");
+        token.finish_into(&mut cache);
+
+        assert_eq!(cache.get_all_cloned().len(), 1);
+        assert!(!Checkpoint::outstanding().contains(&"finish_into_pushes".to_string()));
+    }
+
+    #[test]
+    fn dropping_unfinished_warns() {
+        let before = Checkpoint::leaked();
+        {
+            let _token = Checkpoint::start("dropping_unfinished_warns");
+        }
+
+        //leaks are only counted with `debug_assertions`, so this also runs under `--release`
+        if cfg!(debug_assertions) {
+            assert!(Checkpoint::leaked() > before);
+        } else {
+            assert_eq!(Checkpoint::leaked(), 0);
+        }
+        assert!(!Checkpoint::outstanding().contains(&"dropping_unfinished_warns".to_string()));
+    }
+}
@@ -0,0 +1,132 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+///A source of time - monotonic for measuring gaps, and wall-clock for lining things up with the time of day
+pub trait Clock {
+    ///Gets the current monotonic time
+    fn now(&self) -> Instant;
+
+    ///Gets the current wall-clock time, which (unlike [`Clock::now`]) can jump backwards
+    fn system_now(&self) -> SystemTime;
+
+    ///Gets how long it has been since `earlier`, or zero if `earlier` is in the future
+    fn elapsed(&self, earlier: Instant) -> Duration {
+        self.now().saturating_duration_since(earlier)
+    }
+}
+
+///The real clock, using [`Instant::now`] and [`SystemTime::now`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+///A clock which only moves when told to, for testing.
+///
+///Clones share the same time, so one can be given to a timer and the other kept to advance it.
+#[derive(Debug, Clone)]
+pub struct MockClock {
+    ///The real time when this was created, which all times are offset from
+    start: Instant,
+    ///How many nanoseconds this has been advanced by
+    offset: Arc<AtomicU64>,
+    ///The current wall-clock time
+    system: Arc<Mutex<SystemTime>>,
+}
+
+impl MockClock {
+    ///Creates a new `MockClock`, starting at the current real time
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            offset: Arc::new(AtomicU64::new(0)),
+            system: Arc::new(Mutex::new(SystemTime::now())),
+        }
+    }
+
+    ///Moves the clock forwards by `by`, for both the monotonic and wall-clock time
+    pub fn advance(&self, by: Duration) {
+        let nanos = u64::try_from(by.as_nanos()).unwrap_or(u64::MAX);
+        self.offset.fetch_add(nanos, Ordering::SeqCst);
+        let mut system = self.system.lock().unwrap_or_else(PoisonError::into_inner);
+        *system = system.checked_add(by).unwrap_or(*system);
+    }
+
+    ///Sets the wall-clock time, eg. to test it jumping backwards. The monotonic time isn't changed.
+    pub fn set_system_time(&self, time: SystemTime) {
+        *self.system.lock().unwrap_or_else(PoisonError::into_inner) = time;
+    }
+
+    ///Gets how far the clock has been advanced since it was created
+    #[must_use]
+    pub fn advanced(&self) -> Duration {
+        Duration::from_nanos(self.offset.load(Ordering::SeqCst))
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.advanced()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        *self.system.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mock_clock_only_moves_when_advanced() {
+        let clock = MockClock::new();
+        let shared = clock.clone();
+        let start = clock.now();
+        assert_eq!(clock.now(), start);
+
+        shared.advance(Duration::from_millis(1_500));
+        assert_eq!(clock.elapsed(start), Duration::from_millis(1_500));
+        assert_eq!(clock.advanced(), Duration::from_millis(1_500));
+        assert_eq!(
+            clock.elapsed(clock.now() + Duration::from_secs(1)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn mock_wall_clock_can_be_set() {
+        let clock = MockClock::new();
+        let epoch = SystemTime::UNIX_EPOCH;
+        clock.set_system_time(epoch + Duration::from_secs(100));
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(clock.system_now(), epoch + Duration::from_secs(105));
+
+        //going backwards doesn't touch the monotonic time, and clones see it too
+        let now = clock.now();
+        let other = clock.clone();
+        other.set_system_time(epoch);
+        assert_eq!(clock.system_now(), epoch);
+        assert_eq!(clock.now(), now);
+    }
+}
@@ -0,0 +1,289 @@
+use super::{
+    clock::{Clock, SystemClock},
+    do_on_interval::{DoOnInterval, UpdateOnCheck},
+};
+use std::time::{Duration, Instant};
+
+///What to do with the first call to a [`Debouncer`] or [`Throttle`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirstCall {
+    ///Fire straight away, and then hold off further calls
+    Immediate,
+    ///Hold off, and only fire once the time has passed
+    Wait,
+}
+
+///Timer struct which is only ready once input has *stopped* arriving for a quiet period - eg. to coalesce a burst of filesystem watcher events into one rebuild.
+///
+///Unlike [`DoOnInterval`], a steady stream of input keeps it from being ready.
+#[derive(Debug)]
+pub struct Debouncer<C: Clock = SystemClock> {
+    ///How long input has to stop for
+    quiet: Duration,
+    ///Whether to be ready at the start of a burst, or after it
+    first: FirstCall,
+    ///When input last arrived
+    last_input: Option<Instant>,
+    ///When we become ready, if there is input to report
+    ready_at: Option<Instant>,
+    ///Where to get the time from
+    clock: C,
+}
+
+impl Debouncer {
+    ///Creates a new `Debouncer`.
+    ///
+    ///With [`FirstCall::Wait`] it is ready once `quiet` has passed since the last input. With [`FirstCall::Immediate`] it is ready as soon as a burst starts, and then ignores input until there has been a gap of `quiet`.
+    #[must_use]
+    pub const fn new(quiet: Duration, first: FirstCall) -> Self {
+        Self::with_clock(quiet, first, SystemClock)
+    }
+}
+
+impl<C: Clock> Debouncer<C> {
+    ///Same as [`Debouncer::new`], but getting the time from `clock` - eg. a [`super::clock::MockClock`] for testing
+    #[must_use]
+    pub const fn with_clock(quiet: Duration, first: FirstCall, clock: C) -> Self {
+        Self {
+            quiet,
+            first,
+            last_input: None,
+            ready_at: None,
+            clock,
+        }
+    }
+
+    ///Records that input arrived now
+    pub fn input(&mut self) {
+        let now = self.clock.now();
+        match self.first {
+            FirstCall::Wait => self.ready_at = Some(now + self.quiet),
+            FirstCall::Immediate => {
+                let new_burst = self
+                    .last_input
+                    .is_none_or(|last| now.saturating_duration_since(last) >= self.quiet);
+                if new_burst {
+                    self.ready_at = Some(now);
+                }
+            }
+        }
+        self.last_input = Some(now);
+    }
+
+    ///Checks whether or not there is input to act on. If so, returns true and clears it, else returns false.
+    pub fn ready(&mut self) -> bool {
+        match self.ready_at {
+            Some(at) if self.clock.now() >= at => {
+                self.ready_at = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    ///Whether or not there is input which hasn't been reported by [`Debouncer::ready`] yet
+    #[must_use]
+    pub const fn is_pending(&self) -> bool {
+        self.ready_at.is_some()
+    }
+}
+
+///Wrapper around a function which only calls it at most once per interval, dropping the calls in between.
+///
+///Made with [`Throttle::keep_last`], the last dropped argument is kept and given to the function by [`Throttle::poll`] once the interval has passed.
+pub struct Throttle<T, F: FnMut(T), C: Clock = SystemClock> {
+    ///The function to call
+    f: F,
+    ///When the function can next be called
+    timer: DoOnInterval<UpdateOnCheck, C>,
+    ///Whether or not to keep the last dropped argument
+    keep_last: bool,
+    ///The last dropped argument, if we're keeping it
+    pending: Option<T>,
+}
+
+impl<T, F: FnMut(T)> Throttle<T, F> {
+    ///Creates a new `Throttle`, which calls `f` at most once per `interval`.
+    ///
+    ///With [`FirstCall::Immediate`] the first call goes straight through, and with [`FirstCall::Wait`] nothing goes through until `interval` has passed.
+    #[must_use]
+    pub fn new(interval: Duration, first: FirstCall, f: F) -> Self {
+        Self::with_clock(interval, first, f, SystemClock)
+    }
+}
+
+impl<T, F: FnMut(T), C: Clock> Throttle<T, F, C> {
+    ///Same as [`Throttle::new`], but getting the time from `clock` - eg. a [`super::clock::MockClock`] for testing
+    #[must_use]
+    pub fn with_clock(interval: Duration, first: FirstCall, f: F, clock: C) -> Self {
+        let timer = match first {
+            FirstCall::Immediate => DoOnInterval::with_clock(interval, clock),
+            FirstCall::Wait => DoOnInterval::with_clock_dont_initial_update(interval, clock),
+        };
+        Self {
+            f,
+            timer,
+            keep_last: false,
+            pending: None,
+        }
+    }
+
+    ///Keeps the last dropped argument, to be given to the function by [`Throttle::poll`] once the interval has passed
+    #[must_use]
+    pub const fn keep_last(mut self) -> Self {
+        self.keep_last = true;
+        self
+    }
+
+    ///Calls the function with `arg` if the interval has passed, returning whether or not it was called.
+    ///
+    ///Otherwise, `arg` is dropped, or kept in place of any earlier dropped argument if made with [`Throttle::keep_last`].
+    pub fn call(&mut self, arg: T) -> bool {
+        if self.timer.can_do() {
+            self.pending = None;
+            (self.f)(arg);
+            true
+        } else {
+            if self.keep_last {
+                self.pending = Some(arg);
+            }
+            false
+        }
+    }
+
+    ///Calls the function with the last dropped argument if there is one and the interval has passed, returning whether or not it was called
+    pub fn poll(&mut self) -> bool {
+        if self.pending.is_some() && self.timer.can_do() {
+            if let Some(arg) = self.pending.take() {
+                (self.f)(arg);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    ///Whether or not there is a dropped argument waiting for [`Throttle::poll`]
+    #[must_use]
+    pub const fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+///Wraps `f` so it is called at most once per `interval`, with the first call going straight through and the calls in between dropped.
+///
+///Use a [`Throttle`] to wait before the first call, or to keep the last dropped argument.
+pub fn throttle<T>(interval: Duration, f: impl FnMut(T)) -> impl FnMut(T) {
+    let mut throttle = Throttle::new(interval, FirstCall::Immediate, f);
+    move |arg| {
+        throttle.call(arg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_based_structs::clock::MockClock;
+
+    #[test]
+    fn debouncer_waits_for_quiet() {
+        let clock = MockClock::new();
+        let mut debouncer =
+            Debouncer::with_clock(Duration::from_millis(100), FirstCall::Wait, clock.clone());
+        assert!(!debouncer.ready());
+
+        for _ in 0..5 {
+            debouncer.input();
+            clock.advance(Duration::from_millis(50));
+            assert!(!debouncer.ready());
+        }
+        assert!(debouncer.is_pending());
+
+        clock.advance(Duration::from_millis(50));
+        assert!(debouncer.ready());
+        assert!(!debouncer.ready());
+        assert!(!debouncer.is_pending());
+    }
+
+    #[test]
+    fn debouncer_fires_immediately() {
+        let clock = MockClock::new();
+        let mut debouncer = Debouncer::with_clock(
+            Duration::from_millis(100),
+            FirstCall::Immediate,
+            clock.clone(),
+        );
+
+        debouncer.input();
+        assert!(debouncer.ready());
+        for _ in 0..5 {
+            clock.advance(Duration::from_millis(50));
+            debouncer.input();
+            assert!(!debouncer.ready());
+        }
+
+        clock.advance(Duration::from_millis(100));
+        debouncer.input();
+        assert!(debouncer.ready());
+    }
+
+    #[test]
+    fn throttle_drops_calls_in_between() {
+        let clock = MockClock::new();
+        let mut seen = vec![];
+        {
+            let mut throttle = Throttle::with_clock(
+                Duration::from_millis(100),
+                FirstCall::Immediate,
+                |x| seen.push(x),
+                clock.clone(),
+            );
+            for i in 0..10 {
+                throttle.call(i);
+                clock.advance(Duration::from_millis(30));
+            }
+            assert!(!throttle.has_pending());
+        }
+        assert_eq!(seen, vec![0, 4, 8]);
+    }
+
+    #[test]
+    fn throttle_waits_and_keeps_last() {
+        let clock = MockClock::new();
+        let mut seen = vec![];
+        {
+            let mut throttle = Throttle::with_clock(
+                Duration::from_millis(100),
+                FirstCall::Wait,
+                |x| seen.push(x),
+                clock.clone(),
+            )
+            .keep_last();
+            assert!(!throttle.call(1));
+            assert!(!throttle.call(2));
+            assert!(!throttle.poll());
+            assert!(throttle.has_pending());
+
+            clock.advance(Duration::from_millis(101));
+            assert!(throttle.poll());
+            assert!(!throttle.poll());
+
+            assert!(!throttle.call(3));
+            clock.advance(Duration::from_millis(101));
+            assert!(throttle.call(4));
+            assert!(!throttle.poll());
+        }
+        assert_eq!(seen, vec![2, 4]);
+    }
+
+    #[test]
+    fn throttle_fn() {
+        let mut count = 0;
+        {
+            let mut f = throttle(Duration::from_secs(1_000), |()| count += 1);
+            f(());
+            f(());
+        }
+        assert_eq!(count, 1);
+    }
+}
@@ -1,21 +1,32 @@
 use std::{
     marker::PhantomData,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
+use super::clock::{Clock, SystemClock};
 use crate::{crate_private::Sealed, either::Either, generic_enum};
 
-generic_enum!(Sealed, (DoOnIntervalMode -> "Trait for how `DoOnInterval` should update the timer") => (GiveUpdaters -> "Give updaters that update the timer when they are dropped"), (UpdateOnCheck -> "Update the timer when if we can do the action when we check"));
+generic_enum!(Sealed, (DoOnIntervalMode -> "Trait for how `DoOnInterval` should update the timer") => (GiveUpdaters -> "Give updaters that update the timer when they are dropped"), (UpdateOnCheck -> "Update the timer when if we can do the action when we check"), (UpdateOnDo -> "Only update the timer when told the action is done, so checking can be repeated"));
 
 ///Timer struct to only allow actions to be performed on an interval
+///
+///The time comes from a [`Clock`], which is the real [`SystemClock`] unless made with [`DoOnInterval::with_clock`].
 #[derive(Debug)]
-pub struct DoOnInterval<MODE: DoOnIntervalMode> {
+pub struct DoOnInterval<MODE: DoOnIntervalMode, C: Clock = SystemClock> {
     ///When the action was last done
     last_did: Instant,
     ///Gap between doing actions
     gap: Duration,
     ///Whether or not an instance of [`DOIUpdate`] exists pointing to this right now. Only used in [`GiveUpdaters`]
     updater_exists: bool,
+    ///If [`Some`], readiness is worked out from wall-clock boundaries rather than from `last_did`
+    aligned: Option<Aligned>,
+    ///If [`Some`], then the timer is paused, with how far through the interval we were when it was paused
+    paused: Option<Duration>,
+    ///Whether or not the next check should succeed no matter how much time has elapsed
+    triggered: bool,
+    ///Where to get the time from
+    clock: C,
 
     ///`PhantomData` to make sure mode isn't optimised away
     _pd: PhantomData<MODE>,
@@ -25,32 +36,247 @@ impl<MODE: DoOnIntervalMode> DoOnInterval<MODE> {
     ///Creates a new `DoOnInterval` using the duration given
     #[must_use]
     pub fn new(gap: Duration) -> Self {
+        Self::with_clock(gap, SystemClock)
+    }
+
+    ///Same as the normal `new` method, but will not perform an update straight away
+    #[must_use]
+    pub fn new_dont_initial_update(gap: Duration) -> Self {
+        Self::with_clock_dont_initial_update(gap, SystemClock)
+    }
+
+    ///Creates a new `DoOnInterval` which is ready at wall-clock multiples of `interval` (eg. at :00 and :30 for 30s), rather than every `interval` since it last did the action.
+    ///
+    ///It won't be ready until the next boundary. If the clock jumps forwards past several boundaries it is only ready once, and if it jumps backwards it waits for the next boundary after the new time.
+    #[must_use]
+    pub fn aligned(interval: Duration) -> Self {
+        Self::aligned_with_clock(interval, SystemClock)
+    }
+}
+
+impl<MODE: DoOnIntervalMode, C: Clock> DoOnInterval<MODE, C> {
+    ///Same as [`DoOnInterval::new`], but getting the time from `clock` - eg. a [`super::clock::MockClock`] for testing
+    #[must_use]
+    pub fn with_clock(gap: Duration, clock: C) -> Self {
+        let now = clock.now();
+        //if the clock hasn't been going for long enough (or the gap is huge), there's nothing to backdate to, so trigger it instead
+        let backdated = gap.checked_mul(2).and_then(|g| now.checked_sub(g));
         Self {
-            last_did: Instant::now() - gap * 2,
+            last_did: backdated.unwrap_or(now),
             gap,
             updater_exists: false,
+            aligned: None,
+            paused: None,
+            triggered: backdated.is_none(),
+            clock,
             _pd: PhantomData,
         }
     }
 
-    ///Same as the normal `new` method, but will not perform an update straight away
+    ///Same as [`DoOnInterval::aligned`], but getting the time from `clock` - eg. a [`super::clock::MockClock`] with a set wall-clock time
     #[must_use]
-    pub fn new_dont_initial_update(gap: Duration) -> Self {
+    pub fn aligned_with_clock(interval: Duration, clock: C) -> Self {
+        let last_boundary = floor_to(clock.system_now(), interval);
+        Self {
+            aligned: Some(Aligned { last_boundary }),
+            ..Self::with_clock_dont_initial_update(interval, clock)
+        }
+    }
+
+    ///Same as [`DoOnInterval::new_dont_initial_update`], but getting the time from `clock`
+    #[must_use]
+    pub fn with_clock_dont_initial_update(gap: Duration, clock: C) -> Self {
         Self {
-            last_did: Instant::now(),
+            last_did: clock.now(),
             gap,
             updater_exists: false,
+            aligned: None,
+            paused: None,
+            triggered: false,
+            clock,
+            _pd: PhantomData,
+        }
+    }
+
+    ///Gets the interval between doing actions
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.gap
+    }
+
+    ///Changes the interval between doing actions, without resetting when the action was last done.
+    ///
+    ///This is checked against the time already elapsed, so shrinking the interval below that makes the next check succeed.
+    pub const fn set_interval(&mut self, interval: Duration) {
+        self.gap = interval;
+    }
+
+    ///Makes the next check succeed straight away, eg. if the user asked to save now. Still won't succeed while paused.
+    pub const fn trigger_now(&mut self) {
+        self.triggered = true;
+    }
+
+    ///Marks the action as just done, so the next check won't succeed for a full interval, eg. if the user just saved manually
+    pub fn reset(&mut self) {
+        self.base_update();
+    }
+
+    ///Gets how far through the current interval we are, capped at the interval. Doesn't update the timer.
+    #[must_use]
+    pub fn elapsed_in_interval(&self) -> Duration {
+        if let Some(paused) = self.paused {
+            return paused.min(self.gap);
+        }
+        if self.triggered {
+            return self.gap;
+        }
+
+        let elapsed = self.aligned.map_or_else(
+            || self.clock.elapsed(self.last_did),
+            |aligned| {
+                let now = self.clock.system_now();
+                let current = floor_to(now, self.gap);
+                if current > aligned.last_boundary {
+                    self.gap
+                } else {
+                    now.duration_since(current).unwrap_or_default()
+                }
+            },
+        );
+        elapsed.min(self.gap)
+    }
+
+    ///Gets how long until this will next be ready, ignoring whether or not an updater exists. Doesn't update the timer.
+    #[must_use]
+    pub fn time_until_next(&self) -> Duration {
+        self.gap.saturating_sub(self.elapsed_in_interval())
+    }
+
+    ///Gets how far through the current interval we are from 0.0 to 1.0, eg. for a progress bar. Doesn't update the timer.
+    #[must_use]
+    pub fn fraction_elapsed(&self) -> f64 {
+        if self.gap.is_zero() {
+            return 1.0;
+        }
+        (self.elapsed_in_interval().as_secs_f64() / self.gap.as_secs_f64()).min(1.0)
+    }
+
+    ///Gets when this will next be ready, ignoring whether or not an updater exists
+    #[must_use]
+    pub fn next_boundary(&self) -> SystemTime {
+        let now = self.clock.system_now();
+        if self.triggered {
+            return now;
+        }
+        match self.aligned {
+            Some(_) => floor_to(now, self.gap) + self.gap,
+            None => now + self.gap.saturating_sub(self.clock.elapsed(self.last_did)),
+        }
+    }
+
+    ///Pauses the timer, so it won't be ready until [`DoOnInterval::resume`] is called. Does nothing if already paused.
+    ///
+    ///For [`DoOnInterval::aligned`] timers, the wall-clock boundaries carry on as normal once resumed.
+    pub fn pause(&mut self) {
+        if self.paused.is_none() {
+            self.paused = Some(if self.aligned.is_some() {
+                self.elapsed_in_interval()
+            } else {
+                self.clock.elapsed(self.last_did)
+            });
+        }
+    }
+
+    ///Resumes the timer, so the remaining wait carries on from where it was when paused. Does nothing if not paused.
+    pub fn resume(&mut self) {
+        if let Some(elapsed) = self.paused.take() {
+            let now = self.clock.now();
+            self.last_did = now.checked_sub(elapsed).unwrap_or(now);
+        }
+    }
+
+    ///Whether or not the timer is paused
+    #[must_use]
+    pub const fn is_paused(&self) -> bool {
+        self.paused.is_some()
+    }
+
+    ///Base function to check whether or not enough time has elapsed - never while paused. Clamps the last boundary if the wall-clock has gone backwards.
+    fn base_ready(&mut self) -> bool {
+        if self.paused.is_some() {
+            return false;
+        }
+        if self.triggered {
+            return true;
+        }
+
+        match &mut self.aligned {
+            Some(aligned) => {
+                let current = floor_to(self.clock.system_now(), self.gap);
+                if current < aligned.last_boundary {
+                    aligned.last_boundary = current;
+                }
+                current > aligned.last_boundary
+            }
+            None => self.clock.elapsed(self.last_did) > self.gap,
+        }
+    }
+
+    ///Base function to change the mode, keeping all of the state apart from whether an updater exists
+    fn base_convert<NEW: DoOnIntervalMode>(self) -> DoOnInterval<NEW, C> {
+        DoOnInterval {
+            last_did: self.last_did,
+            gap: self.gap,
+            updater_exists: false,
+            aligned: self.aligned,
+            paused: self.paused,
+            triggered: self.triggered,
+            clock: self.clock,
             _pd: PhantomData,
         }
     }
+
+    ///Base function to mark the action as done now
+    fn base_update(&mut self) {
+        self.last_did = self.clock.now();
+        self.triggered = false;
+        if let Some(aligned) = &mut self.aligned {
+            aligned.last_boundary = floor_to(self.clock.system_now(), self.gap);
+        }
+    }
+}
+
+///Wall-clock state for [`DoOnInterval::aligned`]
+#[derive(Debug, Clone, Copy)]
+struct Aligned {
+    ///The boundary we last did the action in
+    last_boundary: SystemTime,
 }
 
-impl DoOnInterval<GiveUpdaters> {
+///Rounds `time` down to a multiple of `interval` since the [`UNIX_EPOCH`]. Times before the epoch are treated as the epoch.
+fn floor_to(time: SystemTime, interval: Duration) -> SystemTime {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let interval = interval.as_nanos();
+    if interval == 0 {
+        return time;
+    }
+
+    let floored = since_epoch.as_nanos() / interval * interval;
+    #[allow(clippy::cast_possible_truncation)]
+    //nanos % 1e9 always fits, and seconds won't overflow for a long while
+    let floored = Duration::new(
+        (floored / 1_000_000_000) as u64,
+        (floored % 1_000_000_000) as u32,
+    );
+    UNIX_EPOCH + floored
+}
+
+impl<C: Clock> DoOnInterval<GiveUpdaters, C> {
     ///Checks whether or not we can do the action, using the timer and checking whether any instances of [`DOIUpdate`] currently exist
     ///
     /// Returns `None` is we can't, and `Some` if we can. Make sure to bind the [`DOIUpdate`] to allow the [`Drop::drop`] impl to run correctly.
-    pub fn get_updater(&mut self) -> Option<DOIUpdate> {
-        if !self.updater_exists && self.last_did.elapsed() > self.gap {
+    pub fn get_updater(&mut self) -> Option<DOIUpdate<'_, C>> {
+        if !self.updater_exists && self.base_ready() {
             self.updater_exists = true;
             Some(DOIUpdate(self))
         } else {
@@ -60,27 +286,21 @@ impl DoOnInterval<GiveUpdaters> {
 
     ///Turns a [`GiveUpdaters`] to an [`UpdateOnCheck`]. Can return the original [`GiveUpdaters`] if an updater currently exists
     #[must_use]
-    pub const fn to_update_on_check(self) -> Either<Self, DoOnInterval<UpdateOnCheck>> {
+    pub fn to_update_on_check(self) -> Either<Self, DoOnInterval<UpdateOnCheck, C>> {
         if self.updater_exists {
             Either::Left(self)
         } else {
-            let nu = DoOnInterval {
-                last_did: self.last_did,
-                gap: self.gap,
-                updater_exists: false,
-                _pd: PhantomData,
-            };
-            Either::Right(nu)
+            Either::Right(self.base_convert())
         }
     }
 }
-impl DoOnInterval<UpdateOnCheck> {
+impl<C: Clock> DoOnInterval<UpdateOnCheck, C> {
     ///Checks whether or not enough time has elapsed. If so, updates the timer and returns true, else returns false.
     ///
     ///If the action takes a while, it is reccomended to call `update_timer`
     pub fn can_do(&mut self) -> bool {
-        if self.last_did.elapsed() > self.gap {
-            self.last_did = Instant::now();
+        if self.base_ready() {
+            self.base_update();
             true
         } else {
             false
@@ -89,26 +309,340 @@ impl DoOnInterval<UpdateOnCheck> {
 
     ///Updates the timer.
     pub fn update_timer(&mut self) {
-        self.last_did = Instant::now();
+        self.base_update();
+    }
+
+    ///Runs `f` if enough time has elapsed, and then updates the timer. Returns the output of `f`, or [`None`] if it wasn't run.
+    pub fn do_if_ready<T>(&mut self, f: impl FnOnce() -> T) -> Option<T> {
+        if self.base_ready() {
+            let t = f();
+            self.base_update();
+            Some(t)
+        } else {
+            None
+        }
+    }
+
+    ///Gets how many whole intervals have elapsed since the last check, and updates the timer - eg. for running the right number of ticks in a fixed-timestep loop.
+    ///
+    ///To avoid drift, the timer is moved on by exact multiples of the interval rather than to now. Returns 0 while paused, and at least 1 if [`DoOnInterval::trigger_now`] was called.
+    pub fn times_ready(&mut self) -> u32 {
+        if self.paused.is_some() {
+            return 0;
+        }
+        let triggered = std::mem::take(&mut self.triggered);
+        if self.gap.is_zero() {
+            self.base_update();
+            return 1;
+        }
+
+        let gap = self.gap.as_nanos();
+        let times = if let Some(aligned) = &mut self.aligned {
+            let current = floor_to(self.clock.system_now(), self.gap);
+            let since = current
+                .duration_since(aligned.last_boundary)
+                .unwrap_or_default();
+            aligned.last_boundary = current;
+            since.as_nanos() / gap
+        } else {
+            let times = self.clock.elapsed(self.last_did).as_nanos() / gap;
+            //times * gap <= elapsed, so this can't overflow
+            #[allow(clippy::cast_possible_truncation)]
+            let advance = Duration::from_nanos((times * gap) as u64);
+            self.last_did += advance;
+            times
+        };
+
+        u32::try_from(times)
+            .unwrap_or(u32::MAX)
+            .max(u32::from(triggered))
     }
 
     ///Turns a [`UpdateOnCheck`] to a [`GiveUpdaters`]
     #[must_use]
-    pub const fn to_give_updaters(self) -> DoOnInterval<GiveUpdaters> {
-        DoOnInterval {
-            last_did: self.last_did,
-            gap: self.gap,
-            updater_exists: false,
-            _pd: PhantomData,
-        }
+    pub fn to_give_updaters(self) -> DoOnInterval<GiveUpdaters, C> {
+        self.base_convert()
+    }
+
+    ///Turns a [`UpdateOnCheck`] to an [`UpdateOnDo`]
+    #[must_use]
+    pub fn to_update_on_do(self) -> DoOnInterval<UpdateOnDo, C> {
+        self.base_convert()
+    }
+}
+
+impl<C: Clock> DoOnInterval<UpdateOnDo, C> {
+    ///Checks whether or not enough time has elapsed, without updating the timer - this keeps returning true until [`DoOnInterval::done`] is called.
+    pub fn can_do(&mut self) -> bool {
+        self.base_ready()
+    }
+
+    ///Marks the action as done, updating the timer
+    pub fn done(&mut self) {
+        self.base_update();
+    }
+
+    ///Turns an [`UpdateOnDo`] to an [`UpdateOnCheck`]
+    #[must_use]
+    pub fn to_update_on_check(self) -> DoOnInterval<UpdateOnCheck, C> {
+        self.base_convert()
     }
 }
 
 ///Struct to update [`DoOnInterval`] when the action finishes.
-pub struct DOIUpdate<'a>(&'a mut DoOnInterval<GiveUpdaters>);
-impl Drop for DOIUpdate<'_> {
+pub struct DOIUpdate<'a, C: Clock = SystemClock>(&'a mut DoOnInterval<GiveUpdaters, C>);
+impl<C: Clock> Drop for DOIUpdate<'_, C> {
     fn drop(&mut self) {
-        self.0.last_did = Instant::now();
+        self.0.base_update();
         self.0.updater_exists = false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_based_structs::clock::MockClock;
+
+    ///Makes a clock for aligned timers, with the wall-clock time set to `secs` after the epoch
+    fn wall_clock(secs: u64) -> MockClock {
+        let clock = MockClock::new();
+        set_secs(&clock, secs);
+        clock
+    }
+
+    fn set_secs(clock: &MockClock, secs: u64) {
+        clock.set_system_time(UNIX_EPOCH + Duration::from_secs(secs));
+    }
+
+    #[test]
+    fn aligned_fires_on_boundaries() {
+        let clock = wall_clock(1_000_040);
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::aligned_with_clock(Duration::from_secs(30), clock.clone());
+        assert_eq!(
+            doi.next_boundary(),
+            UNIX_EPOCH + Duration::from_secs(1_000_050)
+        );
+        assert!(!doi.can_do());
+
+        set_secs(&clock, 1_000_049);
+        assert!(!doi.can_do());
+        set_secs(&clock, 1_000_050);
+        assert!(doi.can_do());
+        assert!(!doi.can_do());
+
+        //skipping several boundaries only fires once
+        set_secs(&clock, 1_000_230);
+        assert!(doi.can_do());
+        assert!(!doi.can_do());
+    }
+
+    #[test]
+    fn progress_is_read_only() {
+        let clock = wall_clock(1_000_040);
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::aligned_with_clock(Duration::from_secs(40), clock.clone());
+        assert_eq!(doi.interval(), Duration::from_secs(40));
+        assert_eq!(doi.elapsed_in_interval(), Duration::ZERO);
+        assert_eq!(doi.time_until_next(), Duration::from_secs(40));
+
+        set_secs(&clock, 1_000_050);
+        assert_eq!(doi.elapsed_in_interval(), Duration::from_secs(10));
+        assert_eq!(doi.time_until_next(), Duration::from_secs(30));
+        assert!((doi.fraction_elapsed() - 0.25).abs() < f64::EPSILON);
+
+        set_secs(&clock, 1_000_095);
+        assert!((doi.fraction_elapsed() - 1.0).abs() < f64::EPSILON);
+        assert_eq!(doi.time_until_next(), Duration::ZERO);
+        assert!(doi.can_do());
+        assert_eq!(doi.elapsed_in_interval(), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn progress_without_alignment() {
+        let clock = wall_clock(1_000_000);
+        let ready: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock(Duration::from_secs(10), clock.clone());
+        assert!((ready.fraction_elapsed() - 1.0).abs() < f64::EPSILON);
+        assert_eq!(ready.time_until_next(), Duration::ZERO);
+
+        let waiting: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock_dont_initial_update(Duration::from_secs(1_000), clock.clone());
+        clock.advance(Duration::from_secs(250));
+        assert!((waiting.fraction_elapsed() - 0.25).abs() < f64::EPSILON);
+        assert_eq!(waiting.time_until_next(), Duration::from_secs(750));
+        assert_eq!(
+            waiting.next_boundary(),
+            UNIX_EPOCH + Duration::from_secs(1_001_000)
+        );
+    }
+
+    #[test]
+    fn paused_timer_does_not_fire_early() {
+        let clock = MockClock::new();
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock_dont_initial_update(Duration::from_millis(80), clock.clone());
+        clock.advance(Duration::from_millis(50));
+
+        doi.pause();
+        assert!(doi.is_paused());
+        clock.advance(Duration::from_millis(100));
+        assert!(!doi.can_do());
+        assert_eq!(doi.elapsed_in_interval(), Duration::from_millis(50));
+
+        doi.resume();
+        assert!(!doi.is_paused());
+        assert!(!doi.can_do());
+        clock.advance(Duration::from_millis(29));
+        assert!(!doi.can_do());
+        clock.advance(Duration::from_millis(2));
+        assert!(doi.can_do());
+    }
+
+    #[test]
+    fn paused_updaters() {
+        let mut doi: DoOnInterval<GiveUpdaters, _> =
+            DoOnInterval::with_clock(Duration::from_secs(1), MockClock::new());
+        doi.pause();
+        assert!(doi.get_updater().is_none());
+        doi.resume();
+        assert!(doi.get_updater().is_some());
+    }
+
+    #[test]
+    fn changing_interval_uses_elapsed_time() {
+        let clock = MockClock::new();
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock_dont_initial_update(Duration::from_secs(1_000), clock.clone());
+        clock.advance(Duration::from_millis(20));
+        assert!(!doi.can_do());
+
+        doi.set_interval(Duration::from_millis(10));
+        assert_eq!(doi.interval(), Duration::from_millis(10));
+        assert!(doi.can_do());
+        assert!(!doi.can_do());
+    }
+
+    #[test]
+    fn trigger_and_reset() {
+        let clock = MockClock::new();
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock_dont_initial_update(Duration::from_secs(1_000), clock.clone());
+        doi.trigger_now();
+        assert!((doi.fraction_elapsed() - 1.0).abs() < f64::EPSILON);
+        assert!(doi.can_do());
+        assert!(!doi.can_do());
+
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock(Duration::from_secs(1_000), clock);
+        doi.reset();
+        assert!(!doi.can_do());
+
+        doi.trigger_now();
+        doi.pause();
+        assert!(!doi.can_do());
+        doi.resume();
+        assert!(doi.can_do());
+    }
+
+    #[test]
+    fn do_if_ready_runs_once() {
+        let clock = MockClock::new();
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock(Duration::from_secs(1_000), clock.clone());
+        assert_eq!(doi.do_if_ready(|| 5), Some(5));
+        assert_eq!(doi.do_if_ready(|| 6), None);
+        clock.advance(Duration::from_secs(1_001));
+        assert_eq!(doi.do_if_ready(|| 7), Some(7));
+    }
+
+    #[test]
+    fn times_ready_catches_up() {
+        let clock = wall_clock(1_000_000);
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::aligned_with_clock(Duration::from_secs(10), clock.clone());
+        assert_eq!(doi.times_ready(), 0);
+
+        set_secs(&clock, 1_000_035);
+        assert_eq!(doi.times_ready(), 3);
+        assert_eq!(doi.times_ready(), 0);
+
+        set_secs(&clock, 1_000_041);
+        assert_eq!(doi.times_ready(), 1);
+
+        doi.trigger_now();
+        assert_eq!(doi.times_ready(), 1);
+        assert_eq!(doi.times_ready(), 0);
+    }
+
+    #[test]
+    fn times_ready_does_not_drift() {
+        let clock = MockClock::new();
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock_dont_initial_update(Duration::from_millis(20), clock.clone());
+        clock.advance(Duration::from_millis(50));
+        assert_eq!(doi.times_ready(), 2);
+        //the leftover 10ms carries over, rather than starting again from now
+        clock.advance(Duration::from_millis(15));
+        assert_eq!(doi.times_ready(), 1);
+        clock.advance(Duration::from_millis(14));
+        assert_eq!(doi.times_ready(), 0);
+    }
+
+    #[test]
+    fn huge_gaps_are_ready_straight_away() {
+        let clock = MockClock::new();
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock(Duration::MAX, clock.clone());
+        assert_eq!(doi.time_until_next(), Duration::ZERO);
+        assert!(doi.can_do());
+        assert!(!doi.can_do());
+        assert_eq!(doi.time_until_next(), Duration::MAX);
+
+        let mut doi: DoOnInterval<UpdateOnCheck, _> =
+            DoOnInterval::with_clock(Duration::from_secs(u64::MAX / 2), clock);
+        assert_eq!(doi.times_ready(), 1);
+        assert_eq!(doi.times_ready(), 0);
+    }
+
+    #[test]
+    fn update_on_do_checks_are_repeatable() {
+        let mut doi: DoOnInterval<UpdateOnDo, _> =
+            DoOnInterval::with_clock(Duration::from_secs(1_000), MockClock::new());
+        for _ in 0..5 {
+            assert!(doi.can_do());
+        }
+        doi.done();
+        assert!(!doi.can_do());
+
+        let mut doi = doi.to_update_on_check();
+        doi.trigger_now();
+        let mut doi = doi.to_update_on_do();
+        assert!(doi.can_do());
+        assert!(doi.can_do());
+    }
+
+    #[test]
+    fn aligned_clamps_backwards_jumps() {
+        let clock = wall_clock(1_000_050);
+        let mut doi: DoOnInterval<GiveUpdaters, _> =
+            DoOnInterval::aligned_with_clock(Duration::from_secs(30), clock.clone());
+
+        set_secs(&clock, 1_000_085);
+        assert!(doi.get_updater().is_some());
+
+        set_secs(&clock, 900_005);
+        assert!(doi.get_updater().is_none());
+        assert!(doi.get_updater().is_none());
+        assert_eq!(
+            doi.next_boundary(),
+            UNIX_EPOCH + Duration::from_secs(900_030)
+        );
+
+        set_secs(&clock, 900_030);
+        let updater = doi.get_updater();
+        assert!(updater.is_some());
+        drop(updater);
+        assert!(doi.get_updater().is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+///Wrapper around a [`Duration`] which displays it in a sensible unit and precision - eg. `152µs`, `1.53s`, `2m 5s` or `1h 02m`.
+///
+///Anything under a minute is shown to 3 significant figures with trailing zeroes dropped, minutes are shown with whole seconds, and hours with whole minutes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HumanDuration(pub Duration);
+
+impl From<Duration> for HumanDuration {
+    fn from(d: Duration) -> Self {
+        Self(d)
+    }
+}
+
+impl Display for HumanDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        ///The units to use under a minute, with how many nanoseconds are in each and when to move up to the next one
+        const UNITS: [(&str, f64, f64); 3] = [("µs", 1e3, 1e3), ("ms", 1e6, 1e3), ("s", 1e9, 60.0)];
+
+        let nanos = self.0.as_nanos();
+        if nanos < 1_000 {
+            return write!(f, "{nanos}ns");
+        }
+
+        #[allow(clippy::cast_precision_loss)] //we only need 3 significant figures
+        let nanos = nanos as f64;
+        for (unit, scale, next) in UNITS {
+            let value = nanos / scale;
+            let decimals = match value {
+                v if v < 10.0 => 2,
+                v if v < 100.0 => 1,
+                _ => 0,
+            };
+            let rounded = format!("{value:.decimals$}");
+            //rounding can carry over into the next unit, eg. 999.7µs -> 1000µs
+            if rounded.parse::<f64>().is_ok_and(|v| v < next) {
+                let trimmed = if rounded.contains('.') {
+                    rounded.trim_end_matches('0').trim_end_matches('.')
+                } else {
+                    &rounded
+                };
+                return write!(f, "{trimmed}{unit}");
+            }
+        }
+
+        let secs = self.0.as_secs().max(60);
+        if secs < 60 * 60 {
+            write!(f, "{}m {}s", secs / 60, secs % 60)
+        } else {
+            write!(f, "{}h {:02}m", secs / (60 * 60), (secs / 60) % 60)
+        }
+    }
+}
+
+///Formats `d` in a sensible unit and precision - see [`HumanDuration`]
+#[must_use]
+pub fn format_duration(d: Duration) -> String {
+    HumanDuration(d).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formatting_table() {
+        let table = [
+            (Duration::ZERO, "0ns"),
+            (Duration::from_nanos(7), "7ns"),
+            (Duration::from_nanos(999), "999ns"),
+            (Duration::from_nanos(1_001), "1µs"),
+            (Duration::from_nanos(1_532), "1.53µs"),
+            (Duration::from_nanos(152_300), "152µs"),
+            (Duration::from_nanos(999_700), "1ms"),
+            (Duration::from_micros(1_500), "1.5ms"),
+            (Duration::from_micros(25_260), "25.3ms"),
+            (Duration::from_micros(999_999), "1s"),
+            (Duration::from_micros(1_532_416), "1.53s"),
+            (Duration::from_millis(12_345), "12.3s"),
+            (Duration::from_millis(59_940), "59.9s"),
+            (Duration::from_millis(59_960), "1m 0s"),
+            (Duration::from_secs(125), "2m 5s"),
+            (Duration::from_secs(3_599), "59m 59s"),
+            (Duration::from_secs(3_725), "1h 02m"),
+            (Duration::from_secs(90_061), "25h 01m"),
+        ];
+
+        for (d, expected) in table {
+            assert_eq!(format_duration(d), expected, "formatting {d:?}");
+            assert_eq!(HumanDuration(d).to_string(), expected);
+        }
+    }
+}
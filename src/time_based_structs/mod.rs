@@ -1,6 +1,20 @@
 //! Module for structs which deal with time
 
+///Module to hold checkpoints for timing across scopes
+pub mod checkpoint;
+///Module to hold the clocks that the timers get the time from, so they can be tested without sleeping
+pub mod clock;
+///Module to hold debouncers and throttles, for coalescing bursts of calls
+pub mod debounce;
 ///Module to hold `DoOnInterval` and related structs
 pub mod do_on_interval;
+///Module to hold human-readable formatting for durations
+pub mod human_duration;
+///Module to hold a counter for how often something happens, eg. frames per second
+pub mod rate_counter;
+///Module to hold a logger which stops the same warning spamming the logs
+pub mod rate_limited_logger;
 ///Module to hold scoped timers
 pub mod scoped_timers;
+///Module to hold a registry of named timers, which logs summaries on an interval
+pub mod timer_registry;
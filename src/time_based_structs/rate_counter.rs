@@ -0,0 +1,146 @@
+use super::{
+    clock::{Clock, SystemClock},
+    do_on_interval::{DoOnInterval, UpdateOnCheck},
+};
+use crate::memcache::MemoryCacher;
+use std::time::{Duration, Instant};
+
+///Counts how often something happens (eg. frames per second), over the last `N` ticks
+pub struct RateCounter<const N: usize, C: Clock = SystemClock> {
+    ///When the last `N` ticks happened
+    ticks: MemoryCacher<Instant, N>,
+    ///When to give back the rate from [`RateCounter::report_on_interval`]
+    report: DoOnInterval<UpdateOnCheck, C>,
+    ///Where to get the time from
+    clock: C,
+}
+
+impl<const N: usize> RateCounter<N> {
+    ///Creates a new `RateCounter`, which reports every `report_interval`
+    #[must_use]
+    pub fn new(report_interval: Duration) -> Self {
+        Self::with_clock(report_interval, SystemClock)
+    }
+}
+
+impl<const N: usize, C: Clock + Clone> RateCounter<N, C> {
+    ///Same as [`RateCounter::new`], but getting the time from `clock`
+    #[must_use]
+    pub fn with_clock(report_interval: Duration, clock: C) -> Self {
+        Self {
+            ticks: MemoryCacher::default(),
+            report: DoOnInterval::with_clock_dont_initial_update(report_interval, clock.clone()),
+            clock,
+        }
+    }
+}
+
+impl<const N: usize, C: Clock> RateCounter<N, C> {
+    ///Records that the event happened now
+    pub fn tick(&mut self) {
+        self.ticks.push(self.clock.now());
+    }
+
+    ///Gets how many times per second the event happened, over the buffered ticks. Returns 0.0 if there are fewer than two ticks.
+    ///
+    ///The window runs up to now rather than to the latest tick, so the rate drops off if the ticks stop.
+    #[must_use]
+    pub fn rate_per_second(&self) -> f64 {
+        let Some(oldest) = self.ticks.oldest() else {
+            return 0.0;
+        };
+        if self.ticks.len() < 2 {
+            return 0.0;
+        }
+
+        let window = self.clock.elapsed(*oldest).as_secs_f64();
+        if window == 0.0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)] //N won't be anywhere near 2^52
+        let gaps = (self.ticks.len() - 1) as f64;
+        gaps / window
+    }
+
+    ///Gets the average time between the buffered ticks, or [`None`] if there are fewer than two
+    #[must_use]
+    pub fn average_tick_duration(&self) -> Option<Duration> {
+        let (oldest, latest) = (self.ticks.oldest()?, self.ticks.latest()?);
+        let gaps = u32::try_from(self.ticks.len().checked_sub(1)?).ok()?;
+        if gaps == 0 {
+            return None;
+        }
+        Some(latest.saturating_duration_since(*oldest) / gaps)
+    }
+
+    ///Gets the rate from [`RateCounter::rate_per_second`], but only if the report interval has elapsed - eg. to log it once a second rather than every frame
+    pub fn report_on_interval(&mut self) -> Option<f64> {
+        self.report.can_do().then(|| self.rate_per_second())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_based_structs::clock::MockClock;
+
+    #[test]
+    fn steady_rate() {
+        let clock = MockClock::new();
+        let mut counter: RateCounter<10, _> =
+            RateCounter::with_clock(Duration::from_secs(1), clock.clone());
+        assert!(counter.rate_per_second().abs() < f64::EPSILON);
+        assert_eq!(counter.average_tick_duration(), None);
+
+        counter.tick();
+        assert!(counter.rate_per_second().abs() < f64::EPSILON);
+        assert_eq!(counter.average_tick_duration(), None);
+
+        for _ in 0..20 {
+            clock.advance(Duration::from_millis(20));
+            counter.tick();
+        }
+        assert!((counter.rate_per_second() - 50.0).abs() < 1e-9);
+        assert_eq!(
+            counter.average_tick_duration(),
+            Some(Duration::from_millis(20))
+        );
+    }
+
+    #[test]
+    fn rate_drops_after_a_pause() {
+        let clock = MockClock::new();
+        let mut counter: RateCounter<5, _> =
+            RateCounter::with_clock(Duration::from_secs(1), clock.clone());
+        for _ in 0..5 {
+            counter.tick();
+            clock.advance(Duration::from_millis(100));
+        }
+        //4 gaps over the 0.4s of ticks plus the 0.1s since
+        assert!((counter.rate_per_second() - 8.0).abs() < 1e-9);
+
+        clock.advance(Duration::from_millis(3_500));
+        assert!((counter.rate_per_second() - 1.0).abs() < 1e-9);
+        assert_eq!(
+            counter.average_tick_duration(),
+            Some(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn reports_on_interval() {
+        let clock = MockClock::new();
+        let mut counter: RateCounter<4, _> =
+            RateCounter::with_clock(Duration::from_secs(1), clock.clone());
+        counter.tick();
+        assert_eq!(counter.report_on_interval(), None);
+
+        clock.advance(Duration::from_millis(500));
+        counter.tick();
+        assert_eq!(counter.report_on_interval(), None);
+
+        clock.advance(Duration::from_millis(501));
+        assert!(counter.report_on_interval().is_some());
+        assert_eq!(counter.report_on_interval(), None);
+    }
+}
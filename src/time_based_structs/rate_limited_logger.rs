@@ -0,0 +1,158 @@
+use super::{
+    clock::{Clock, SystemClock},
+    do_on_interval::{DoOnInterval, UpdateOnCheck},
+};
+#[cfg(feature = "console")]
+use crate::error_ext::LogLevel;
+use crate::error_types::BError;
+use std::{collections::HashMap, time::Duration};
+
+///The state for one key in a [`RateLimitedLogger`]
+struct KeyState<C: Clock> {
+    ///When this key is next allowed to log
+    timer: DoOnInterval<UpdateOnCheck, C>,
+    ///How many warnings have been swallowed since this key last logged
+    suppressed: usize,
+}
+
+///Stops the same warning spamming the logs (eg. every frame) by only logging it at most once per interval for each key.
+///
+///Warnings which get swallowed are counted, and the next one to get through says how many there were - eg. `disk full (suppressed 59 similar)`. See [`crate::error_ext::ErrorExt::warn_rate_limited`] for using this straight from a `Result`.
+pub struct RateLimitedLogger<C: Clock = SystemClock> {
+    ///The state for each key which has been seen
+    keys: HashMap<String, KeyState<C>>,
+    ///The minimum gap between two warnings with the same key
+    interval: Duration,
+    ///Where the timers for new keys get the time from
+    clock: C,
+}
+
+impl RateLimitedLogger {
+    ///Creates a new `RateLimitedLogger`, which logs each key at most once every `interval`
+    #[must_use]
+    pub fn new(interval: Duration) -> Self {
+        Self::with_clock(interval, SystemClock)
+    }
+}
+
+impl<C: Clock + Clone> RateLimitedLogger<C> {
+    ///Same as [`RateLimitedLogger::new`], but getting the time from `clock`
+    #[must_use]
+    pub fn with_clock(interval: Duration, clock: C) -> Self {
+        Self {
+            keys: HashMap::new(),
+            interval,
+            clock,
+        }
+    }
+
+    ///Writes `err` to `warn!` if `key` hasn't been logged in the last interval, else just counts it as suppressed.
+    ///
+    ///Returns whether or not it got logged. The first warning for a new key always gets logged.
+    pub fn rate_limited_warn(&mut self, key: &str, err: &BError) -> bool {
+        let Some(suppressed) = self.base_check(key) else {
+            return false;
+        };
+
+        let message = base_message(err, suppressed);
+        #[cfg(feature = "console")]
+        crate::console::record(LogLevel::Warn, || format!("{key}: {message}"));
+        crate::bn_warn!(%key, "{message}");
+        true
+    }
+
+    ///Base function to check whether `key` can log now. If so, gives back how many were suppressed since it last did and resets the count, else adds one to the count.
+    fn base_check(&mut self, key: &str) -> Option<usize> {
+        let state = self
+            .keys
+            .entry(key.to_string())
+            .or_insert_with(|| KeyState {
+                timer: DoOnInterval::with_clock(self.interval, self.clock.clone()),
+                suppressed: 0,
+            });
+
+        if state.timer.can_do() {
+            Some(std::mem::take(&mut state.suppressed))
+        } else {
+            state.suppressed += 1;
+            None
+        }
+    }
+}
+
+impl<C: Clock> RateLimitedLogger<C> {
+    ///Gets how many warnings for `key` have been suppressed since it last logged
+    #[must_use]
+    pub fn suppressed(&self, key: &str) -> usize {
+        self.keys.get(key).map_or(0, |state| state.suppressed)
+    }
+
+    ///Gets the minimum gap between two warnings with the same key
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+}
+
+///Base function for the line to log, mentioning how many were suppressed if there were any
+fn base_message(err: &BError, suppressed: usize) -> String {
+    if suppressed == 0 {
+        format!("{err:#}")
+    } else {
+        format!("{err:#} (suppressed {suppressed} similar)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error_ext::ErrorExt, error_types::BResult, time_based_structs::clock::MockClock};
+
+    #[test]
+    fn counts_suppressed_warnings() {
+        let clock = MockClock::new();
+        let mut limiter = RateLimitedLogger::with_clock(Duration::from_secs(1), clock.clone());
+        let err = BError::msg("disk full");
+
+        assert!(limiter.rate_limited_warn("disk", &err));
+        for _ in 0..3 {
+            clock.advance(Duration::from_millis(200));
+            assert!(!limiter.rate_limited_warn("disk", &err));
+        }
+        assert_eq!(limiter.suppressed("disk"), 3);
+
+        //other keys have their own timers
+        assert!(limiter.rate_limited_warn("network", &BError::msg("timed out")));
+        assert_eq!(limiter.suppressed("network"), 0);
+
+        clock.advance(Duration::from_millis(401));
+        assert_eq!(limiter.base_check("disk"), Some(3));
+        assert_eq!(limiter.suppressed("disk"), 0);
+        assert_eq!(limiter.base_check("disk"), None);
+        assert_eq!(limiter.suppressed("disk"), 1);
+    }
+
+    #[test]
+    fn message_mentions_suppressed() {
+        let err = BError::msg("disk full");
+        assert_eq!(base_message(&err, 0), "disk full");
+        assert_eq!(base_message(&err, 59), "disk full (suppressed 59 similar)");
+    }
+
+    #[test]
+    fn warn_rate_limited_from_result() {
+        let clock = MockClock::new();
+        let mut limiter = RateLimitedLogger::with_clock(Duration::from_secs(1), clock.clone());
+
+        for _ in 0..5 {
+            BResult::<()>::Err(BError::msg("no audio device"))
+                .warn_rate_limited(&mut limiter, "audio");
+            BResult::Ok(()).warn_rate_limited(&mut limiter, "audio");
+        }
+        assert_eq!(limiter.suppressed("audio"), 4);
+
+        clock.advance(Duration::from_millis(1001));
+        BResult::<()>::Err(BError::msg("no audio device")).warn_rate_limited(&mut limiter, "audio");
+        assert_eq!(limiter.suppressed("audio"), 0);
+    }
+}
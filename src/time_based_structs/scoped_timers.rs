@@ -1,4 +1,8 @@
-use crate::memcache::MemoryCacher;
+use super::{
+    clock::{Clock, SystemClock},
+    human_duration::HumanDuration,
+};
+use crate::{error_ext::LogLevel, memcache::MemoryCacher};
 use std::{
     fmt::Display,
     sync::{Arc, Mutex},
@@ -6,45 +10,320 @@ use std::{
 };
 
 ///Struct to time how long actions in a given scope last.
-pub struct ScopedTimer {
+pub struct ScopedTimer<C: Clock = SystemClock> {
     ///The message to print to the logs
     msg: String,
     ///When the action starts
     start_time: Instant,
+    ///Where to get the time from
+    clock: C,
+    ///If [`Some`], then only log if the scope took at least this long
+    min: Option<Duration>,
+    ///If [`Some`], then log as a warning rather than the normal level if the scope took at least this long
+    warn_at: Option<Duration>,
+    ///When the current lap started
+    lap_start: Instant,
+    ///The labels and times of all of the finished laps
+    laps: Vec<(String, Duration)>,
+    ///Whether or not the time has already been logged (or cancelled), so dropping shouldn't log it
+    fired: bool,
+    ///The level to log at, unless escalated by `warn_at`
+    level: LogLevel,
+    ///Extra `key=value` tags to log
+    tags: Vec<(&'static str, String)>,
 }
 
 impl ScopedTimer {
     ///Function to create a new `ScopedTimer` and start the timer
     pub fn new(msg: impl Display) -> Self {
+        Self::with_clock(msg, SystemClock)
+    }
+
+    ///Same as [`ScopedTimer::new`], but only logs if the scope took at least `min` - eg. to skip sub-millisecond noise
+    pub fn with_threshold(msg: impl Display, min: Duration) -> Self {
+        Self::new(msg).threshold(min)
+    }
+
+    ///Same as [`ScopedTimer::new`], but logs as a warning rather than info if the scope took at least `warn_at`
+    pub fn with_warn_threshold(msg: impl Display, warn_at: Duration) -> Self {
+        Self::new(msg).warn_threshold(warn_at)
+    }
+
+    ///Creates a builder for a timer with a custom level and extra tags. The timer doesn't start until [`ScopedTimerBuilder::build`] is called.
+    pub fn builder(msg: impl Display) -> ScopedTimerBuilder {
+        ScopedTimerBuilder {
+            msg: msg.to_string(),
+            clock: SystemClock,
+            level: LogLevel::Info,
+            tags: vec![],
+            min: None,
+            warn_at: None,
+        }
+    }
+}
+
+///Builder for a [`ScopedTimer`], made with [`ScopedTimer::builder`]
+#[must_use]
+pub struct ScopedTimerBuilder<C: Clock = SystemClock> {
+    ///The message to print to the logs
+    msg: String,
+    ///Where to get the time from
+    clock: C,
+    ///The level to log at
+    level: LogLevel,
+    ///Extra `key=value` tags to log
+    tags: Vec<(&'static str, String)>,
+    ///See [`ScopedTimer::threshold`]
+    min: Option<Duration>,
+    ///See [`ScopedTimer::warn_threshold`]
+    warn_at: Option<Duration>,
+}
+
+impl<C: Clock> ScopedTimerBuilder<C> {
+    ///Sets the level to log at - eg. [`LogLevel::Trace`] for hot paths. Defaults to [`LogLevel::Info`].
+    pub const fn level(mut self, level: LogLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    ///Adds an extra `key=value` tag to the log line, eg. a request id.
+    ///
+    ///Tags are just text - with `tracing` they all go in one `tags` value, as `tracing` needs the names of fields at compile time, so subscribers can't filter on them one by one. For that, start the timer inside a span which has them as fields.
+    pub fn tag(mut self, key: &'static str, value: impl Display) -> Self {
+        self.tags.push((key, value.to_string()));
+        self
+    }
+
+    ///See [`ScopedTimer::threshold`]
+    pub const fn threshold(mut self, min: Duration) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    ///See [`ScopedTimer::warn_threshold`]
+    pub const fn warn_threshold(mut self, warn_at: Duration) -> Self {
+        self.warn_at = Some(warn_at);
+        self
+    }
+
+    ///Gets the time from `clock` rather than the current clock
+    pub fn clock<D: Clock>(self, clock: D) -> ScopedTimerBuilder<D> {
+        ScopedTimerBuilder {
+            msg: self.msg,
+            clock,
+            level: self.level,
+            tags: self.tags,
+            min: self.min,
+            warn_at: self.warn_at,
+        }
+    }
+
+    ///Creates the timer, and starts it
+    pub fn build(self) -> ScopedTimer<C> {
+        let mut timer = ScopedTimer::with_clock(self.msg, self.clock);
+        timer.level = self.level;
+        timer.tags = self.tags;
+        timer.min = self.min;
+        timer.warn_at = self.warn_at;
+        timer
+    }
+}
+
+impl<C: Clock> ScopedTimer<C> {
+    ///Same as [`ScopedTimer::new`], but getting the time from `clock` - eg. a [`super::clock::MockClock`] for testing
+    pub fn with_clock(msg: impl Display, clock: C) -> Self {
+        let now = clock.now();
         Self {
             msg: msg.to_string(),
-            start_time: Instant::now(),
+            start_time: now,
+            clock,
+            min: None,
+            warn_at: None,
+            lap_start: now,
+            laps: vec![],
+            fired: false,
+            level: LogLevel::Info,
+            tags: vec![],
+        }
+    }
+
+    ///Records the time since the last lap (or the start) under `label`, and starts a new lap - eg. for timing each phase of a long function.
+    ///
+    ///The laps get logged along with the total when the timer is dropped. Returns the time taken for this lap.
+    pub fn lap(&mut self, label: impl Display) -> Duration {
+        let now = self.clock.now();
+        let taken = now.saturating_duration_since(self.lap_start);
+        self.lap_start = now;
+        self.laps.push((label.to_string(), taken));
+        taken
+    }
+
+    ///Gets the labels and times of all of the laps so far
+    #[must_use]
+    pub fn checkpoints(&self) -> &[(String, Duration)] {
+        &self.laps
+    }
+
+    ///Only log if the scope took at least `min`
+    #[must_use]
+    pub const fn threshold(mut self, min: Duration) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    ///Log as a warning rather than info if the scope took at least `warn_at`
+    #[must_use]
+    pub const fn warn_threshold(mut self, warn_at: Duration) -> Self {
+        self.warn_at = Some(warn_at);
+        self
+    }
+
+    ///Stops the timer without logging anything - eg. if the action failed early
+    pub fn cancel(mut self) {
+        self.fired = true;
+    }
+
+    ///Stops the timer and logs straight away, rather than waiting to be dropped. Returns the time taken, eg. to also put in a metrics struct.
+    pub fn finish(mut self) -> Duration {
+        let elapsed = self.elapsed();
+        self.base_log(elapsed);
+        self.fired = true;
+        elapsed
+    }
+
+    ///Base function for logging the time taken, along with any laps
+    fn base_log(&self, elapsed: Duration) {
+        let Some(level) = self.base_level(elapsed) else {
+            return;
+        };
+        let msg = if self.laps.is_empty() {
+            self.msg.clone()
+        } else {
+            let laps: Vec<String> = self
+                .laps
+                .iter()
+                .map(|(label, taken)| format!("{label}: {}", HumanDuration(*taken)))
+                .collect();
+            format!("{} ({})", self.msg, laps.join(", "))
+        };
+
+        let tags: Vec<String> = self
+            .tags
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+        let tags = tags.join(" ");
+
+        let took = HumanDuration(elapsed);
+
+        ///Logs with a given `bn_*` macro, as they need the level at compile time
+        macro_rules! log {
+            ($mac:ident) => {
+                if tags.is_empty() {
+                    crate::$mac!(time_taken = %took, "{msg}")
+                } else {
+                    crate::$mac!(time_taken = %took, %tags, "{msg}")
+                }
+            };
+        }
+
+        match level {
+            LogLevel::Trace => log!(bn_trace),
+            LogLevel::Debug => log!(bn_debug),
+            LogLevel::Info => log!(bn_info),
+            LogLevel::Warn => log!(bn_warn),
+            LogLevel::Error => log!(bn_error),
         }
     }
+
+    ///Base function for working out what level to log at after `elapsed`, or [`None`] if it shouldn't be logged
+    fn base_level(&self, elapsed: Duration) -> Option<LogLevel> {
+        if self.min.is_some_and(|min| elapsed < min) {
+            None
+        } else if self.warn_at.is_some_and(|warn_at| elapsed >= warn_at) {
+            Some(self.level.max(LogLevel::Warn))
+        } else {
+            Some(self.level)
+        }
+    }
+
+    ///Gets how long it has been since the timer started
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed(self.start_time)
+    }
 }
 
-impl Drop for ScopedTimer {
+impl<C: Clock> Drop for ScopedTimer<C> {
     fn drop(&mut self) {
-        #[cfg(feature = "tracing")]
-        tracing::info!(time_taken=?self.start_time.elapsed(), msg=%self.msg);
-        #[cfg(not(feature = "tracing"))]
-        println!("{} took {:?}", self.msg, self.start_time.elapsed());
+        if !self.fired {
+            self.base_log(self.elapsed());
+        }
     }
 }
 
 ///Same as [`ScopedTimer`], but updates a [`crate::memcache::MemoryCacher`] rather than adding to logs
-pub struct ScopedToListTimer<'a, const N: usize>(&'a mut MemoryCacher<Duration, N>, Instant);
+pub struct ScopedToListTimer<'a, const N: usize, C: Clock = SystemClock>(
+    &'a mut MemoryCacher<Duration, N>,
+    Instant,
+    C,
+);
 
 impl<'a, const N: usize> ScopedToListTimer<'a, N> {
     ///Creates a new `ScopedToListTimer`, and starts the timer
     pub fn new(t: &'a mut MemoryCacher<Duration, N>) -> Self {
-        Self(t, Instant::now())
+        Self::with_clock(t, SystemClock)
+    }
+}
+
+impl<'a, const N: usize, C: Clock> ScopedToListTimer<'a, N, C> {
+    ///Same as [`ScopedToListTimer::new`], but getting the time from `clock`
+    pub fn with_clock(t: &'a mut MemoryCacher<Duration, N>, clock: C) -> Self {
+        Self(t, clock.now(), clock)
+    }
+}
+
+impl<const N: usize, C: Clock> Drop for ScopedToListTimer<'_, N, C> {
+    fn drop(&mut self) {
+        self.0.push(self.2.elapsed(self.1));
+    }
+}
+
+///Same as [`ScopedTimer`], but calls `F` with the time taken rather than logging it - eg. to send it to a metrics sink or a channel.
+///
+///As `&mut F` is also a closure when `F: FnMut(Duration)`, one closure can be reused for lots of timers by passing `&mut f`.
+pub struct ScopedCallbackTimer<F: FnOnce(Duration), C: Clock = SystemClock> {
+    ///What to call with the time taken - only [`None`] once it has been called
+    callback: Option<F>,
+    ///When the action starts
+    start_time: Instant,
+    ///Where to get the time from
+    clock: C,
+}
+
+impl<F: FnOnce(Duration)> ScopedCallbackTimer<F> {
+    ///Creates a new `ScopedCallbackTimer`, and starts the timer
+    pub fn new(callback: F) -> Self {
+        Self::with_clock(callback, SystemClock)
+    }
+}
+
+impl<F: FnOnce(Duration), C: Clock> ScopedCallbackTimer<F, C> {
+    ///Same as [`ScopedCallbackTimer::new`], but getting the time from `clock`
+    pub fn with_clock(callback: F, clock: C) -> Self {
+        Self {
+            callback: Some(callback),
+            start_time: clock.now(),
+            clock,
+        }
     }
 }
 
-impl<'a, const N: usize> Drop for ScopedToListTimer<'a, N> {
+impl<F: FnOnce(Duration), C: Clock> Drop for ScopedCallbackTimer<F, C> {
     fn drop(&mut self) {
-        self.0.push(self.1.elapsed());
+        if let Some(callback) = self.callback.take() {
+            callback(self.clock.elapsed(self.start_time));
+        }
     }
 }
 
@@ -81,3 +360,210 @@ impl<const N: usize> Drop for ThreadSafeScopedToListTimer<N> {
         lock.push(elapsed);
     }
 }
+
+///Times how long an expression takes with a [`ScopedTimer`] using `label`, and gives back the value of the expression.
+///
+///The expression is evaluated in place, so its type and any moves are unchanged, and it works as a statement or as an expression. The timer is dropped after the expression, so the time is still logged if it returns early or uses `?`.
+///
+///```rust
+/// use burntnail_utils::timed;
+///
+/// let sum = timed!("adding", 1 + 2);
+/// assert_eq!(sum, 3);
+///
+/// let owned = String::from("moved in");
+/// let len = timed!("measuring", { let s = owned; s.len() });
+/// assert_eq!(len, 8);
+///
+/// timed!("printing", println!("hello"));
+///```
+#[macro_export]
+macro_rules! timed {
+    ($label:expr, $e:expr $(,)?) => {{
+        let _timer = $crate::time_based_structs::scoped_timers::ScopedTimer::new($label);
+        $e
+    }};
+}
+
+///Same as [`timed!`], but pushes the time taken into a `&mut MemoryCacher<Duration, N>` with a [`ScopedToListTimer`] rather than logging it.
+///
+///```rust
+/// use burntnail_utils::{memcache::MemoryCacher, timed_into};
+/// use std::time::Duration;
+///
+/// fn find_zero(times: &mut MemoryCacher<Duration, 8>, xs: &[u8]) -> Option<usize> {
+///     timed_into!(times, {
+///         for (i, x) in xs.iter().enumerate() {
+///             if *x == 0 {
+///                 return Some(i); //still pushes the time, as the timer is dropped
+///             }
+///         }
+///         None
+///     })
+/// }
+///
+/// let mut times = MemoryCacher::default();
+/// assert_eq!(find_zero(&mut times, &[3, 0, 1]), Some(1));
+/// assert_eq!(find_zero(&mut times, &[3, 1]), None);
+/// assert_eq!(times.len(), 2);
+///
+/// let doubled = timed_into!(&mut times, 21 * 2);
+/// assert_eq!(doubled, 42);
+/// assert_eq!(times.len(), 3);
+///```
+#[macro_export]
+macro_rules! timed_into {
+    ($cacher:expr, $e:expr $(,)?) => {{
+        let _timer = $crate::time_based_structs::scoped_timers::ScopedToListTimer::new($cacher);
+        $e
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_based_structs::clock::MockClock;
+
+    #[test]
+    fn thresholds_pick_the_level() {
+        let clock = MockClock::new();
+        let timer =
+            ScopedTimer::with_clock("quiet", clock.clone()).threshold(Duration::from_millis(5));
+        assert_eq!(timer.base_level(Duration::from_millis(4)), None);
+        assert_eq!(
+            timer.base_level(Duration::from_millis(5)),
+            Some(LogLevel::Info)
+        );
+
+        let timer = ScopedTimer::with_clock("slow", clock)
+            .threshold(Duration::from_millis(5))
+            .warn_threshold(Duration::from_millis(50));
+        assert_eq!(timer.base_level(Duration::from_millis(1)), None);
+        assert_eq!(
+            timer.base_level(Duration::from_millis(20)),
+            Some(LogLevel::Info)
+        );
+        assert_eq!(
+            timer.base_level(Duration::from_millis(50)),
+            Some(LogLevel::Warn)
+        );
+
+        let timer = ScopedTimer::with_warn_threshold("plain", Duration::from_secs(1));
+        assert_eq!(timer.base_level(Duration::ZERO), Some(LogLevel::Info));
+        drop(ScopedTimer::with_threshold(
+            "dropped",
+            Duration::from_secs(1),
+        ));
+    }
+
+    #[test]
+    fn laps_are_recorded() {
+        let clock = MockClock::new();
+        let mut timer = ScopedTimer::with_clock("phases", clock.clone());
+        clock.advance(Duration::from_millis(10));
+        assert_eq!(timer.lap("load"), Duration::from_millis(10));
+        clock.advance(Duration::from_millis(25));
+        timer.lap(format_args!("step {}", 2));
+
+        assert_eq!(
+            timer.checkpoints(),
+            &[
+                ("load".to_string(), Duration::from_millis(10)),
+                ("step 2".to_string(), Duration::from_millis(25))
+            ]
+        );
+        assert_eq!(timer.elapsed(), Duration::from_millis(35));
+    }
+
+    #[test]
+    fn builder_sets_level_and_tags() {
+        let clock = MockClock::new();
+        let timer = ScopedTimer::builder("request")
+            .level(LogLevel::Debug)
+            .tag("request_id", 5)
+            .tag("user", "bob")
+            .warn_threshold(Duration::from_millis(100))
+            .clock(clock.clone())
+            .build();
+
+        assert_eq!(
+            timer.tags,
+            vec![("request_id", "5".to_string()), ("user", "bob".to_string())]
+        );
+        assert_eq!(
+            timer.base_level(Duration::from_millis(1)),
+            Some(LogLevel::Debug)
+        );
+        assert_eq!(
+            timer.base_level(Duration::from_millis(100)),
+            Some(LogLevel::Warn)
+        );
+
+        clock.advance(Duration::from_millis(3));
+        assert_eq!(timer.finish(), Duration::from_millis(3));
+
+        let timer = ScopedTimer::builder("critical")
+            .level(LogLevel::Error)
+            .warn_threshold(Duration::ZERO)
+            .build();
+        assert_eq!(timer.base_level(Duration::ZERO), Some(LogLevel::Error));
+        timer.cancel();
+    }
+
+    #[test]
+    fn finish_and_cancel() {
+        let clock = MockClock::new();
+        let timer = ScopedTimer::with_clock("finished", clock.clone());
+        clock.advance(Duration::from_millis(7));
+        assert_eq!(timer.finish(), Duration::from_millis(7));
+
+        let timer = ScopedTimer::with_clock("cancelled", clock);
+        assert!(!timer.fired);
+        timer.cancel();
+    }
+
+    #[test]
+    fn callbacks_get_the_time() {
+        let clock = MockClock::new();
+        let (tx, rx) = std::sync::mpsc::channel();
+        {
+            let _timer = ScopedCallbackTimer::with_clock(
+                move |taken| tx.send(taken).unwrap(),
+                clock.clone(),
+            );
+            clock.advance(Duration::from_millis(12));
+        }
+        assert_eq!(rx.recv().unwrap(), Duration::from_millis(12));
+
+        let mut all = vec![];
+        let mut record = |taken| all.push(taken);
+        for i in 1..=3 {
+            let _timer = ScopedCallbackTimer::with_clock(&mut record, clock.clone());
+            clock.advance(Duration::from_millis(i));
+        }
+        assert_eq!(
+            all,
+            vec![
+                Duration::from_millis(1),
+                Duration::from_millis(2),
+                Duration::from_millis(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn timers_use_the_clock() {
+        let clock = MockClock::new();
+        let timer = ScopedTimer::with_clock("test", clock.clone());
+        clock.advance(Duration::from_millis(250));
+        assert_eq!(timer.elapsed(), Duration::from_millis(250));
+        drop(timer);
+
+        let mut cache: MemoryCacher<Duration, 4> = MemoryCacher::new(None);
+        {
+            let _timer = ScopedToListTimer::with_clock(&mut cache, clock.clone());
+            clock.advance(Duration::from_millis(40));
+        }
+        assert_eq!(cache.get_all_ordered(), vec![Duration::from_millis(40)]);
+    }
+}
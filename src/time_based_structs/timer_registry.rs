@@ -0,0 +1,209 @@
+use super::{
+    clock::{Clock, SystemClock},
+    do_on_interval::{DoOnInterval, UpdateOnCheck},
+    human_duration::HumanDuration,
+    scoped_timers::ScopedCallbackTimer,
+};
+use crate::{error_ext::MutexExt, memcache::MemoryCacher};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+///Summary of the recent timings for one name in a [`TimerRegistry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimerSummary {
+    ///How many timings there are - at most the capacity of the registry
+    pub count: usize,
+    ///The mean of the timings
+    pub average: Duration,
+    ///The longest of the timings
+    pub max: Duration,
+}
+
+///The shared state of a [`TimerRegistry`]
+struct Inner<const N: usize, C: Clock> {
+    ///The most recent timings for each name
+    timings: BTreeMap<String, MemoryCacher<Duration, N>>,
+    ///When to log out the summaries
+    report: DoOnInterval<UpdateOnCheck, C>,
+}
+
+///Keeps the last `N` timings for each named scope, and logs a summary line for each name (count, average and max) on an interval, before clearing them.
+///
+///Clones share the same timings, so one registry can be handed out to lots of threads.
+pub struct TimerRegistry<const N: usize, C: Clock = SystemClock> {
+    ///The shared state
+    inner: Arc<Mutex<Inner<N, C>>>,
+    ///Where to get the time from for timers
+    clock: C,
+}
+
+impl<const N: usize, C: Clock + Clone> Clone for TimerRegistry<N, C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            clock: self.clock.clone(),
+        }
+    }
+}
+
+impl<const N: usize> TimerRegistry<N> {
+    ///Creates a new `TimerRegistry`, which logs out summaries every `summary_interval`
+    #[must_use]
+    pub fn new(summary_interval: Duration) -> Self {
+        Self::with_clock(summary_interval, SystemClock)
+    }
+}
+
+impl<const N: usize, C: Clock + Clone> TimerRegistry<N, C> {
+    ///Same as [`TimerRegistry::new`], but getting the time from `clock`
+    #[must_use]
+    pub fn with_clock(summary_interval: Duration, clock: C) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Inner {
+                timings: BTreeMap::new(),
+                report: DoOnInterval::with_clock_dont_initial_update(
+                    summary_interval,
+                    clock.clone(),
+                ),
+            })),
+            clock,
+        }
+    }
+
+    ///Starts timing a scope under `name`. The time gets recorded when the guard is dropped.
+    #[must_use]
+    pub fn time(&self, name: impl Display) -> ScopedCallbackTimer<impl FnOnce(Duration), C> {
+        let registry = self.clone();
+        let name = name.to_string();
+        ScopedCallbackTimer::with_clock(
+            move |taken| registry.record(&name, taken),
+            self.clock.clone(),
+        )
+    }
+
+    ///Records a timing under `name`, and logs out and clears the summaries if the interval has elapsed
+    pub fn record(&self, name: &str, taken: Duration) {
+        let mut inner = self.inner.lock_panic("locking timer registry");
+        inner
+            .timings
+            .entry(name.to_string())
+            .or_default()
+            .push(taken);
+
+        if inner.report.can_do() {
+            let summaries = base_summaries(&inner.timings);
+            inner.timings.clear();
+            drop(inner);
+            log_summaries(&summaries);
+        }
+    }
+
+    ///Gets the summaries for every name, sorted by name
+    #[must_use]
+    pub fn summaries(&self) -> Vec<(String, TimerSummary)> {
+        base_summaries(&self.inner.lock_panic("locking timer registry").timings)
+    }
+
+    ///Logs out and clears the summaries straight away, rather than waiting for the interval
+    pub fn report_now(&self) {
+        let mut inner = self.inner.lock_panic("locking timer registry");
+        let summaries = base_summaries(&inner.timings);
+        inner.timings.clear();
+        inner.report.update_timer();
+        drop(inner);
+        log_summaries(&summaries);
+    }
+}
+
+///Base function for getting the summaries of every name, skipping any without timings
+fn base_summaries<const N: usize>(
+    timings: &BTreeMap<String, MemoryCacher<Duration, N>>,
+) -> Vec<(String, TimerSummary)> {
+    timings
+        .iter()
+        .filter_map(|(name, timings)| {
+            Some((
+                name.clone(),
+                TimerSummary {
+                    count: timings.len(),
+                    average: timings.average()?,
+                    max: *timings.max()?,
+                },
+            ))
+        })
+        .collect()
+}
+
+///Logs out one line for each summary
+fn log_summaries(summaries: &[(String, TimerSummary)]) {
+    for (name, summary) in summaries {
+        crate::bn_info!(%name, count = summary.count, average = %HumanDuration(summary.average), max = %HumanDuration(summary.max), "Timer summary");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_based_structs::clock::MockClock;
+
+    #[test]
+    fn summarises_each_name() {
+        let clock = MockClock::new();
+        let registry: TimerRegistry<3, _> =
+            TimerRegistry::with_clock(Duration::from_secs(1), clock.clone());
+
+        for taken in [10, 30, 50, 20] {
+            let _timer = registry.time("physics");
+            clock.advance(Duration::from_millis(taken));
+        }
+        registry.record("render", Duration::from_millis(5));
+
+        //only the last 3 physics timings are kept
+        assert_eq!(
+            registry.summaries(),
+            vec![
+                (
+                    "physics".to_string(),
+                    TimerSummary {
+                        count: 3,
+                        average: Duration::from_nanos(33_333_333),
+                        max: Duration::from_millis(50),
+                    }
+                ),
+                (
+                    "render".to_string(),
+                    TimerSummary {
+                        count: 1,
+                        average: Duration::from_millis(5),
+                        max: Duration::from_millis(5),
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn clears_after_reporting() {
+        let clock = MockClock::new();
+        let registry: TimerRegistry<8, _> =
+            TimerRegistry::with_clock(Duration::from_millis(100), clock.clone());
+
+        registry.record("io", Duration::from_millis(1));
+        assert_eq!(registry.summaries().len(), 1);
+
+        clock.advance(Duration::from_millis(101));
+        let other_thread = registry.clone();
+        std::thread::spawn(move || other_thread.record("io", Duration::from_millis(2)))
+            .join()
+            .unwrap();
+        assert!(registry.summaries().is_empty());
+
+        registry.record("io", Duration::from_millis(3));
+        registry.report_now();
+        assert!(registry.summaries().is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use super::TwoArray;
+
+///What [`TwoArray::convolve`] uses for the parts of the kernel which hang off the edge of the array
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConvolveEdges<T> {
+    ///Uses the nearest element on the edge, as if the edges went on forever
+    Clamp,
+    ///Uses this value instead
+    Fill(T),
+}
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Makes a new array by running `f` over a `kernel_width`x`kernel_height` neighbourhood of every element - eg. for a box blur, or a step of the Game of Life.
+    ///
+    ///`f` gets the neighbourhood in row-major order, with the element itself at `kernel_width / 2` across and `kernel_height / 2` down. Parts of the neighbourhood off the edge of the array come from `edges`.
+    pub fn convolve<U>(
+        &self,
+        kernel_width: usize,
+        kernel_height: usize,
+        edges: &ConvolveEdges<T>,
+        f: impl Fn(&[&T]) -> U,
+    ) -> TwoArray<U, W, H> {
+        let (half_width, half_height) = (kernel_width / 2, kernel_height / 2);
+        //finds where a part of the kernel lands on one axis, or None if off the edge and not clamping
+        let sample = |centre: usize, offset: usize, half: usize, len: usize| {
+            let pos = (centre + offset).checked_sub(half);
+            match edges {
+                ConvolveEdges::Clamp => Some(pos.unwrap_or(0).min(len - 1)),
+                ConvolveEdges::Fill(_) => pos.filter(|&pos| pos < len),
+            }
+        };
+
+        let mut backing = Vec::with_capacity(W * H);
+        let mut neighbourhood = Vec::with_capacity(kernel_width * kernel_height);
+        for y in 0..H {
+            for x in 0..W {
+                neighbourhood.clear();
+                for ky in 0..kernel_height {
+                    let sy = sample(y, ky, half_height, H);
+                    for kx in 0..kernel_width {
+                        let sx = sample(x, kx, half_width, W);
+                        neighbourhood.push(match (sx, sy, edges) {
+                            (Some(sx), Some(sy), _) => &self.backing[sy * W + sx],
+                            (_, _, ConvolveEdges::Fill(fill)) => fill,
+                            //clamping always lands inside the array
+                            (_, _, ConvolveEdges::Clamp) => unreachable!(),
+                        });
+                    }
+                }
+                backing.push(f(&neighbourhood));
+            }
+        }
+
+        TwoArray { backing }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twod_array::test_fixtures::indices;
+
+    ///1 2 3
+    ///4 5 6
+    ///7 8 9
+    fn counting() -> TwoArray<usize, 3, 3> {
+        indices().map(|i| i + 1)
+    }
+
+    fn sum(els: &[&usize]) -> usize {
+        els.iter().copied().sum()
+    }
+
+    #[test]
+    fn box_blur() {
+        let array = counting();
+
+        let filled = array.convolve(3, 3, &ConvolveEdges::Fill(0), sum);
+        assert_eq!(filled.as_slice(), &[12, 21, 16, 27, 45, 33, 24, 39, 28]);
+
+        let clamped = array.convolve(3, 3, &ConvolveEdges::Clamp, sum);
+        assert_eq!(clamped.as_slice(), &[21, 27, 33, 39, 45, 51, 57, 63, 69]);
+
+        let blurred = array.convolve(3, 3, &ConvolveEdges::Clamp, |els| sum(els) / 9);
+        assert_eq!(blurred.as_slice(), &[2, 3, 3, 4, 5, 5, 6, 7, 7]);
+    }
+
+    #[test]
+    fn kernel_shapes() {
+        let array = counting();
+
+        //a 1x1 kernel just sees the element itself
+        let same = array.convolve(1, 1, &ConvolveEdges::Fill(0), |els| *els[0]);
+        assert_eq!(same.as_slice(), array.as_slice());
+
+        //an even kernel has the element just below and right of the middle
+        let pairs = array.convolve(2, 1, &ConvolveEdges::Fill(0), |els| (*els[0], *els[1]));
+        assert_eq!(pairs[(0, 0)], (0, 1));
+        assert_eq!(pairs[(2, 1)], (5, 6));
+
+        //game of life style neighbour counts
+        let live: TwoArray<bool, 3, 3> = TwoArray::try_from(vec![
+            false, true, false, false, true, false, false, true, false,
+        ])
+        .unwrap();
+        let neighbours = live.convolve(3, 3, &ConvolveEdges::Fill(false), |els| {
+            els.iter().filter(|el| ***el).count() - usize::from(*els[4])
+        });
+        assert_eq!(neighbours.as_slice(), &[2, 1, 2, 3, 2, 3, 2, 1, 2]);
+    }
+}
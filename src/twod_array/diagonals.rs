@@ -0,0 +1,136 @@
+use super::{ArrayCoords, TwoArray};
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Iterates down-right along the main diagonal from the top left. For non-square arrays, this stops at whichever edge it hits first.
+    pub fn main_diagonal(&self) -> impl Iterator<Item = &T> {
+        (0..W.min(H)).map(move |i| &self.backing[i * W + i])
+    }
+
+    ///Iterates down-left along the anti-diagonal from the top right. For non-square arrays, this stops at whichever edge it hits first.
+    pub fn anti_diagonal(&self) -> impl Iterator<Item = &T> {
+        (0..W.min(H)).map(move |i| &self.backing[i * W + (W - 1 - i)])
+    }
+
+    ///Iterates over every down-right diagonal line, including the short ones in the corners - eg. for checking for a line of 4 in connect four.
+    ///
+    ///Goes from the line starting in the bottom left corner, up the left column and then along the top row to the one in the top right corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<ArrayCoords<W, H>>> {
+        let starts = (0..H).rev().map(|y| (0, y)).chain((1..W).map(|x| (x, 0)));
+        //an empty array still has a row or column to start from
+        starts.filter(|_| W > 0 && H > 0).map(|(x, y)| {
+            (0..(W - x).min(H - y))
+                .map(|i| ArrayCoords::InBounds(x + i, y + i))
+                .collect()
+        })
+    }
+
+    ///Iterates over every down-left diagonal line, including the short ones in the corners - the counterpart to [`TwoArray::diagonals`].
+    ///
+    ///Goes from the line starting in the top left corner, along the top row and then down the right column to the one in the bottom right corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<ArrayCoords<W, H>>> {
+        let starts = (0..W)
+            .map(|x| (x, 0))
+            .chain((1..H).map(|y| (W.saturating_sub(1), y)));
+        starts.filter(|_| W > 0 && H > 0).map(|(x, y)| {
+            (0..(x + 1).min(H - y))
+                .map(|i| ArrayCoords::InBounds(x - i, y + i))
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twod_array::test_fixtures::numbered;
+
+    fn values<const W: usize, const H: usize>(
+        array: &TwoArray<usize, W, H>,
+        lines: impl Iterator<Item = Vec<ArrayCoords<W, H>>>,
+    ) -> Vec<Vec<usize>> {
+        lines
+            .map(|line| line.into_iter().map(|c| array[c]).collect())
+            .collect()
+    }
+
+    #[test]
+    fn main_and_anti() {
+        let square = numbered::<3, 3>();
+        assert_eq!(
+            square.main_diagonal().copied().collect::<Vec<_>>(),
+            vec![0, 11, 22]
+        );
+        assert_eq!(
+            square.anti_diagonal().copied().collect::<Vec<_>>(),
+            vec![2, 11, 20]
+        );
+
+        let wide = numbered::<4, 2>();
+        assert_eq!(
+            wide.main_diagonal().copied().collect::<Vec<_>>(),
+            vec![0, 11]
+        );
+        assert_eq!(
+            wide.anti_diagonal().copied().collect::<Vec<_>>(),
+            vec![3, 12]
+        );
+
+        let tall = numbered::<2, 3>();
+        assert_eq!(
+            tall.main_diagonal().copied().collect::<Vec<_>>(),
+            vec![0, 11]
+        );
+        assert_eq!(
+            tall.anti_diagonal().copied().collect::<Vec<_>>(),
+            vec![1, 10]
+        );
+    }
+
+    #[test]
+    fn every_line() {
+        let wide = numbered::<4, 2>();
+        assert_eq!(
+            values(&wide, wide.diagonals()),
+            vec![vec![10], vec![0, 11], vec![1, 12], vec![2, 13], vec![3]]
+        );
+        assert_eq!(
+            values(&wide, wide.anti_diagonals()),
+            vec![vec![0], vec![1, 10], vec![2, 11], vec![3, 12], vec![13]]
+        );
+
+        let tall = numbered::<2, 3>();
+        assert_eq!(
+            values(&tall, tall.diagonals()),
+            vec![vec![20], vec![10, 21], vec![0, 11], vec![1]]
+        );
+        assert_eq!(
+            values(&tall, tall.anti_diagonals()),
+            vec![vec![0], vec![1, 10], vec![11, 20], vec![21]]
+        );
+
+        //every cell is in exactly one line each way
+        let big = numbered::<5, 4>();
+        for lines in [
+            values(&big, big.diagonals()),
+            values(&big, big.anti_diagonals()),
+        ] {
+            assert_eq!(lines.len(), 5 + 4 - 1);
+            let mut all: Vec<usize> = lines.into_iter().flatten().collect();
+            all.sort_unstable();
+            assert_eq!(all, big.as_slice());
+        }
+    }
+
+    #[test]
+    fn empty_arrays() {
+        let empty: TwoArray<u8, 0, 3> = TwoArray::try_from(vec![]).unwrap();
+        assert_eq!(empty.main_diagonal().count(), 0);
+        assert_eq!(empty.diagonals().count(), 0);
+        assert_eq!(empty.anti_diagonals().count(), 0);
+
+        let empty: TwoArray<u8, 3, 0> = TwoArray::try_from(vec![]).unwrap();
+        assert_eq!(empty.anti_diagonal().count(), 0);
+        assert_eq!(empty.diagonals().count(), 0);
+        assert_eq!(empty.anti_diagonals().count(), 0);
+    }
+}
@@ -0,0 +1,245 @@
+use super::{ArrayCoords, TwoArray};
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+///A [`TwoArray`] which keeps track of which cells have been changed, so that only those need to be sent over the network etc.
+///
+///Reading is done through [`DirtyTwoArray::array`], and all writes have to go through the methods here so they can be tracked.
+pub struct DirtyTwoArray<T, const W: usize, const H: usize> {
+    ///The actual array
+    array: TwoArray<T, W, H>,
+    ///The flat indices of all of the cells that have changed since the last [`DirtyTwoArray::encode_dirty`]
+    dirty: BTreeSet<usize>,
+}
+
+impl<T, const W: usize, const H: usize> From<TwoArray<T, W, H>> for DirtyTwoArray<T, W, H> {
+    ///Wraps the array, with nothing marked as dirty
+    fn from(array: TwoArray<T, W, H>) -> Self {
+        Self {
+            array,
+            dirty: BTreeSet::new(),
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> DirtyTwoArray<T, W, H> {
+    ///Gets the underlying array for reading
+    #[must_use]
+    pub const fn array(&self) -> &TwoArray<T, W, H> {
+        &self.array
+    }
+
+    ///Unwraps the underlying array, forgetting about which cells are dirty
+    #[must_use]
+    #[allow(clippy::missing_const_for_fn)] //destructor issues
+    pub fn into_inner(self) -> TwoArray<T, W, H> {
+        self.array
+    }
+
+    ///Gets a cell mutably, marking it as dirty. Returns [`None`] if `c` is OOB
+    pub fn get_mut(&mut self, c: ArrayCoords<W, H>) -> Option<&mut T> {
+        let index = c.to_usize()?;
+        let el = self.array.backing.get_mut(index)?;
+        self.dirty.insert(index);
+        Some(el)
+    }
+
+    ///Sets a cell, marking it as dirty and returning the old value. Returns [`None`] (and drops `value`) if `c` is OOB
+    pub fn set(&mut self, c: ArrayCoords<W, H>, value: T) -> Option<T> {
+        self.get_mut(c).map(|el| std::mem::replace(el, value))
+    }
+
+    ///Whether or not a cell has changed since the last [`DirtyTwoArray::encode_dirty`]
+    #[must_use]
+    pub fn is_dirty(&self, c: ArrayCoords<W, H>) -> bool {
+        c.to_usize().is_some_and(|i| self.dirty.contains(&i))
+    }
+
+    ///How many cells have changed since the last [`DirtyTwoArray::encode_dirty`]
+    #[must_use]
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+}
+
+impl<T: Clone, const W: usize, const H: usize> DirtyTwoArray<T, W, H> {
+    ///Captures all of the changed cells into a [`DirtyPatch`], and clears the dirty set
+    pub fn encode_dirty(&mut self) -> DirtyPatch<T> {
+        let cells = std::mem::take(&mut self.dirty)
+            .into_iter()
+            .map(|i| (i, self.array.backing[i].clone()))
+            .collect();
+
+        DirtyPatch {
+            width: W,
+            height: H,
+            cells,
+        }
+    }
+}
+
+///A set of changed cells from a [`DirtyTwoArray`], to be applied to another [`TwoArray`] of the same size with [`TwoArray::apply_patch`]
+///
+///With `serde`, this serializes as `(width, height, [(flat index, value)])`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirtyPatch<T> {
+    ///The width of the array this was made from
+    width: usize,
+    ///The height of the array this was made from
+    height: usize,
+    ///The flat indices and new values of the changed cells
+    cells: Vec<(usize, T)>,
+}
+
+impl<T> DirtyPatch<T> {
+    ///Gets the `(width, height)` of the array this was made from
+    #[must_use]
+    pub const fn dims(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    ///Gets the flat indices and new values of the changed cells
+    #[must_use]
+    pub fn cells(&self) -> &[(usize, T)] {
+        &self.cells
+    }
+}
+
+///Error for when a [`DirtyPatch`] can't be applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchError {
+    ///The patch was made for an array with different dimensions
+    WrongDimensions {
+        ///The `(width, height)` of the array being patched
+        expected: (usize, usize),
+        ///The `(width, height)` the patch was made for
+        got: (usize, usize),
+    },
+    ///One of the cells in the patch is outside the array
+    OutOfBounds(usize),
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongDimensions { expected, got } => write!(
+                f,
+                "patch is for a {}x{} grid, but this is a {}x{} grid",
+                got.0, got.1, expected.0, expected.1
+            ),
+            Self::OutOfBounds(i) => write!(f, "patch has a cell at index {i}, which is OOB"),
+        }
+    }
+}
+
+impl Error for PatchError {}
+
+impl<T: Clone, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Applies a [`DirtyPatch`], setting all of the cells in it.
+    ///
+    ///Everything is validated before anything gets changed, so if this fails the array is untouched.
+    ///
+    /// # Errors
+    /// - [`PatchError::WrongDimensions`] if the patch was made for a different size of array
+    /// - [`PatchError::OutOfBounds`] if any of the cells are outside the array
+    pub fn apply_patch(&mut self, patch: &DirtyPatch<T>) -> Result<(), PatchError> {
+        if patch.dims() != (W, H) {
+            return Err(PatchError::WrongDimensions {
+                expected: (W, H),
+                got: patch.dims(),
+            });
+        }
+        if let Some((i, _)) = patch.cells.iter().find(|(i, _)| *i >= W * H) {
+            return Err(PatchError::OutOfBounds(*i));
+        }
+
+        for (i, value) in &patch.cells {
+            self.backing[*i] = value.clone();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+///`serde` implementations for [`DirtyPatch`]
+mod serde_impls {
+    use super::DirtyPatch;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    impl<T: Serialize> Serialize for DirtyPatch<T> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            (self.width, self.height, &self.cells).serialize(serializer)
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for DirtyPatch<T> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let (width, height, cells) = Deserialize::deserialize(deserializer)?;
+            Ok(Self {
+                width,
+                height,
+                cells,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patch_syncs_grids() {
+        let mut server: DirtyTwoArray<u8, 4, 3> = TwoArray::default().into();
+        let mut client: TwoArray<u8, 4, 3> = TwoArray::default();
+
+        server.set(ArrayCoords::InBounds(1, 1), 5);
+        *server.get_mut(ArrayCoords::InBounds(3, 2)).unwrap() = 7;
+        server.set(ArrayCoords::InBounds(1, 1), 6);
+        assert!(server.is_dirty(ArrayCoords::InBounds(1, 1)));
+        assert_eq!(server.dirty_count(), 2);
+
+        let patch = server.encode_dirty();
+        assert_eq!(patch.cells(), &[(5, 6), (11, 7)]);
+        assert_eq!(server.dirty_count(), 0);
+
+        client.apply_patch(&patch).unwrap();
+        assert_eq!(client.as_slice(), server.array().as_slice());
+    }
+
+    #[test]
+    fn patch_for_other_dimensions_is_rejected() {
+        let mut other: DirtyTwoArray<u8, 3, 4> = TwoArray::default().into();
+        other.set(ArrayCoords::InBounds(0, 0), 1);
+        let patch = other.encode_dirty();
+
+        let mut client: TwoArray<u8, 4, 3> = TwoArray::default();
+        assert_eq!(
+            client.apply_patch(&patch),
+            Err(PatchError::WrongDimensions {
+                expected: (4, 3),
+                got: (3, 4)
+            })
+        );
+        assert!(client.as_slice().iter().all(|el| *el == 0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn patch_serializes_compactly() {
+        let mut server: DirtyTwoArray<u8, 2, 2> = TwoArray::default().into();
+        server.set(ArrayCoords::InBounds(1, 0), 9);
+        let patch = server.encode_dirty();
+
+        let json = serde_json::to_string(&patch).unwrap();
+        assert_eq!(json, "[2,2,[[1,9]]]");
+        assert_eq!(
+            serde_json::from_str::<DirtyPatch<u8>>(&json).unwrap(),
+            patch
+        );
+    }
+}
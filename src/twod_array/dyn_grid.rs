@@ -0,0 +1,436 @@
+use super::{FlatGrid, ShapeError, TwoArray};
+use crate::{coords::UnboundedCoord, error_ext::ToNotErr};
+use std::{
+    fmt::{Debug, Display, Formatter},
+    ops::{Index, IndexMut},
+};
+
+///Struct for a 2D grid whose dimensions are only known at runtime, eg. from a loaded level file, backed by a [`Vec`].
+///
+///The backing always has exactly `width * height` elements, in row-major order. Use [`DynGrid::try_into_two_array`] and [`TwoArray::to_dyn`] to go between this and a [`TwoArray`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct DynGrid<T> {
+    ///The width of the grid
+    width: usize,
+    ///The height of the grid
+    height: usize,
+    ///Base of the struct which holds all of the data
+    backing: Vec<T>,
+}
+
+impl<T> FlatGrid<T> for DynGrid<T> {
+    fn flat_dims(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn flat(&self) -> &[T] {
+        &self.backing
+    }
+
+    fn flat_mut(&mut self) -> &mut [T] {
+        &mut self.backing
+    }
+}
+
+impl<T: Clone> DynGrid<T> {
+    ///Instantiates a new `DynGrid`, with all elements being clones of `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            backing: vec![fill; width * height],
+        }
+    }
+
+    ///Sets every element to a clone of `value`
+    pub fn fill(&mut self, value: T) {
+        self.backing.fill(value);
+    }
+}
+
+impl<T> DynGrid<T> {
+    ///Instantiates a new `DynGrid`, with all elements being from the function given. Goes in row-major order.
+    pub fn from_function(
+        width: usize,
+        height: usize,
+        f: impl Fn(UnboundedCoord<usize>) -> T,
+    ) -> Self {
+        let backing = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(UnboundedCoord::from_usize_pair(x, y)))
+            .collect();
+
+        Self {
+            width,
+            height,
+            backing,
+        }
+    }
+
+    ///Creates a `DynGrid` using the [`Vec`] as the backing, in row-major order. Fails if there aren't exactly `width * height` elements.
+    ///
+    /// # Errors
+    /// - [`ShapeError::WrongLength`] if there are the wrong number of elements
+    pub fn try_from_vec(width: usize, height: usize, backing: Vec<T>) -> Result<Self, ShapeError> {
+        if backing.len() == width * height {
+            Ok(Self {
+                width,
+                height,
+                backing,
+            })
+        } else {
+            Err(ShapeError::WrongLength {
+                width,
+                height,
+                got: backing.len(),
+            })
+        }
+    }
+
+    ///Creates a `DynGrid` from a list of rows, taking the width from the first row. Fails if the rows aren't all the same length.
+    ///
+    /// # Errors
+    /// - [`ShapeError::WrongRowLength`] if any of the rows are a different length to the first
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ShapeError> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some((row, r)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(ShapeError::WrongRowLength {
+                row,
+                expected: width,
+                got: r.len(),
+            });
+        }
+
+        Ok(Self {
+            width,
+            height: rows.len(),
+            backing: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    ///Creates a `DynGrid` straight from a backing which is already known to have exactly `width * height` elements, eg. copied out of a view
+    pub(super) fn from_parts(width: usize, height: usize, backing: Vec<T>) -> Self {
+        debug_assert_eq!(backing.len(), width * height);
+        Self {
+            width,
+            height,
+            backing,
+        }
+    }
+
+    ///Gets the width of the grid
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    ///Gets the height of the grid
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    ///Gets the element at a coordinate, or [`None`] if it's OOB or outside the grid
+    #[must_use]
+    pub fn get(&self, c: UnboundedCoord<usize>) -> Option<&T> {
+        let (x, y) = c.to_option()?;
+        self.base_get(x, y)
+    }
+
+    ///Gets the element at a coordinate mutably, or [`None`] if it's OOB or outside the grid
+    pub fn get_mut(&mut self, c: UnboundedCoord<usize>) -> Option<&mut T> {
+        let (x, y) = c.to_option()?;
+        self.base_get_mut(x, y)
+    }
+
+    ///Gets all of the elements in row-major order
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.backing
+    }
+
+    ///Iterates over the rows, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.base_rows()
+    }
+
+    ///Iterates over every element along with its coordinates, in row-major order
+    #[must_use]
+    pub fn iter(&self) -> DynGridIterator<std::slice::Iter<'_, T>> {
+        DynGridIterator::new(self.backing.iter(), self.width)
+    }
+
+    ///Iterates over every element mutably along with its coordinates, in row-major order
+    pub fn iter_mut(&mut self) -> DynGridIterator<std::slice::IterMut<'_, T>> {
+        DynGridIterator::new(self.backing.iter_mut(), self.width)
+    }
+
+    ///Creates a new `DynGrid` by running the function given over every element, keeping the same ordering
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> DynGrid<U> {
+        DynGrid {
+            width: self.width,
+            height: self.height,
+            backing: self.backing.iter().map(f).collect(),
+        }
+    }
+
+    ///Turns this into a [`TwoArray`] with the same elements. Fails if the grid isn't `W`x`H`.
+    ///
+    /// # Errors
+    /// - [`ShapeError::WrongDimensions`] if the grid is the wrong size
+    pub fn try_into_two_array<const W: usize, const H: usize>(
+        self,
+    ) -> Result<TwoArray<T, W, H>, ShapeError> {
+        if (self.width, self.height) == (W, H) {
+            TwoArray::try_from(self.backing)
+        } else {
+            Err(ShapeError::WrongDimensions {
+                expected: (W, H),
+                got: (self.width, self.height),
+            })
+        }
+    }
+}
+
+impl<T: Clone, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Copies this into a [`DynGrid`] with the same dimensions and elements
+    #[must_use]
+    pub fn to_dyn(&self) -> DynGrid<T> {
+        DynGrid {
+            width: W,
+            height: H,
+            backing: self.backing.clone(),
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> From<TwoArray<T, W, H>> for DynGrid<T> {
+    fn from(array: TwoArray<T, W, H>) -> Self {
+        Self {
+            width: W,
+            height: H,
+            backing: array.backing,
+        }
+    }
+}
+
+impl<T: Display> DynGrid<T> {
+    ///Formats the grid with each row on its own line, and each element separated by `sep`. See [`TwoArray::to_grid_string`].
+    #[must_use]
+    pub fn to_grid_string(&self, sep: &str) -> String {
+        self.base_grid_string(sep)
+    }
+}
+
+impl<T: Display> Display for DynGrid<T> {
+    ///Formats using [`DynGrid::to_grid_string`], separated by spaces
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_grid_string(" "))
+    }
+}
+
+impl<T: Debug> Debug for DynGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynGrid")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("backing", &self.backing)
+            .finish()
+    }
+}
+
+impl<T> Index<UnboundedCoord<usize>> for DynGrid<T> {
+    type Output = T;
+
+    fn index(&self, index: UnboundedCoord<usize>) -> &Self::Output {
+        self.get(index)
+            .unwrap_log_error_with_context(|| format!("getting index {index:?}"))
+    }
+}
+impl<T> IndexMut<UnboundedCoord<usize>> for DynGrid<T> {
+    fn index_mut(&mut self, index: UnboundedCoord<usize>) -> &mut Self::Output {
+        self.get_mut(index)
+            .unwrap_log_error_with_context(|| format!("getting index {index:?}"))
+    }
+}
+impl<T> Index<(usize, usize)> for DynGrid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.base_get(x, y)
+            .unwrap_log_error_with_context(|| format!("getting index {:?}", (x, y)))
+    }
+}
+impl<T> IndexMut<(usize, usize)> for DynGrid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.base_get_mut(x, y)
+            .unwrap_log_error_with_context(|| format!("getting index {:?}", (x, y)))
+    }
+}
+
+///Iterator struct for [`DynGrid`], which gives every element (owned, borrowed or mutably borrowed depending on `I`) along with its coordinates in row-major order
+pub struct DynGridIterator<I> {
+    ///The elements which haven't been given out yet
+    backing: I,
+    ///The width of the grid, for working out coordinates
+    width: usize,
+    ///The index of the next element
+    index: usize,
+}
+
+impl<I> DynGridIterator<I> {
+    ///Creates a new iterator starting at the top left of a grid of width `width`
+    const fn new(backing: I, width: usize) -> Self {
+        Self {
+            backing,
+            width,
+            index: 0,
+        }
+    }
+}
+
+impl<I: Iterator> Iterator for DynGridIterator<I> {
+    type Item = (I::Item, UnboundedCoord<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let el = self.backing.next()?;
+        let c = UnboundedCoord::from_usize_pair(self.index % self.width, self.index / self.width);
+        self.index += 1;
+
+        Some((el, c))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.backing.size_hint()
+    }
+}
+
+impl<T> IntoIterator for DynGrid<T> {
+    type Item = (T, UnboundedCoord<usize>);
+    type IntoIter = DynGridIterator<std::vec::IntoIter<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DynGridIterator::new(self.backing.into_iter(), self.width)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DynGrid<T> {
+    type Item = (&'a T, UnboundedCoord<usize>);
+    type IntoIter = DynGridIterator<std::slice::Iter<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut DynGrid<T> {
+    type Item = (&'a mut T, UnboundedCoord<usize>);
+    type IntoIter = DynGridIterator<std::slice::IterMut<'a, T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twod_array::{GridLike, ShapeError};
+
+    #[test]
+    fn construction_and_access() {
+        let mut grid = DynGrid::new(3, 2, 0);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.as_slice(), &[0; 6]);
+
+        grid[(2, 1)] = 5;
+        *grid.get_mut(UnboundedCoord::InBounds(0, 1)).unwrap() = 3;
+        assert_eq!(grid[UnboundedCoord::InBounds(2, 1)], 5);
+        assert_eq!(grid.get(UnboundedCoord::InBounds(3, 0)), None);
+        assert_eq!(grid.get(UnboundedCoord::OutOfBounds), None);
+        assert_eq!(GridLike::get(&grid, 0, 1), Some(&3));
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[0, 0, 0][..], &[3, 0, 5][..]]
+        );
+        assert_eq!(grid.to_string(), "0 0 0\n3 0 5");
+
+        let from_fn = DynGrid::from_function(3, 2, |c| c.to_usize_pair().unwrap());
+        assert_eq!(from_fn[(1, 1)], (1, 1));
+        assert_eq!(from_fn.map(|(x, y)| x + y)[(2, 1)], 3);
+
+        assert_eq!(
+            DynGrid::try_from_vec(2, 2, vec![1, 2, 3]).err(),
+            Some(ShapeError::WrongLength {
+                width: 2,
+                height: 2,
+                got: 3
+            })
+        );
+        assert_eq!(
+            DynGrid::from_rows(vec![vec![1, 2], vec![3]]).err(),
+            Some(ShapeError::WrongRowLength {
+                row: 1,
+                expected: 2,
+                got: 1
+            })
+        );
+        let from_rows = DynGrid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+        assert_eq!((from_rows.width(), from_rows.height()), (2, 3));
+    }
+
+    #[test]
+    fn into_iter_coords() {
+        let grid = DynGrid::from_function(2, 3, |c| c);
+
+        for (el, c) in grid {
+            assert_eq!(el, c);
+        }
+        assert_eq!(DynGrid::new(2, 3, ()).into_iter().count(), 6);
+        assert_eq!(DynGrid::new(0, 3, ()).into_iter().count(), 0);
+    }
+
+    #[test]
+    fn borrowed_iteration() {
+        let mut grid = DynGrid::from_function(3, 2, |c| c.to_usize_pair().unwrap());
+
+        for (el, c) in &grid {
+            assert_eq!(UnboundedCoord::from_usize_pair(el.0, el.1), c);
+        }
+        for (el, c) in &mut grid {
+            if c == UnboundedCoord::InBounds(1, 1) {
+                *el = (9, 9);
+            }
+        }
+        grid.iter_mut().for_each(|(el, _)| el.0 += 1);
+
+        assert_eq!(grid[(1, 1)], (10, 9));
+        assert_eq!(
+            grid.iter().map(|(el, _)| el.0).collect::<Vec<_>>(),
+            vec![1, 2, 3, 1, 10, 3]
+        );
+        assert_eq!(grid.iter().size_hint(), (6, Some(6)));
+    }
+
+    #[test]
+    fn to_and_from_two_array() {
+        let array: TwoArray<usize, 3, 2> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        let grid = array.to_dyn();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.as_slice(), array.as_slice());
+        assert_eq!(grid.to_string(), array.to_string());
+        assert_eq!(DynGrid::from(array), grid);
+
+        assert_eq!(
+            grid.clone().try_into_two_array::<2, 3>().err(),
+            Some(ShapeError::WrongDimensions {
+                expected: (2, 3),
+                got: (3, 2)
+            })
+        );
+        let back = grid.try_into_two_array::<3, 2>().unwrap();
+        assert_eq!(back[(2, 1)], 5);
+    }
+}
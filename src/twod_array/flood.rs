@@ -0,0 +1,193 @@
+use super::{ArrayCoords, GridLike, GridLikeMut, TwoArray};
+
+///Base function for finding the 4-connected region of cells matching `pred` around `(x, y)` in any [`GridLike`].
+///
+///Uses an explicit stack rather than recursion, so big regions can't overflow the call stack. Returns the coordinates of the region.
+fn base_flood<T, G: GridLike<T> + ?Sized>(
+    grid: &G,
+    (sx, sy): (usize, usize),
+    pred: impl Fn(&T) -> bool,
+) -> Vec<(usize, usize)> {
+    if !grid.get(sx, sy).is_some_and(&pred) {
+        return vec![];
+    }
+
+    let (w, h) = grid.dims();
+    let mut seen = vec![false; w * h];
+    seen[sy * w + sx] = true;
+    let mut stack = vec![(sx, sy)];
+    let mut region = vec![];
+
+    while let Some((x, y)) = stack.pop() {
+        region.push((x, y));
+
+        let candidates = [
+            (y > 0).then(|| (x, y - 1)),
+            (x + 1 < w).then(|| (x + 1, y)),
+            (y + 1 < h).then(|| (x, y + 1)),
+            (x > 0).then(|| (x - 1, y)),
+        ];
+
+        for (nx, ny) in candidates.into_iter().flatten() {
+            let index = ny * w + nx;
+            if !seen[index] && grid.get(nx, ny).is_some_and(&pred) {
+                seen[index] = true;
+                stack.push((nx, ny));
+            }
+        }
+    }
+
+    region
+}
+
+///Visits every cell in the 4-connected region of cells matching `pred` around the `(x, y)` coordinate `start` in any [`GridLike`], eg. for region detection.
+///
+///Returns how many cells were visited, which is 0 if `start` is outside the grid or doesn't match. The visiting order is unspecified.
+pub fn flood_visit<T, G: GridLike<T> + ?Sized>(
+    grid: &G,
+    start: (usize, usize),
+    pred: impl Fn(&T) -> bool,
+    mut visit: impl FnMut((usize, usize), &T),
+) -> usize {
+    let region = base_flood(grid, start, pred);
+    for &(x, y) in &region {
+        if let Some(el) = grid.get(x, y) {
+            visit((x, y), el);
+        }
+    }
+    region.len()
+}
+
+///Paint-bucket fill over any [`GridLikeMut`] - replaces the 4-connected region of cells equal to the value at the `(x, y)` coordinate `start` with `new_value`.
+///
+///Returns how many cells were changed, which is 0 if `start` is outside the grid or already has `new_value`.
+pub fn flood_fill<T: PartialEq + Clone, G: GridLikeMut<T> + ?Sized>(
+    grid: &mut G,
+    (x, y): (usize, usize),
+    new_value: T,
+) -> usize {
+    let Some(target) = grid.get(x, y).cloned() else {
+        return 0;
+    };
+    if target == new_value {
+        return 0;
+    }
+
+    let region = base_flood(&*grid, (x, y), |el| el == &target);
+    //the region always has `start` in it, and the last cell can take `new_value` itself rather than a clone
+    let Some((&(lx, ly), rest)) = region.split_last() else {
+        return 0;
+    };
+    for &(x, y) in rest {
+        if let Some(el) = grid.get_mut(x, y) {
+            *el = new_value.clone();
+        }
+    }
+    if let Some(el) = grid.get_mut(lx, ly) {
+        *el = new_value;
+    }
+    region.len()
+}
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Visits every cell in the 4-connected region of cells matching `pred` around `start`, eg. for region detection. See [`flood_visit`] for other grids.
+    ///
+    ///Returns how many cells were visited, which is 0 if `start` is OOB or doesn't match. The visiting order is unspecified.
+    pub fn flood_visit(
+        &self,
+        start: ArrayCoords<W, H>,
+        pred: impl Fn(&T) -> bool,
+        mut visit: impl FnMut(ArrayCoords<W, H>, &T),
+    ) -> usize {
+        let Some(start) = start.to_option() else {
+            return 0;
+        };
+        flood_visit(self, start, pred, |c, el| visit(ArrayCoords::from(c), el))
+    }
+}
+
+impl<T: PartialEq + Clone, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Paint-bucket fill - replaces the 4-connected region of cells equal to the value at `start` with `new_value`. See [`flood_fill`] for other grids.
+    ///
+    ///Returns how many cells were changed, which is 0 if `start` is OOB or already has `new_value`.
+    pub fn flood_fill(&mut self, start: ArrayCoords<W, H>, new_value: T) -> usize {
+        let Some(start) = start.to_option() else {
+            return 0;
+        };
+        flood_fill(self, start, new_value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_connected_region_only() {
+        //. # .
+        //. # .
+        //. . #
+        let mut array: TwoArray<char, 3, 3> = TwoArray {
+            backing: vec!['.', '#', '.', '.', '#', '.', '.', '.', '#'],
+        };
+
+        assert_eq!(array.flood_fill(ArrayCoords::InBounds(0, 0), 'o'), 4);
+        assert_eq!(
+            array.as_slice(),
+            &['o', '#', '.', 'o', '#', '.', 'o', 'o', '#']
+        );
+
+        assert_eq!(array.flood_fill(ArrayCoords::InBounds(0, 0), 'o'), 0);
+        assert_eq!(array.flood_fill(ArrayCoords::OutOfBounds, 'x'), 0);
+        assert_eq!(array.flood_fill(ArrayCoords::InBounds(2, 2), 'x'), 1);
+    }
+
+    #[test]
+    fn visit_finds_regions() {
+        let array: TwoArray<u8, 4, 2> = TwoArray {
+            backing: vec![1, 1, 0, 1, 0, 1, 0, 1],
+        };
+
+        let mut visited = vec![];
+        let count = array.flood_visit(
+            ArrayCoords::InBounds(0, 0),
+            |el| *el == 1,
+            |c, _| visited.push(c.to_usize().unwrap()),
+        );
+        visited.sort_unstable();
+        assert_eq!(count, 3);
+        assert_eq!(visited, vec![0, 1, 5]);
+
+        assert_eq!(
+            array.flood_visit(ArrayCoords::InBounds(2, 0), |el| *el == 1, |_, _| {}),
+            0
+        );
+    }
+
+    #[test]
+    fn fills_inside_a_view_only() {
+        let mut array: TwoArray<u8, 4, 3> = TwoArray::default();
+        let mut view = array.view_mut(ArrayCoords::InBounds(1, 1), 2, 2).unwrap();
+
+        assert_eq!(flood_fill(&mut view, (0, 0), 7), 4);
+        assert_eq!(flood_fill(&mut view, (2, 0), 7), 0);
+        assert_eq!(array.as_slice(), &[0, 0, 0, 0, 0, 7, 7, 0, 0, 7, 7, 0]);
+
+        let mut count = 0;
+        assert_eq!(
+            flood_visit(&array, (0, 0), |el| *el == 0, |_, _| count += 1),
+            8
+        );
+        assert_eq!(count, 8);
+    }
+
+    #[test]
+    fn big_fill_does_not_overflow() {
+        let mut array: TwoArray<u8, 1000, 1000> = TwoArray::default();
+        assert_eq!(
+            array.flood_fill(ArrayCoords::InBounds(500, 500), 1),
+            1_000_000
+        );
+        assert!(array.as_slice().iter().all(|el| *el == 1));
+    }
+}
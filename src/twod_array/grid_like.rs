@@ -0,0 +1,146 @@
+use super::{DynGrid, FlatGrid, TwoArray, TwoArrayView, TwoArrayViewMut};
+
+///Trait for anything which can be treated as a 2D grid, so that algorithms (like [`super::astar`]) only have to be written once.
+///
+///Coordinates are `(x, y)`, with `(0, 0)` at the top left.
+pub trait GridLike<T> {
+    ///Gets the `(width, height)` of the grid
+    fn dims(&self) -> (usize, usize);
+
+    ///Gets the element at `(x, y)`, or [`None`] if that's outside the grid
+    fn get(&self, x: usize, y: usize) -> Option<&T>;
+
+    ///Whether or not `(x, y)` is inside the grid
+    fn contains(&self, x: usize, y: usize) -> bool {
+        let (w, h) = self.dims();
+        x < w && y < h
+    }
+
+    ///Iterates over every coordinate in the grid, in row-major order
+    fn coords(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (w, h) = self.dims();
+        (0..h).flat_map(move |y| (0..w).map(move |x| (x, y)))
+    }
+
+    ///Iterates over every element in the grid along with its coordinates, in row-major order
+    fn iter_grid<'a>(&'a self) -> impl Iterator<Item = ((usize, usize), &'a T)>
+    where
+        T: 'a,
+    {
+        self.coords()
+            .filter_map(|(x, y)| self.get(x, y).map(|el| ((x, y), el)))
+    }
+}
+
+///Trait for a [`GridLike`] which can also be mutated
+pub trait GridLikeMut<T>: GridLike<T> {
+    ///Gets the element at `(x, y)` mutably, or [`None`] if that's outside the grid
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T>;
+}
+
+impl<T, const W: usize, const H: usize> GridLike<T> for TwoArray<T, W, H> {
+    fn dims(&self) -> (usize, usize) {
+        (W, H)
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.base_get(x, y)
+    }
+}
+
+impl<T, const W: usize, const H: usize> GridLikeMut<T> for TwoArray<T, W, H> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.base_get_mut(x, y)
+    }
+}
+
+impl<T> GridLike<T> for DynGrid<T> {
+    fn dims(&self) -> (usize, usize) {
+        self.flat_dims()
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.base_get(x, y)
+    }
+}
+
+impl<T> GridLikeMut<T> for DynGrid<T> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.base_get_mut(x, y)
+    }
+}
+
+impl<T, const W: usize, const H: usize> GridLike<T> for TwoArrayView<'_, T, W, H> {
+    fn dims(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        Self::get(self, (x, y))
+    }
+}
+
+impl<T, const W: usize, const H: usize> GridLike<T> for TwoArrayViewMut<'_, T, W, H> {
+    fn dims(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    fn get(&self, x: usize, y: usize) -> Option<&T> {
+        Self::get(self, (x, y))
+    }
+}
+
+impl<T, const W: usize, const H: usize> GridLikeMut<T> for TwoArrayViewMut<'_, T, W, H> {
+    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        Self::get_mut(self, (x, y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twod_array::{astar, flood_fill, ArrayCoords};
+
+    ///Runs A* and then a flood fill over any grid, so the exact same code can be checked against different grid types. `#` is a wall.
+    fn path_then_fill<G: GridLikeMut<char>>(grid: &mut G) -> (Option<u32>, usize) {
+        let total = astar(&*grid, (0, 0), (4, 0), |c| (*c != '#').then_some(1)).map(|(_, t)| t);
+        let filled = flood_fill(grid, (0, 0), 'o');
+        (total, filled)
+    }
+
+    #[test]
+    fn grid_like_over_array_and_view() {
+        let mut array: TwoArray<usize, 4, 3> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        assert_eq!(GridLike::dims(&array), (4, 3));
+        assert_eq!(GridLike::get(&array, 3, 2), Some(&11));
+        assert_eq!(GridLike::get(&array, 4, 0), None);
+        assert_eq!(array.iter_grid().count(), 12);
+
+        *GridLikeMut::get_mut(&mut array, 0, 0).unwrap() = 100;
+
+        let view = array.view(ArrayCoords::InBounds(0, 0), 2, 2).unwrap();
+        assert_eq!(GridLike::dims(&view), (2, 2));
+        assert_eq!(GridLike::get(&view, 0, 0), Some(&100));
+        assert_eq!(
+            view.iter_grid().map(|(_, el)| *el).collect::<Vec<_>>(),
+            vec![100, 1, 4, 5]
+        );
+    }
+
+    #[test]
+    fn same_algorithms_over_array_and_dyn_grid() {
+        //. . # . .
+        //. # # . .
+        //. . . . #
+        let rows = ["..#..", ".##..", "....#"];
+        let mut grid =
+            DynGrid::from_rows(rows.iter().map(|r| r.chars().collect()).collect()).unwrap();
+        let mut array = grid.clone().try_into_two_array::<5, 3>().unwrap();
+
+        assert_eq!(path_then_fill(&mut grid), (Some(8), 11));
+        assert_eq!(path_then_fill(&mut array), (Some(8), 11));
+        assert_eq!(grid.as_slice(), array.as_slice());
+        assert_eq!(grid.to_string(), "o o # o o\no # # o o\no o o o #");
+    }
+}
@@ -0,0 +1,685 @@
+//! A module to hold a 2D grid array
+
+use crate::{coords::Coords, error_ext::ToNotErr};
+use std::{
+    error::Error,
+    fmt::{Debug, Display, Formatter, Write},
+    ops::{Index, IndexMut},
+};
+
+///Convolution-style iteration over the neighbourhood of every element of a [`TwoArray`]
+mod convolve;
+pub use convolve::*;
+///Iterating along the diagonals of a [`TwoArray`]
+mod diagonals;
+///A [`TwoArray`] wrapper which tracks changed cells, and patches to sync them
+mod dirty;
+pub use dirty::*;
+///A grid like [`TwoArray`], but with dimensions only known at runtime
+mod dyn_grid;
+pub use dyn_grid::*;
+///Flood fills over any [`GridLike`]
+mod flood;
+pub use flood::*;
+///A trait for treating different grid types the same
+mod grid_like;
+pub use grid_like::*;
+///Neighbour access on a [`TwoArray`]
+mod neighbours;
+///A* pathfinding over any [`GridLike`]
+mod pathfinding;
+pub use pathfinding::*;
+///Cropping, pasting and padding a [`TwoArray`] into different dimensions
+mod resize;
+///Swapping and shifting whole rows and columns of a [`TwoArray`]
+mod rows;
+///Searching through a [`TwoArray`]
+mod search;
+#[cfg(feature = "serde")]
+///`serde` implementations for [`TwoArray`]
+mod serde_impls;
+#[cfg(test)]
+///Arrays shared between the tests of the submodules
+mod test_fixtures;
+///Borrowed views into rectangular windows of a [`TwoArray`]
+mod view;
+pub use view::*;
+
+///Offsets for moving around a grid - the first 4 are orthogonal, and the last 4 are diagonal
+const OFFSETS: [(isize, isize); 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+];
+
+///Internal trait over a flat row-major backing along with its dimensions, so that [`TwoArray`] and [`DynGrid`] can share their implementations
+trait FlatGrid<T> {
+    ///Gets the `(width, height)` of the grid
+    fn flat_dims(&self) -> (usize, usize);
+
+    ///Gets the backing, in row-major order
+    fn flat(&self) -> &[T];
+
+    ///Gets the backing mutably, in row-major order
+    fn flat_mut(&mut self) -> &mut [T];
+
+    ///Base function for turning `(x, y)` into an index into the backing, or [`None`] if that's outside the grid
+    fn base_index(&self, x: usize, y: usize) -> Option<usize> {
+        let (w, h) = self.flat_dims();
+        (x < w && y < h).then(|| y * w + x)
+    }
+
+    ///Base function for getting the element at `(x, y)`
+    fn base_get(&self, x: usize, y: usize) -> Option<&T> {
+        self.base_index(x, y).and_then(|i| self.flat().get(i))
+    }
+
+    ///Base function for getting the element at `(x, y)` mutably
+    fn base_get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.base_index(x, y)
+            .and_then(|i| self.flat_mut().get_mut(i))
+    }
+
+    ///Base function for iterating over the rows, from top to bottom
+    fn base_rows(&self) -> std::slice::Chunks<'_, T> {
+        self.flat().chunks(self.flat_dims().0.max(1))
+    }
+
+    ///Base function for formatting the grid with each row on its own line, and each element separated by `sep`
+    fn base_grid_string(&self, sep: &str) -> String
+    where
+        T: Display,
+    {
+        let mut s = String::new();
+
+        for (i, row) in self.base_rows().enumerate() {
+            if i > 0 {
+                s.push('\n');
+            }
+            for (j, el) in row.iter().enumerate() {
+                if j > 0 {
+                    s.push_str(sep);
+                }
+                let _ = write!(s, "{el}");
+            }
+        }
+
+        s
+    }
+}
+
+impl<T, const W: usize, const H: usize> FlatGrid<T> for TwoArray<T, W, H> {
+    fn flat_dims(&self) -> (usize, usize) {
+        (W, H)
+    }
+
+    fn flat(&self) -> &[T] {
+        &self.backing
+    }
+
+    fn flat_mut(&mut self) -> &mut [T] {
+        &mut self.backing
+    }
+}
+
+///Type alias for Usize coordinates used for Array indexing
+pub type ArrayCoords<const W: usize, const H: usize> = Coords<usize, W, H>;
+
+///Struct for a 2D Array, backed by a [`Vec`]
+///
+///The backing always has exactly `W * H` elements, in row-major order.
+pub struct TwoArray<T, const W: usize, const H: usize> {
+    ///Base of the struct which holds all of the data
+    backing: Vec<T>,
+}
+
+///Error for when data of the wrong shape is used to make a [`TwoArray`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeError {
+    ///There were the wrong number of elements in total
+    WrongLength {
+        ///The width of the array
+        width: usize,
+        ///The height of the array
+        height: usize,
+        ///How many elements there actually were
+        got: usize,
+    },
+    ///There were the wrong number of rows
+    WrongRowCount {
+        ///How many rows there should have been
+        expected: usize,
+        ///How many rows there actually were
+        got: usize,
+    },
+    ///One of the rows had the wrong number of elements
+    WrongRowLength {
+        ///The index of the row
+        row: usize,
+        ///How many elements there should have been
+        expected: usize,
+        ///How many elements there actually were
+        got: usize,
+    },
+    ///The grid was a different size to the one wanted, eg. converting a [`DynGrid`] into a [`TwoArray`]
+    WrongDimensions {
+        ///The `(width, height)` wanted
+        expected: (usize, usize),
+        ///The `(width, height)` the grid actually had
+        got: (usize, usize),
+    },
+}
+
+impl Display for ShapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongLength { width, height, got } => write!(
+                f,
+                "expected {} elements for {width}x{height} grid, got {got}",
+                width * height
+            ),
+            Self::WrongRowCount { expected, got } => {
+                write!(f, "expected {expected} rows, got {got}")
+            }
+            Self::WrongRowLength { row, expected, got } => {
+                write!(f, "expected {expected} elements in row {row}, got {got}")
+            }
+            Self::WrongDimensions {
+                expected: (ew, eh),
+                got: (gw, gh),
+            } => write!(f, "expected a {ew}x{eh} grid, got {gw}x{gh}"),
+        }
+    }
+}
+
+impl Error for ShapeError {}
+
+impl<T: Default, const W: usize, const H: usize> Default for TwoArray<T, W, H> {
+    ///Creates a `TwoArray` where every element is `T::default()`
+    fn default() -> Self {
+        Self::from_function(|_| T::default())
+    }
+}
+
+impl<T, const W: usize, const H: usize> TryFrom<Vec<T>> for TwoArray<T, W, H> {
+    type Error = ShapeError;
+
+    ///Uses the [`Vec`] as the backing, in row-major order. Fails if there aren't exactly `W * H` elements.
+    fn try_from(backing: Vec<T>) -> Result<Self, Self::Error> {
+        if backing.len() == W * H {
+            Ok(Self { backing })
+        } else {
+            Err(ShapeError::WrongLength {
+                width: W,
+                height: H,
+                got: backing.len(),
+            })
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Creates a `TwoArray` from an iterator in row-major order. Fails if there aren't exactly `W * H` elements.
+    ///
+    /// # Errors
+    /// - [`ShapeError::WrongLength`] if there are the wrong number of elements
+    pub fn try_from_iter(iter: impl IntoIterator<Item = T>) -> Result<Self, ShapeError> {
+        Self::try_from(iter.into_iter().collect::<Vec<_>>())
+    }
+
+    ///Creates a `TwoArray` from a list of rows. Fails if there aren't `H` rows, each with `W` elements.
+    ///
+    /// # Errors
+    /// - [`ShapeError::WrongRowCount`] if there are the wrong number of rows
+    /// - [`ShapeError::WrongRowLength`] if any of the rows are the wrong length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, ShapeError> {
+        if rows.len() != H {
+            return Err(ShapeError::WrongRowCount {
+                expected: H,
+                got: rows.len(),
+            });
+        }
+        if let Some((row, r)) = rows.iter().enumerate().find(|(_, r)| r.len() != W) {
+            return Err(ShapeError::WrongRowLength {
+                row,
+                expected: W,
+                got: r.len(),
+            });
+        }
+
+        Ok(Self {
+            backing: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    ///Gets all of the elements in row-major order
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.backing
+    }
+
+    ///Iterates over the rows, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.base_rows()
+    }
+}
+impl<T: Clone, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Instantiates a new `TwoArray`, with all elements being the default given
+    ///
+    ///If you want to just use the default T value, then consider using the [`Default`] trait implementation
+    pub fn from_one_clone(default: T) -> Self {
+        Self {
+            backing: vec![default; W * H],
+        }
+    }
+
+    ///Sets every element to a clone of `value`
+    pub fn fill(&mut self, value: T) {
+        self.backing.fill(value);
+    }
+
+    ///Sets every element in a rectangular region to a clone of `value`.
+    ///
+    ///Returns `false` (and doesn't change anything) if the region doesn't fit inside the array - see [`TwoArray::view_mut`]
+    pub fn fill_region(
+        &mut self,
+        top_left: ArrayCoords<W, H>,
+        width: usize,
+        height: usize,
+        value: &T,
+    ) -> bool {
+        self.view_mut(top_left, width, height)
+            .map(|mut view| view.fill(value))
+            .is_some()
+    }
+}
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Instantiates a new `TwoArray`, with all elements being from the function given
+    pub fn from_function<F: Fn(ArrayCoords<W, H>) -> T>(f: F) -> Self {
+        let mut backing = Vec::with_capacity(W * H);
+        let mut index = ArrayCoords::default();
+
+        backing.push(f(index));
+        while index.increment() {
+            backing.push(f(index));
+        }
+
+        Self { backing }
+    }
+
+    ///Creates a new `TwoArray` by running the function given over every element, keeping the same ordering
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> TwoArray<U, W, H> {
+        TwoArray {
+            backing: self.backing.iter().map(f).collect(),
+        }
+    }
+
+    ///Creates a new `TwoArray` by running the function given over every element along with its coordinates, keeping the same ordering
+    pub fn map_with_coords<U>(&self, f: impl Fn(ArrayCoords<W, H>, &T) -> U) -> TwoArray<U, W, H> {
+        TwoArray::from_function(|c| f(c, &self[c]))
+    }
+
+    ///Runs the function given over every element in place, along with its coordinates. Goes in row-major order.
+    pub fn for_each_mut(&mut self, mut f: impl FnMut(ArrayCoords<W, H>, &mut T)) {
+        let mut index = ArrayCoords::default();
+
+        for el in &mut self.backing {
+            f(index, el);
+            index.increment();
+        }
+    }
+
+    ///Swaps the elements at two coordinates.
+    ///
+    ///Returns `false` (and doesn't swap) if either coordinate is OOB
+    pub fn swap(&mut self, a: ArrayCoords<W, H>, b: ArrayCoords<W, H>) -> bool {
+        match (a.to_usize(), b.to_usize()) {
+            (Some(a), Some(b)) if a < self.backing.len() && b < self.backing.len() => {
+                self.backing.swap(a, b);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    ///Replaces the element at a coordinate, returning the old one.
+    ///
+    ///Returns [`None`] (and drops `value`) if the coordinate is OOB
+    pub fn replace(&mut self, c: ArrayCoords<W, H>, value: T) -> Option<T> {
+        let el = self.backing.get_mut(c.to_usize()?)?;
+        Some(std::mem::replace(el, value))
+    }
+
+    ///Moves every element into a new `TwoArray` with different dimensions. `old_index` gets given the new `(x, y)` and should return the index into the old backing to take from.
+    fn rearrange<const NW: usize, const NH: usize>(
+        self,
+        old_index: impl Fn(usize, usize) -> usize,
+    ) -> TwoArray<T, NW, NH> {
+        let mut old: Vec<Option<T>> = self.backing.into_iter().map(Some).collect();
+        let mut backing = Vec::with_capacity(NW * NH);
+
+        for y in 0..NH {
+            for x in 0..NW {
+                backing.push(
+                    old[old_index(x, y)]
+                        .take()
+                        .unwrap_log_error_with_context(|| format!("rearranging to ({x}, {y})")),
+                );
+            }
+        }
+
+        TwoArray { backing }
+    }
+
+    ///Transposes the array, so that the rows become the columns. Note that the width and height swap.
+    #[must_use]
+    pub fn transpose(self) -> TwoArray<T, H, W> {
+        self.rearrange(|x, y| x * W + y)
+    }
+
+    ///Rotates the array 90° clockwise. Note that the width and height swap.
+    #[must_use]
+    pub fn rotate_cw(self) -> TwoArray<T, H, W> {
+        self.rearrange(|x, y| (H - 1 - x) * W + y)
+    }
+
+    ///Rotates the array 90° anti-clockwise. Note that the width and height swap.
+    #[must_use]
+    pub fn rotate_ccw(self) -> TwoArray<T, H, W> {
+        self.rearrange(|x, y| x * W + (W - 1 - y))
+    }
+
+    ///Rotates the array 180°
+    #[must_use]
+    pub fn rotate_180(mut self) -> Self {
+        self.backing.reverse();
+        self
+    }
+}
+
+impl<T: Display, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Formats the array as a grid, with each row on its own line, and each element separated by `sep`.
+    #[must_use]
+    pub fn to_grid_string(&self, sep: &str) -> String {
+        self.base_grid_string(sep)
+    }
+}
+
+impl<T: Display, const W: usize, const H: usize> Display for TwoArray<T, W, H> {
+    ///Formats using [`TwoArray::to_grid_string`], separated by spaces
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_grid_string(" "))
+    }
+}
+
+impl<T: Debug, const W: usize, const H: usize> Debug for TwoArray<T, W, H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TwoArray")
+            .field("width", &W)
+            .field("height", &H)
+            .field("backing", &self.backing)
+            .finish()
+    }
+}
+
+impl<T, const W: usize, const H: usize> Index<ArrayCoords<W, H>> for TwoArray<T, W, H> {
+    type Output = T;
+
+    fn index(&self, index: ArrayCoords<W, H>) -> &Self::Output {
+        &self.backing[index
+            .to_usize()
+            .unwrap_log_error_with_context(|| format!("getting index {index:?}"))]
+    }
+}
+impl<T, const W: usize, const H: usize> IndexMut<ArrayCoords<W, H>> for TwoArray<T, W, H> {
+    fn index_mut(&mut self, index: ArrayCoords<W, H>) -> &mut Self::Output {
+        &mut self.backing[index
+            .to_usize()
+            .unwrap_log_error_with_context(|| format!("getting index {index:?}"))]
+    }
+}
+impl<T, const W: usize, const H: usize> Index<(usize, usize)> for TwoArray<T, W, H> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.backing[ArrayCoords::<W, H>::from(index)
+            .to_usize()
+            .unwrap_log_error_with_context(|| format!("getting index {index:?}"))]
+    }
+}
+impl<T, const W: usize, const H: usize> IndexMut<(usize, usize)> for TwoArray<T, W, H> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.backing[ArrayCoords::<W, H>::from(index)
+            .to_usize()
+            .unwrap_log_error_with_context(|| format!("getting index {index:?}"))]
+    }
+}
+
+///Iterator struct for [`TwoArray`]
+pub struct TwoArrayIterator<T: Clone, const W: usize, const H: usize> {
+    ///Base of the struct which holds all of the data
+    backing: Vec<T>,
+    ///The current position we're going over
+    current_position: ArrayCoords<W, H>,
+}
+
+impl<T: Clone, const W: usize, const H: usize> Iterator for TwoArrayIterator<T, W, H> {
+    type Item = (T, ArrayCoords<W, H>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current_position.is_oob() {
+            return None;
+        }
+
+        let item = (
+            self.backing[self.current_position.to_usize().unwrap_or_default()].clone(),
+            self.current_position,
+        );
+        self.current_position.increment();
+
+        Some(item)
+    }
+}
+
+impl<T: Clone, const W: usize, const H: usize> From<TwoArrayIterator<T, W, H>>
+    for TwoArray<T, W, H>
+{
+    fn from(i: TwoArrayIterator<T, W, H>) -> Self {
+        Self { backing: i.backing }
+    }
+}
+impl<T: Clone, const W: usize, const H: usize> From<TwoArray<T, W, H>>
+    for TwoArrayIterator<T, W, H>
+{
+    fn from(i: TwoArray<T, W, H>) -> Self {
+        Self {
+            backing: i.backing,
+            current_position: ArrayCoords::InBounds(0, 0),
+        }
+    }
+}
+
+impl<T: Clone, const W: usize, const H: usize> IntoIterator for TwoArray<T, W, H> {
+    type Item = (T, ArrayCoords<W, H>);
+    type IntoIter = TwoArrayIterator<T, W, H>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order_from_fn_test() {
+        let array: TwoArray<ArrayCoords<3, 3>, 3, 3> = TwoArray::from_function(|c| c);
+
+        let ac = ArrayCoords::InBounds;
+        assert_eq!(
+            array.backing,
+            vec![
+                ac(0, 0),
+                ac(1, 0),
+                ac(2, 0),
+                ac(0, 1),
+                ac(1, 1),
+                ac(2, 1),
+                ac(0, 2),
+                ac(1, 2),
+                ac(2, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn map_test() {
+        let array: TwoArray<ArrayCoords<3, 2>, 3, 2> = TwoArray::from_function(|c| c);
+
+        let mapped = array.map(|c| c.to_usize().unwrap());
+        assert_eq!(mapped.backing, (0..6).collect::<Vec<_>>());
+
+        let mapped_with_coords = array.map_with_coords(|c, el| {
+            assert_eq!(&c, el);
+            c.to_usize().unwrap()
+        });
+        assert_eq!(mapped_with_coords.backing, mapped.backing);
+
+        let mut array = array;
+        array.for_each_mut(|c, el| {
+            assert_eq!(&c, el);
+            *el = ArrayCoords::OutOfBounds;
+        });
+        assert!(array.backing.iter().all(Coords::is_oob));
+    }
+
+    #[test]
+    fn rotation_test() {
+        //a b
+        //c d
+        //e f
+        let array = || TwoArray::<char, 2, 3> {
+            backing: vec!['a', 'b', 'c', 'd', 'e', 'f'],
+        };
+
+        let transposed = array().transpose();
+        assert_eq!(transposed.backing, vec!['a', 'c', 'e', 'b', 'd', 'f']);
+        assert_eq!(transposed.transpose().backing, array().backing);
+
+        let cw = array().rotate_cw();
+        assert_eq!(cw.backing, vec!['e', 'c', 'a', 'f', 'd', 'b']);
+        assert_eq!(cw[(0, 0)], 'e');
+        assert_eq!(cw[(2, 1)], 'b');
+
+        let ccw = array().rotate_ccw();
+        assert_eq!(ccw.backing, vec!['b', 'd', 'f', 'a', 'c', 'e']);
+        assert_eq!(ccw.rotate_cw().backing, array().backing);
+
+        assert_eq!(
+            array().rotate_180().backing,
+            vec!['f', 'e', 'd', 'c', 'b', 'a']
+        );
+    }
+
+    #[test]
+    fn display_test() {
+        let array: TwoArray<usize, 3, 2> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        assert_eq!(array.to_string(), "0 1 2\n3 4 5");
+        assert_eq!(array.to_grid_string(", "), "0, 1, 2\n3, 4, 5");
+        assert_eq!(
+            format!("{array:?}"),
+            "TwoArray { width: 3, height: 2, backing: [0, 1, 2, 3, 4, 5] }"
+        );
+
+        let column: TwoArray<usize, 1, 3> = TwoArray::from_function(|c| c.to_usize().unwrap());
+        assert_eq!(column.to_grid_string(", "), "0\n1\n2");
+    }
+
+    #[test]
+    fn mutation_helpers_test() {
+        let mut array: TwoArray<usize, 3, 2> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        assert!(array.swap(ArrayCoords::InBounds(0, 0), ArrayCoords::InBounds(2, 1)));
+        assert!(!array.swap(ArrayCoords::InBounds(0, 0), ArrayCoords::OutOfBounds));
+        assert_eq!(array.backing, vec![5, 1, 2, 3, 4, 0]);
+
+        assert_eq!(array.replace(ArrayCoords::InBounds(1, 0), 10), Some(1));
+        assert_eq!(array.replace(ArrayCoords::OutOfBounds, 10), None);
+        assert_eq!(array[(1, 0)], 10);
+
+        assert!(array.fill_region(ArrayCoords::InBounds(1, 0), 2, 2, &7));
+        assert!(!array.fill_region(ArrayCoords::InBounds(2, 0), 2, 1, &8));
+        assert_eq!(array.backing, vec![5, 7, 7, 3, 7, 7]);
+
+        array.fill(1);
+        assert_eq!(array.backing, vec![1; 6]);
+    }
+
+    #[test]
+    fn construction_is_validated() {
+        let array = TwoArray::<_, 3, 2>::try_from(vec![0, 1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(array.as_slice(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(
+            TwoArray::<_, 3, 2>::try_from(vec![0, 1]).err(),
+            Some(ShapeError::WrongLength {
+                width: 3,
+                height: 2,
+                got: 2
+            })
+        );
+
+        assert_eq!(
+            TwoArray::<_, 3, 2>::try_from_iter(0..6).unwrap().as_slice(),
+            array.as_slice()
+        );
+        assert!(TwoArray::<_, 3, 2>::try_from_iter(0..7).is_err());
+
+        assert_eq!(
+            TwoArray::<_, 3, 2>::from_rows(vec![vec![0, 1, 2], vec![3, 4, 5]])
+                .unwrap()
+                .as_slice(),
+            array.as_slice()
+        );
+        assert_eq!(
+            TwoArray::<_, 3, 2>::from_rows(vec![vec![0, 1, 2]]).err(),
+            Some(ShapeError::WrongRowCount {
+                expected: 2,
+                got: 1
+            })
+        );
+        assert_eq!(
+            TwoArray::<_, 3, 2>::from_rows(vec![vec![0, 1, 2], vec![3, 4]])
+                .err()
+                .map(|e| e.to_string()),
+            Some("expected 3 elements in row 1, got 2".to_string())
+        );
+
+        assert_eq!(TwoArray::<u8, 2, 2>::default().as_slice(), &[0; 4]);
+    }
+
+    #[test]
+    fn index_iter_test() {
+        let get_index = |cs| match cs {
+            Coords::OutOfBounds => 0,
+            Coords::InBounds(x, y) => x * 3 + y,
+        };
+
+        let array = TwoArray::from_function(get_index);
+        let mut index = ArrayCoords::<3, 3>::default();
+
+        for (el, ind) in array {
+            assert_eq!(ind, index);
+            assert_eq!(el, get_index(index));
+
+            index.increment();
+        }
+        assert!(index.is_oob());
+    }
+}
@@ -0,0 +1,106 @@
+use super::{ArrayCoords, TwoArray, OFFSETS};
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Base function for iterating over the first `n` of [`OFFSETS`] around a coordinate
+    fn base_neighbours(
+        &self,
+        c: ArrayCoords<W, H>,
+        n: usize,
+    ) -> impl Iterator<Item = (ArrayCoords<W, H>, &T)> {
+        let centre = c.to_option();
+
+        OFFSETS[..n].iter().filter_map(move |&(ox, oy)| {
+            let (x, y) = centre?;
+            let nc =
+                ArrayCoords::<W, H>::from((x.checked_add_signed(ox)?, y.checked_add_signed(oy)?));
+            let index = nc.to_usize()?;
+
+            self.backing.get(index).map(|el| (nc, el))
+        })
+    }
+
+    ///Iterates over the 4 orthogonal neighbours of a coordinate, along with their values.
+    ///
+    ///Skips positions off the edge of the grid, and is empty if `c` is OOB
+    pub fn neighbours(
+        &self,
+        c: ArrayCoords<W, H>,
+    ) -> impl Iterator<Item = (ArrayCoords<W, H>, &T)> {
+        self.base_neighbours(c, 4)
+    }
+
+    ///Iterates over the 8 neighbours (including diagonals) of a coordinate, along with their values.
+    ///
+    ///Skips positions off the edge of the grid, and is empty if `c` is OOB
+    pub fn neighbours_8(
+        &self,
+        c: ArrayCoords<W, H>,
+    ) -> impl Iterator<Item = (ArrayCoords<W, H>, &T)> {
+        self.base_neighbours(c, 8)
+    }
+
+    ///Counts how many of the 4 orthogonal neighbours of a coordinate match the predicate
+    pub fn count_neighbours_matching(
+        &self,
+        c: ArrayCoords<W, H>,
+        pred: impl Fn(&T) -> bool,
+    ) -> usize {
+        self.neighbours(c).filter(|(_, el)| pred(el)).count()
+    }
+
+    ///Counts how many of the 8 neighbours (including diagonals) of a coordinate match the predicate - eg. for Game of Life
+    pub fn count_neighbours_8_matching(
+        &self,
+        c: ArrayCoords<W, H>,
+        pred: impl Fn(&T) -> bool,
+    ) -> usize {
+        self.neighbours_8(c).filter(|(_, el)| pred(el)).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges_and_corners() {
+        let array: TwoArray<usize, 3, 3> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        assert_eq!(array.neighbours(ArrayCoords::InBounds(1, 1)).count(), 4);
+        assert_eq!(array.neighbours_8(ArrayCoords::InBounds(1, 1)).count(), 8);
+
+        let mut corner = array
+            .neighbours_8(ArrayCoords::InBounds(0, 0))
+            .map(|(_, el)| *el)
+            .collect::<Vec<_>>();
+        corner.sort_unstable();
+        assert_eq!(corner, vec![1, 3, 4]);
+
+        assert_eq!(array.neighbours(ArrayCoords::InBounds(2, 1)).count(), 3);
+        assert_eq!(array.neighbours_8(ArrayCoords::InBounds(2, 1)).count(), 5);
+        assert_eq!(array.neighbours_8(ArrayCoords::OutOfBounds).count(), 0);
+    }
+
+    #[test]
+    fn counting() {
+        //# . #
+        //. . .
+        //# # .
+        let array: TwoArray<bool, 3, 3> = TwoArray {
+            backing: vec![true, false, true, false, false, false, true, true, false],
+        };
+
+        assert_eq!(
+            array.count_neighbours_8_matching(ArrayCoords::InBounds(1, 1), |b| *b),
+            4
+        );
+        assert_eq!(
+            array.count_neighbours_matching(ArrayCoords::InBounds(1, 1), |b| *b),
+            1
+        );
+        assert_eq!(
+            array.count_neighbours_matching(ArrayCoords::InBounds(0, 1), |b| *b),
+            2
+        );
+    }
+}
@@ -0,0 +1,443 @@
+use super::{ArrayCoords, GridLike, TwoArray, OFFSETS};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
+///Gets the cost of a diagonal step into a cell, from the cost of entering it orthogonally. Approximates `cost * √2`, rounding up.
+const fn diagonal_cost(cost: u32) -> u32 {
+    cost.saturating_mul(1414).div_ceil(1000)
+}
+
+///Finds the cheapest path between two `(x, y)` coordinates in any [`GridLike`], only moving orthogonally, using A* with the manhattan distance as the heuristic.
+///
+///`cost` gives the cost of moving *into* a cell, with [`None`] meaning that the cell is impassable. The heuristic assumes that every passable cell costs at least `1`.
+///
+///Returns the path (including both ends) along with the total cost, or [`None`] if there is no path or either end is outside the grid.
+pub fn astar<T, G: GridLike<T> + ?Sized>(
+    grid: &G,
+    from: (usize, usize),
+    to: (usize, usize),
+    cost: impl Fn(&T) -> Option<u32>,
+) -> Option<(Vec<(usize, usize)>, u32)> {
+    base_astar(grid, from, to, false, cost)
+}
+
+///Same as [`astar`], but can also move diagonally, using the octile distance as the heuristic.
+///
+///A diagonal step costs the cost of the cell multiplied by √2, rounded up.
+pub fn astar_diagonal<T, G: GridLike<T> + ?Sized>(
+    grid: &G,
+    from: (usize, usize),
+    to: (usize, usize),
+    cost: impl Fn(&T) -> Option<u32>,
+) -> Option<(Vec<(usize, usize)>, u32)> {
+    base_astar(grid, from, to, true, cost)
+}
+
+///Base function for A* with or without diagonals
+fn base_astar<T, G: GridLike<T> + ?Sized>(
+    grid: &G,
+    (fx, fy): (usize, usize),
+    (tx, ty): (usize, usize),
+    diagonal: bool,
+    cost: impl Fn(&T) -> Option<u32>,
+) -> Option<(Vec<(usize, usize)>, u32)> {
+    if !grid.contains(fx, fy) || !grid.contains(tx, ty) {
+        return None;
+    }
+    let (w, h) = grid.dims();
+
+    let heuristic = |x: usize, y: usize| -> u32 {
+        let dx = u32::try_from(x.abs_diff(tx)).unwrap_or(u32::MAX);
+        let dy = u32::try_from(y.abs_diff(ty)).unwrap_or(u32::MAX);
+
+        if diagonal {
+            //octile distance, rounded down so it stays admissible
+            let (min, max) = (dx.min(dy), dx.max(dy));
+            (max - min).saturating_add(min.saturating_mul(1414) / 1000)
+        } else {
+            dx.saturating_add(dy)
+        }
+    };
+
+    let mut best = vec![u32::MAX; w * h];
+    let mut came_from: Vec<Option<usize>> = vec![None; w * h];
+    let mut frontier = BinaryHeap::new();
+
+    best[fy * w + fx] = 0;
+    frontier.push(Reverse((heuristic(fx, fy), 0, fx, fy)));
+
+    while let Some(Reverse((_, so_far, x, y))) = frontier.pop() {
+        let index = y * w + x;
+        if so_far > best[index] {
+            continue; //stale entry
+        }
+
+        if (x, y) == (tx, ty) {
+            let mut path = vec![(x, y)];
+            let mut current = index;
+            while let Some(previous) = came_from[current] {
+                path.push((previous % w, previous / w));
+                current = previous;
+            }
+            path.reverse();
+
+            return Some((path, so_far));
+        }
+
+        let offsets = if diagonal {
+            &OFFSETS[..]
+        } else {
+            &OFFSETS[..4]
+        };
+        for &(ox, oy) in offsets {
+            let (Some(nx), Some(ny)) = (x.checked_add_signed(ox), y.checked_add_signed(oy)) else {
+                continue;
+            };
+            let Some(step) = grid.get(nx, ny).and_then(&cost) else {
+                continue;
+            };
+            let step = if ox != 0 && oy != 0 {
+                diagonal_cost(step)
+            } else {
+                step
+            };
+
+            let n_index = ny * w + nx;
+            let new_cost = so_far.saturating_add(step);
+            if new_cost < best[n_index] {
+                best[n_index] = new_cost;
+                came_from[n_index] = Some(index);
+                frontier.push(Reverse((
+                    new_cost.saturating_add(heuristic(nx, ny)),
+                    new_cost,
+                    nx,
+                    ny,
+                )));
+            }
+        }
+    }
+
+    None
+}
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Base function for a breadth-first search over the 4-connected passable cells from `start`, stopping early once `goal` is reached.
+    ///
+    ///Uses an explicit queue and a visited list sized `W * H`, so it's `O(cells)` and can't overflow the call stack. Returns the distance to and the previous cell of every cell reached, by flat index.
+    fn base_bfs(
+        &self,
+        start: usize,
+        goal: Option<usize>,
+        passable: impl Fn(&T) -> bool,
+    ) -> (Vec<Option<u32>>, Vec<Option<usize>>) {
+        let mut distances = vec![None; W * H];
+        let mut came_from = vec![None; W * H];
+        if !self.backing.get(start).is_some_and(&passable) {
+            return (distances, came_from);
+        }
+
+        let mut visited = vec![false; W * H];
+        visited[start] = true;
+        distances[start] = Some(0);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(index) = queue.pop_front() {
+            if Some(index) == goal {
+                break;
+            }
+
+            let so_far = distances[index].unwrap_or_default();
+            let (x, y) = (index % W, index / W);
+            let candidates = [
+                (y > 0).then(|| index - W),
+                (x + 1 < W).then(|| index + 1),
+                (y + 1 < H).then(|| index + W),
+                (x > 0).then(|| index - 1),
+            ];
+
+            for next in candidates.into_iter().flatten() {
+                if !visited[next] && passable(&self.backing[next]) {
+                    visited[next] = true;
+                    distances[next] = Some(so_far.saturating_add(1));
+                    came_from[next] = Some(index);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        (distances, came_from)
+    }
+
+    ///Finds the shortest path between two coordinates, only moving orthogonally between cells which match `passable`, using a breadth-first search.
+    ///
+    ///Returns the path including both ends, or [`None`] if there is no path or either end is OOB or impassable.
+    pub fn shortest_path(
+        &self,
+        start: ArrayCoords<W, H>,
+        goal: ArrayCoords<W, H>,
+        passable: impl Fn(&T) -> bool,
+    ) -> Option<Vec<ArrayCoords<W, H>>> {
+        let start = start.to_usize()?;
+        let goal = goal.to_usize()?;
+        if !passable(self.backing.get(goal)?) {
+            return None;
+        }
+
+        let (distances, came_from) = self.base_bfs(start, Some(goal), passable);
+        distances[goal]?;
+
+        let to_coords = |index: usize| ArrayCoords::from((index % W, index / W));
+        let mut path = vec![to_coords(goal)];
+        let mut current = goal;
+        while let Some(previous) = came_from[current] {
+            path.push(to_coords(previous));
+            current = previous;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    ///Finds the number of orthogonal steps from `start` to every cell, only moving between cells which match `passable`, using a breadth-first search.
+    ///
+    ///Cells which can't be reached are [`None`], as is everything if `start` is OOB or impassable.
+    pub fn distance_field(
+        &self,
+        start: ArrayCoords<W, H>,
+        passable: impl Fn(&T) -> bool,
+    ) -> TwoArray<Option<u32>, W, H> {
+        let distances = start.to_usize().map_or_else(
+            || vec![None; W * H],
+            |start| self.base_bfs(start, None, passable).0,
+        );
+
+        TwoArray { backing: distances }
+    }
+
+    ///Finds the cheapest path between two coordinates, only moving orthogonally. See [`astar`] for more details.
+    pub fn astar(
+        &self,
+        from: ArrayCoords<W, H>,
+        to: ArrayCoords<W, H>,
+        cost: impl Fn(&T) -> Option<u32>,
+    ) -> Option<(Vec<ArrayCoords<W, H>>, u32)> {
+        let (path, total) = astar(self, from.to_option()?, to.to_option()?, cost)?;
+        Some((path.into_iter().map(ArrayCoords::from).collect(), total))
+    }
+
+    ///Same as [`TwoArray::astar`], but can also move diagonally. See [`astar_diagonal`] for more details.
+    pub fn astar_diagonal(
+        &self,
+        from: ArrayCoords<W, H>,
+        to: ArrayCoords<W, H>,
+        cost: impl Fn(&T) -> Option<u32>,
+    ) -> Option<(Vec<ArrayCoords<W, H>>, u32)> {
+        let (path, total) = astar_diagonal(self, from.to_option()?, to.to_option()?, cost)?;
+        Some((path.into_iter().map(ArrayCoords::from).collect(), total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///`#` is a wall, `~` is a swamp which costs 10 and anything else costs 1
+    fn map<const W: usize, const H: usize>(rows: &[&str]) -> TwoArray<char, W, H> {
+        TwoArray {
+            backing: rows.iter().flat_map(|r| r.chars()).collect(),
+        }
+    }
+
+    ///Costs for [`map`]
+    #[allow(clippy::trivially_copy_pass_by_ref)] //needs to take a reference for `astar`
+    fn cost(c: &char) -> Option<u32> {
+        match c {
+            '#' => None,
+            '~' => Some(10),
+            _ => Some(1),
+        }
+    }
+
+    #[test]
+    fn cheapest_path_avoids_swamp() {
+        //the direct route along the top is 4 steps, but goes through the swamp
+        let grid: TwoArray<char, 5, 3> = map(&["..~..", ".#.#.", "....."]);
+
+        let (path, total) = grid
+            .astar(
+                ArrayCoords::InBounds(0, 0),
+                ArrayCoords::InBounds(4, 0),
+                cost,
+            )
+            .unwrap();
+
+        assert_eq!(total, 8);
+        assert_eq!(path.len(), 9);
+        assert_eq!(path.first(), Some(&ArrayCoords::InBounds(0, 0)));
+        assert_eq!(path.last(), Some(&ArrayCoords::InBounds(4, 0)));
+        assert!(!path.contains(&ArrayCoords::InBounds(2, 0)));
+    }
+
+    #[test]
+    fn diagonal_path() {
+        let grid: TwoArray<char, 3, 3> = map(&[".~~", "~.~", "~~."]);
+
+        let (path, total) = grid
+            .astar_diagonal(
+                ArrayCoords::InBounds(0, 0),
+                ArrayCoords::InBounds(2, 2),
+                cost,
+            )
+            .unwrap();
+
+        assert_eq!(
+            path,
+            vec![
+                ArrayCoords::InBounds(0, 0),
+                ArrayCoords::InBounds(1, 1),
+                ArrayCoords::InBounds(2, 2)
+            ]
+        );
+        assert_eq!(total, diagonal_cost(1) * 2);
+    }
+
+    #[test]
+    fn unreachable() {
+        let grid: TwoArray<char, 3, 3> = map(&[".#.", "##.", "..."]);
+
+        assert!(grid
+            .astar(
+                ArrayCoords::InBounds(0, 0),
+                ArrayCoords::InBounds(2, 2),
+                cost
+            )
+            .is_none());
+        assert!(grid
+            .astar_diagonal(
+                ArrayCoords::InBounds(0, 0),
+                ArrayCoords::InBounds(2, 2),
+                cost
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn bfs_straight_corridor() {
+        let grid: TwoArray<char, 5, 3> = map(&["#####", ".....", "#####"]);
+
+        let path = grid
+            .shortest_path(
+                ArrayCoords::InBounds(0, 1),
+                ArrayCoords::InBounds(4, 1),
+                |c| *c != '#',
+            )
+            .unwrap();
+        assert_eq!(
+            path,
+            (0..5)
+                .map(|x| ArrayCoords::InBounds(x, 1))
+                .collect::<Vec<_>>()
+        );
+
+        let field = grid.distance_field(ArrayCoords::InBounds(0, 1), |c| *c != '#');
+        assert_eq!(field[(4, 1)], Some(4));
+        assert_eq!(field[(0, 0)], None);
+
+        //start and goal being the same is just that cell
+        assert_eq!(
+            grid.shortest_path(
+                ArrayCoords::InBounds(2, 1),
+                ArrayCoords::InBounds(2, 1),
+                |c| *c != '#'
+            ),
+            Some(vec![ArrayCoords::InBounds(2, 1)])
+        );
+    }
+
+    #[test]
+    fn bfs_maze_with_one_path() {
+        let grid: TwoArray<char, 5, 5> = map(&[".#...", ".#.#.", ".#.#.", ".#.#.", "...#."]);
+
+        let path = grid
+            .shortest_path(
+                ArrayCoords::InBounds(0, 0),
+                ArrayCoords::InBounds(4, 4),
+                |c| *c != '#',
+            )
+            .unwrap();
+        assert_eq!(path.len(), 17);
+        assert_eq!(path.first(), Some(&ArrayCoords::InBounds(0, 0)));
+        assert_eq!(path.last(), Some(&ArrayCoords::InBounds(4, 4)));
+        assert!(path.contains(&ArrayCoords::InBounds(3, 0)));
+        assert!(path.windows(2).all(|w| match (w[0], w[1]) {
+            (ArrayCoords::InBounds(ax, ay), ArrayCoords::InBounds(bx, by)) =>
+                ax.abs_diff(bx) + ay.abs_diff(by) == 1,
+            _ => false,
+        }));
+
+        let field = grid.distance_field(ArrayCoords::InBounds(0, 0), |c| *c != '#');
+        assert_eq!(field[(4, 4)], Some(16));
+        assert_eq!(field[(2, 4)], Some(6));
+    }
+
+    #[test]
+    fn bfs_unreachable() {
+        let grid: TwoArray<char, 3, 3> = map(&[".#.", "##.", "..."]);
+        let passable = |c: &char| *c != '#';
+
+        assert!(grid
+            .shortest_path(
+                ArrayCoords::InBounds(0, 0),
+                ArrayCoords::InBounds(2, 2),
+                passable
+            )
+            .is_none());
+        //impassable or OOB ends
+        assert!(grid
+            .shortest_path(
+                ArrayCoords::InBounds(2, 2),
+                ArrayCoords::InBounds(1, 1),
+                passable
+            )
+            .is_none());
+        assert!(grid
+            .shortest_path(
+                ArrayCoords::OutOfBounds,
+                ArrayCoords::InBounds(2, 2),
+                passable
+            )
+            .is_none());
+
+        let field = grid.distance_field(ArrayCoords::InBounds(0, 0), passable);
+        assert_eq!(field.as_slice().iter().flatten().count(), 1);
+        assert!(grid
+            .distance_field(ArrayCoords::InBounds(1, 1), passable)
+            .as_slice()
+            .iter()
+            .all(Option::is_none));
+    }
+
+    #[test]
+    fn same_path_over_a_view() {
+        //the view is the bottom 5x3 of this, which is the same as the map in `cheapest_path_avoids_swamp`
+        let grid: TwoArray<char, 5, 4> = map(&["#####", "..~..", ".#.#.", "....."]);
+        let view = grid.view(ArrayCoords::InBounds(0, 1), 5, 3).unwrap();
+
+        let (path, total) = astar(&view, (0, 0), (4, 0), cost).unwrap();
+        assert_eq!(total, 8);
+        assert_eq!(path.len(), 9);
+    }
+
+    #[test]
+    fn same_path_over_a_dyn_grid() {
+        let grid = map::<5, 3>(&["..~..", ".#.#.", "....."]).to_dyn();
+
+        let (path, total) = astar(&grid, (0, 0), (4, 0), cost).unwrap();
+        assert_eq!(total, 8);
+        assert_eq!(path.len(), 9);
+        assert!(!path.contains(&(2, 0)));
+        assert_eq!(astar(&grid, (0, 0), (5, 0), cost), None);
+    }
+}
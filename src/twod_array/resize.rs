@@ -0,0 +1,137 @@
+use super::{ArrayCoords, TwoArray};
+
+impl<T: Clone, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Copies the `NW`x`NH` region starting at `top_left` out into its own array.
+    ///
+    ///Returns [`None`] if `top_left` is OOB, or if the region would go past the edge of this array
+    #[must_use]
+    pub fn crop<const NW: usize, const NH: usize>(
+        &self,
+        top_left: ArrayCoords<W, H>,
+    ) -> Option<TwoArray<T, NW, NH>> {
+        let (x, y) = top_left.to_option()?;
+        let fits = x.checked_add(NW).is_some_and(|end| end <= W)
+            && y.checked_add(NH).is_some_and(|end| end <= H);
+        if !fits {
+            return None;
+        }
+
+        let backing = self
+            .backing
+            .chunks_exact(W)
+            .skip(y)
+            .take(NH)
+            .flat_map(|row| row[x..x + NW].iter().cloned())
+            .collect();
+        Some(TwoArray { backing })
+    }
+
+    ///Copies all of `src` into this array, with its top left corner at `at` - eg. for stamping a prefab into a map.
+    ///
+    ///Returns `false` (and doesn't change anything) if `at` is OOB, or if `src` wouldn't fit
+    pub fn paste<const SW: usize, const SH: usize>(
+        &mut self,
+        src: &TwoArray<T, SW, SH>,
+        at: ArrayCoords<W, H>,
+    ) -> bool {
+        let Some((x, y)) = at.to_option() else {
+            return false;
+        };
+        let fits = x.checked_add(SW).is_some_and(|end| end <= W)
+            && y.checked_add(SH).is_some_and(|end| end <= H);
+        if !fits {
+            return false;
+        }
+
+        if SW > 0 {
+            for (row, src_row) in self
+                .backing
+                .chunks_exact_mut(W)
+                .skip(y)
+                .zip(src.backing.chunks_exact(SW))
+            {
+                row[x..x + SW].clone_from_slice(src_row);
+            }
+        }
+        true
+    }
+
+    ///Makes a bigger `NW`x`NH` array with this one in the top left corner, and everything else filled with clones of `fill`.
+    ///
+    /// # Panics
+    /// - If `NW < W` or `NH < H`, as that can't be checked at compile time. Use [`TwoArray::crop`] to shrink an array
+    #[must_use]
+    pub fn pad_to<const NW: usize, const NH: usize>(&self, fill: T) -> TwoArray<T, NW, NH> {
+        assert!(
+            NW >= W && NH >= H,
+            "can't pad a {W}x{H} array to a smaller {NW}x{NH} one"
+        );
+
+        let mut padded = TwoArray {
+            backing: vec![fill; NW * NH],
+        };
+        padded.paste(self, ArrayCoords::from_usize_pair(0, 0));
+        padded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twod_array::test_fixtures::indices;
+
+    #[test]
+    fn crop_regions() {
+        let array = indices::<4, 3>();
+
+        let middle: TwoArray<usize, 2, 2> = array.crop(ArrayCoords::InBounds(1, 1)).unwrap();
+        assert_eq!(middle.as_slice(), &[5, 6, 9, 10]);
+
+        let whole: TwoArray<usize, 4, 3> = array.crop(ArrayCoords::InBounds(0, 0)).unwrap();
+        assert_eq!(whole.as_slice(), array.as_slice());
+
+        assert!(array.crop::<2, 2>(ArrayCoords::InBounds(3, 0)).is_none());
+        assert!(array.crop::<1, 4>(ArrayCoords::InBounds(0, 0)).is_none());
+        assert!(array.crop::<1, 1>(ArrayCoords::OutOfBounds).is_none());
+    }
+
+    #[test]
+    fn paste_prefab() {
+        let mut map = TwoArray::<usize, 4, 4>::from_one_clone(0);
+        let prefab = TwoArray::<usize, 2, 2>::from_one_clone(1);
+
+        assert!(map.paste(&prefab, ArrayCoords::InBounds(2, 1)));
+        assert_eq!(
+            map.as_slice(),
+            &[0, 0, 0, 0, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 0, 0]
+        );
+
+        //overflowing the edge is rejected without touching anything
+        let before = map.as_slice().to_vec();
+        assert!(!map.paste(&prefab, ArrayCoords::InBounds(3, 0)));
+        assert!(!map.paste(&prefab, ArrayCoords::InBounds(0, 3)));
+        assert!(!map.paste(&prefab, ArrayCoords::OutOfBounds));
+        assert_eq!(map.as_slice(), before);
+
+        //crop and paste round-trip
+        let cropped: TwoArray<usize, 2, 2> = map.crop(ArrayCoords::InBounds(2, 1)).unwrap();
+        assert_eq!(cropped.as_slice(), prefab.as_slice());
+    }
+
+    #[test]
+    fn pad() {
+        let array = indices::<2, 2>();
+
+        let padded: TwoArray<usize, 3, 4> = array.pad_to(9);
+        assert_eq!(padded.as_slice(), &[0, 1, 9, 2, 3, 9, 9, 9, 9, 9, 9, 9]);
+
+        let same: TwoArray<usize, 2, 2> = array.pad_to(9);
+        assert_eq!(same.as_slice(), array.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "can't pad a 2x2 array to a smaller 1x3 one")]
+    fn pad_smaller_panics() {
+        let _: TwoArray<usize, 1, 3> = indices::<2, 2>().pad_to(0);
+    }
+}
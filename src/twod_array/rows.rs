@@ -0,0 +1,116 @@
+use super::TwoArray;
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Swaps two whole rows.
+    ///
+    ///Returns `false` (and doesn't swap) if either row is out of range
+    pub fn swap_rows(&mut self, a: usize, b: usize) -> bool {
+        if a >= H || b >= H {
+            return false;
+        }
+        if a != b {
+            let (top, bottom) = (a.min(b), a.max(b));
+            let (before, after) = self.backing.split_at_mut(bottom * W);
+            before[top * W..(top + 1) * W].swap_with_slice(&mut after[..W]);
+        }
+        true
+    }
+
+    ///Swaps two whole columns.
+    ///
+    ///Returns `false` (and doesn't swap) if either column is out of range
+    pub fn swap_columns(&mut self, a: usize, b: usize) -> bool {
+        if a >= W || b >= W {
+            return false;
+        }
+        for row in self.backing.chunks_exact_mut(W) {
+            row.swap(a, b);
+        }
+        true
+    }
+}
+
+impl<T: Clone, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Removes row `y`, moving every row above it down one - eg. for clearing a line in Tetris. The new top row is filled with clones of `fill`.
+    ///
+    ///Returns `false` (and doesn't change anything) if the row is out of range
+    pub fn remove_row_and_shift_down(&mut self, y: usize, fill: T) -> bool {
+        if y >= H {
+            return false;
+        }
+        //row y goes round to the top, so the rest only move once
+        self.backing[..(y + 1) * W].rotate_right(W);
+        self.backing[..W].fill(fill);
+        true
+    }
+
+    ///Removes column `x`, moving every column to the left of it right one. The new leftmost column is filled with clones of `fill`.
+    ///
+    ///Returns `false` (and doesn't change anything) if the column is out of range
+    pub fn remove_column_and_shift_right(&mut self, x: usize, fill: T) -> bool {
+        if x >= W {
+            return false;
+        }
+        for row in self.backing.chunks_exact_mut(W) {
+            row[..=x].rotate_right(1);
+            row[0] = fill.clone();
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twod_array::test_fixtures::numbered;
+
+    #[test]
+    fn swapping() {
+        let mut array: TwoArray<usize, 3, 4> = numbered();
+
+        assert!(array.swap_rows(0, 2));
+        assert_eq!(
+            array.as_slice(),
+            &[20, 21, 22, 10, 11, 12, 0, 1, 2, 30, 31, 32]
+        );
+        assert!(array.swap_rows(3, 3));
+        assert!(!array.swap_rows(1, 4));
+
+        assert!(array.swap_columns(2, 0));
+        assert_eq!(
+            array.as_slice(),
+            &[22, 21, 20, 12, 11, 10, 2, 1, 0, 32, 31, 30]
+        );
+        assert!(!array.swap_columns(3, 0));
+
+        assert!(array.swap_rows(2, 0) && array.swap_columns(0, 2));
+        assert_eq!(array.as_slice(), numbered::<3, 4>().as_slice());
+    }
+
+    #[test]
+    fn shifting() {
+        let mut array: TwoArray<usize, 3, 4> = numbered();
+
+        assert!(array.remove_row_and_shift_down(2, 99));
+        assert_eq!(
+            array.as_slice(),
+            &[99, 99, 99, 0, 1, 2, 10, 11, 12, 30, 31, 32]
+        );
+        assert!(array.remove_row_and_shift_down(0, 98));
+        assert_eq!(&array.as_slice()[..3], &[98, 98, 98]);
+        assert!(!array.remove_row_and_shift_down(4, 0));
+
+        let mut array: TwoArray<usize, 3, 4> = numbered();
+        assert!(array.remove_column_and_shift_right(1, 99));
+        assert_eq!(
+            array.as_slice(),
+            &[99, 0, 2, 99, 10, 12, 99, 20, 22, 99, 30, 32]
+        );
+        assert!(array.remove_column_and_shift_right(2, 98));
+        assert_eq!(
+            array.as_slice(),
+            &[98, 99, 0, 98, 99, 10, 98, 99, 20, 98, 99, 30]
+        );
+        assert!(!array.remove_column_and_shift_right(3, 0));
+    }
+}
@@ -0,0 +1,74 @@
+use super::{ArrayCoords, TwoArray};
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Turns an index into the backing into coordinates, without having to increment coordinates for every cell
+    const fn coords_of(index: usize) -> ArrayCoords<W, H> {
+        ArrayCoords::InBounds(index % W, index / W)
+    }
+
+    ///Finds the coordinates of the first element (in row-major order) which matches the predicate
+    pub fn find(&self, pred: impl Fn(&T) -> bool) -> Option<ArrayCoords<W, H>> {
+        self.backing.iter().position(pred).map(Self::coords_of)
+    }
+
+    ///Iterates over the coordinates of all of the elements which match the predicate, in row-major order
+    pub fn find_all<'a>(
+        &'a self,
+        pred: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = ArrayCoords<W, H>> + 'a {
+        self.backing
+            .iter()
+            .enumerate()
+            .filter(move |(_, el)| pred(el))
+            .map(|(i, _)| Self::coords_of(i))
+    }
+
+    ///Whether or not any of the elements match the predicate
+    pub fn any(&self, pred: impl Fn(&T) -> bool) -> bool {
+        self.backing.iter().any(pred)
+    }
+
+    ///Whether or not all of the elements match the predicate
+    pub fn all(&self, pred: impl Fn(&T) -> bool) -> bool {
+        self.backing.iter().all(pred)
+    }
+
+    ///Counts how many of the elements match the predicate
+    pub fn count_matching(&self, pred: impl Fn(&T) -> bool) -> usize {
+        self.backing.iter().filter(|el| pred(el)).count()
+    }
+}
+
+impl<T: PartialEq, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Finds the coordinates of the first element (in row-major order) which is equal to `value`
+    pub fn position_of(&self, value: &T) -> Option<ArrayCoords<W, H>> {
+        self.find(|el| el == value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn searching() {
+        //. @ .
+        //# . #
+        let array: TwoArray<char, 3, 2> = TwoArray {
+            backing: vec!['.', '@', '.', '#', '.', '#'],
+        };
+
+        assert_eq!(array.position_of(&'@'), Some(ArrayCoords::InBounds(1, 0)));
+        assert_eq!(array.position_of(&'x'), None);
+        assert_eq!(array.find(|c| *c == '#'), Some(ArrayCoords::InBounds(0, 1)));
+        assert_eq!(
+            array.find_all(|c| *c == '#').collect::<Vec<_>>(),
+            vec![ArrayCoords::InBounds(0, 1), ArrayCoords::InBounds(2, 1)]
+        );
+
+        assert!(array.any(|c| *c == '@'));
+        assert!(!array.all(|c| *c == '.'));
+        assert!(array.all(char::is_ascii_punctuation));
+        assert_eq!(array.count_matching(|c| *c == '.'), 3);
+    }
+}
@@ -0,0 +1,43 @@
+use super::TwoArray;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+impl<T: Serialize, const W: usize, const H: usize> Serialize for TwoArray<T, W, H> {
+    ///Serializes just the backing, as a sequence
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.backing.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const W: usize, const H: usize> Deserialize<'de>
+    for TwoArray<T, W, H>
+{
+    ///Deserializes the backing from a sequence, and checks that there are exactly `W * H` elements so it can't panic on indexing later
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::try_from(Vec::<T>::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let array: TwoArray<usize, 3, 2> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        let json = serde_json::to_string(&array).unwrap();
+        assert_eq!(json, "[0,1,2,3,4,5]");
+
+        let back: TwoArray<usize, 3, 2> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.backing, array.backing);
+    }
+
+    #[test]
+    fn wrong_length_fails() {
+        let err = serde_json::from_str::<TwoArray<u8, 8, 8>>(&format!("{:?}", vec![0; 60]))
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.starts_with("expected 64 elements for 8x8 grid, got 60"));
+    }
+}
@@ -0,0 +1,14 @@
+use super::TwoArray;
+
+///Makes a `W`x`H` array where each cell is `10 * y + x`, so the coordinates can be read straight off the values
+pub fn numbered<const W: usize, const H: usize>() -> TwoArray<usize, W, H> {
+    TwoArray::from_function(|c| {
+        let (x, y) = c.to_option().unwrap();
+        10 * y + x
+    })
+}
+
+///Makes a `W`x`H` array where every element is its index
+pub fn indices<const W: usize, const H: usize>() -> TwoArray<usize, W, H> {
+    TwoArray::from_function(|c| c.to_usize().unwrap())
+}
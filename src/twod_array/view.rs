@@ -0,0 +1,318 @@
+use super::{ArrayCoords, DynGrid, TwoArray};
+use crate::error_ext::ToNotErr;
+use std::ops::{Index, IndexMut};
+
+impl<T, const W: usize, const H: usize> TwoArray<T, W, H> {
+    ///Checks that a window fits inside the array, and if so gives back the top left as `(x, y)`
+    fn window_start(
+        top_left: ArrayCoords<W, H>,
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize)> {
+        let (x, y) = top_left.to_option()?;
+        let fits = x.checked_add(width).is_some_and(|end| end <= W)
+            && y.checked_add(height).is_some_and(|end| end <= H);
+        fits.then_some((x, y))
+    }
+
+    ///Gets a borrowed view of a rectangular window of the array, without copying.
+    ///
+    ///Returns [`None`] if `top_left` is OOB, or the window would go outside the array
+    #[must_use]
+    pub fn view(
+        &self,
+        top_left: ArrayCoords<W, H>,
+        width: usize,
+        height: usize,
+    ) -> Option<TwoArrayView<'_, T, W, H>> {
+        let (x, y) = Self::window_start(top_left, width, height)?;
+        Some(TwoArrayView {
+            parent: self,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    ///Gets a mutably borrowed view of a rectangular window of the array, without copying.
+    ///
+    ///Returns [`None`] if `top_left` is OOB, or the window would go outside the array
+    #[must_use]
+    pub fn view_mut(
+        &mut self,
+        top_left: ArrayCoords<W, H>,
+        width: usize,
+        height: usize,
+    ) -> Option<TwoArrayViewMut<'_, T, W, H>> {
+        let (x, y) = Self::window_start(top_left, width, height)?;
+        Some(TwoArrayViewMut {
+            parent: self,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    ///Iterates over every `width`x`height` window which fits fully inside the array, as borrowed views along with the coordinates of their centres. Goes in row-major order of the top lefts.
+    ///
+    ///The centre is `width / 2` across and `height / 2` down from the top left, so for even sizes it is just below and to the right of the middle. Gives nothing if either size is zero or bigger than the array.
+    pub fn windows(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = (ArrayCoords<W, H>, TwoArrayView<'_, T, W, H>)> {
+        let fits = width > 0 && height > 0 && width <= W && height <= H;
+        let (across, down) = if fits {
+            (W - width + 1, H - height + 1)
+        } else {
+            (0, 0)
+        };
+
+        (0..down).flat_map(move |y| {
+            (0..across).map(move |x| {
+                let centre = ArrayCoords::InBounds(x + width / 2, y + height / 2);
+                let view = TwoArrayView {
+                    parent: self,
+                    x,
+                    y,
+                    width,
+                    height,
+                };
+                (centre, view)
+            })
+        })
+    }
+}
+
+///Creates the read-only methods and [`Index`] impl for a view type
+macro_rules! view_impl {
+    ($($name:ident),+) => {
+        $(
+            impl<T, const W: usize, const H: usize> $name<'_, T, W, H> {
+                ///The width of the view
+                #[must_use]
+                pub const fn width(&self) -> usize {
+                    self.width
+                }
+
+                ///The height of the view
+                #[must_use]
+                pub const fn height(&self) -> usize {
+                    self.height
+                }
+
+                ///Turns local coordinates in the view into the index into the parent's backing, or [`None`] if they are outside the view
+                fn parent_index(&self, (x, y): (usize, usize)) -> Option<usize> {
+                    (x < self.width && y < self.height).then(|| (self.y + y) * W + self.x + x)
+                }
+
+                ///Turns local coordinates in the view into coordinates in the parent array
+                #[must_use]
+                pub const fn to_parent(&self, (x, y): (usize, usize)) -> ArrayCoords<W, H> {
+                    if x < self.width && y < self.height {
+                        ArrayCoords::InBounds(self.x + x, self.y + y)
+                    } else {
+                        ArrayCoords::OutOfBounds
+                    }
+                }
+
+                ///Gets the element at the local coordinates, or [`None`] if they are outside the view
+                #[must_use]
+                pub fn get(&self, local: (usize, usize)) -> Option<&T> {
+                    self.parent_index(local).map(|i| &self.parent.backing[i])
+                }
+
+                ///Iterates over every element in the view in row-major order, along with its local coordinates
+                pub fn iter(&self) -> impl Iterator<Item = (&T, (usize, usize))> {
+                    let (x, width) = (self.x, self.width);
+                    self.parent
+                        .backing
+                        .chunks(W.max(1))
+                        .skip(self.y)
+                        .take(self.height)
+                        .enumerate()
+                        .flat_map(move |(y, row)| {
+                            row[x..x + width]
+                                .iter()
+                                .enumerate()
+                                .map(move |(x, el)| (el, (x, y)))
+                        })
+                }
+            }
+
+            impl<T: Clone, const W: usize, const H: usize> $name<'_, T, W, H> {
+                ///Copies every element in the view out into a new [`Vec`], in row-major order with [`Self::width`] elements per row
+                #[must_use]
+                pub fn to_vec(&self) -> Vec<T> {
+                    self.iter().map(|(el, _)| el.clone()).collect()
+                }
+
+                ///Copies the view out into an owned [`DynGrid`] with the same dimensions, as the size of a view is only known at runtime
+                #[must_use]
+                pub fn to_dyn(&self) -> DynGrid<T> {
+                    DynGrid::from_parts(self.width, self.height, self.to_vec())
+                }
+            }
+
+            impl<T, const W: usize, const H: usize> Index<(usize, usize)> for $name<'_, T, W, H> {
+                type Output = T;
+
+                fn index(&self, index: (usize, usize)) -> &Self::Output {
+                    self.get(index)
+                        .unwrap_log_error_with_context(|| format!("getting view index {index:?}"))
+                }
+            }
+        )+
+    };
+}
+
+///A borrowed rectangular window into a [`TwoArray`], indexed using local coordinates
+pub struct TwoArrayView<'a, T, const W: usize, const H: usize> {
+    ///The array we are a view into
+    parent: &'a TwoArray<T, W, H>,
+    ///The x of the top left
+    x: usize,
+    ///The y of the top left
+    y: usize,
+    ///How wide the view is
+    width: usize,
+    ///How tall the view is
+    height: usize,
+}
+
+///A mutably borrowed rectangular window into a [`TwoArray`], indexed using local coordinates
+pub struct TwoArrayViewMut<'a, T, const W: usize, const H: usize> {
+    ///The array we are a view into
+    parent: &'a mut TwoArray<T, W, H>,
+    ///The x of the top left
+    x: usize,
+    ///The y of the top left
+    y: usize,
+    ///How wide the view is
+    width: usize,
+    ///How tall the view is
+    height: usize,
+}
+
+view_impl!(TwoArrayView, TwoArrayViewMut);
+
+impl<T, const W: usize, const H: usize> TwoArrayViewMut<'_, T, W, H> {
+    ///Gets the element at the local coordinates mutably, or [`None`] if they are outside the view
+    #[must_use]
+    pub fn get_mut(&mut self, local: (usize, usize)) -> Option<&mut T> {
+        self.parent_index(local)
+            .map(|i| &mut self.parent.backing[i])
+    }
+
+    ///Iterates mutably over every element in the view in row-major order, along with its local coordinates
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (&mut T, (usize, usize))> {
+        let (x, width) = (self.x, self.width);
+        self.parent
+            .backing
+            .chunks_mut(W.max(1))
+            .skip(self.y)
+            .take(self.height)
+            .enumerate()
+            .flat_map(move |(y, row)| {
+                row[x..x + width]
+                    .iter_mut()
+                    .enumerate()
+                    .map(move |(x, el)| (el, (x, y)))
+            })
+    }
+}
+
+impl<T: Clone, const W: usize, const H: usize> TwoArrayViewMut<'_, T, W, H> {
+    ///Sets every element in the view to a clone of `value` - eg. for clearing a region
+    pub fn fill(&mut self, value: &T) {
+        for (el, _) in self.iter_mut() {
+            *el = value.clone();
+        }
+    }
+}
+
+impl<T, const W: usize, const H: usize> IndexMut<(usize, usize)> for TwoArrayViewMut<'_, T, W, H> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        self.get_mut(index)
+            .unwrap_log_error_with_context(|| format!("getting view index {index:?}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn view_indexing() {
+        let array: TwoArray<usize, 4, 3> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        let view = array.view(ArrayCoords::InBounds(1, 1), 2, 2).unwrap();
+        assert_eq!((view.width(), view.height()), (2, 2));
+        assert_eq!(view[(0, 0)], 5);
+        assert_eq!(view[(1, 1)], 10);
+        assert_eq!(view.get((2, 0)), None);
+        assert_eq!(view.to_parent((1, 0)), ArrayCoords::InBounds(2, 1));
+        assert_eq!(view.to_vec(), vec![5, 6, 9, 10]);
+
+        let owned = view.to_dyn();
+        assert_eq!((owned.width(), owned.height()), (2, 2));
+        assert_eq!(owned.as_slice(), &[5, 6, 9, 10]);
+        assert_eq!(owned[(1, 0)], 6);
+        assert_eq!(
+            view.iter().map(|(_, c)| c).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 0), (0, 1), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn out_of_bounds_views() {
+        let array: TwoArray<usize, 4, 3> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        assert!(array.view(ArrayCoords::InBounds(3, 0), 2, 1).is_none());
+        assert!(array.view(ArrayCoords::InBounds(0, 2), 1, 2).is_none());
+        assert!(array.view(ArrayCoords::OutOfBounds, 1, 1).is_none());
+        assert!(array.view(ArrayCoords::InBounds(0, 0), 4, 3).is_some());
+
+        //huge sizes don't wrap around into looking like they fit
+        assert!(array
+            .view(ArrayCoords::InBounds(1, 0), usize::MAX, 1)
+            .is_none());
+        let mut array = array;
+        assert!(array
+            .view_mut(ArrayCoords::InBounds(0, 1), 1, usize::MAX)
+            .is_none());
+    }
+
+    #[test]
+    fn windows_with_centres() {
+        let array: TwoArray<usize, 4, 3> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        let windows: Vec<_> = array.windows(2, 2).collect();
+        assert_eq!(windows.len(), 6);
+        assert_eq!(windows[0].0, ArrayCoords::InBounds(1, 1));
+        assert_eq!(windows[0].1.to_vec(), vec![0, 1, 4, 5]);
+        assert_eq!(windows[5].0, ArrayCoords::InBounds(3, 2));
+        assert_eq!(windows[5].1.to_vec(), vec![6, 7, 10, 11]);
+
+        let whole: Vec<_> = array.windows(4, 3).collect();
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole[0].0, ArrayCoords::InBounds(2, 1));
+
+        assert_eq!(array.windows(5, 1).count(), 0);
+        assert_eq!(array.windows(0, 1).count(), 0);
+        assert_eq!(array.windows(1, 1).count(), 12);
+    }
+
+    #[test]
+    fn mutable_view_clears_region() {
+        let mut array: TwoArray<usize, 4, 3> = TwoArray::from_function(|c| c.to_usize().unwrap());
+
+        let mut view = array.view_mut(ArrayCoords::InBounds(2, 0), 2, 2).unwrap();
+        view.fill(&0);
+        view[(0, 1)] = 100;
+
+        assert_eq!(array.backing, vec![0, 1, 0, 0, 4, 5, 100, 0, 8, 9, 10, 11]);
+    }
+}
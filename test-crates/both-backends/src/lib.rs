@@ -0,0 +1,41 @@
+//! Checks that `burntnail-utils` compiles with both `ah` and `eyre` enabled, and that `eyre` wins for the top-level types.
+
+#[cfg(test)]
+mod tests {
+    use burntnail_utils::{
+        error_ext::ErrorExt,
+        error_types::{anyhow_backend, eyre_backend, BError, BResult, Contextable},
+    };
+
+    fn parse_port(s: &str) -> BResult<u16> {
+        Ok(s.parse::<u16>()?)
+    }
+
+    fn parse_port_anyhow(s: &str) -> anyhow_backend::BResult<u16> {
+        Ok(s.parse::<u16>()?)
+    }
+
+    #[test]
+    fn eyre_wins() {
+        let e: eyre_backend::BError = parse_port("nope").unwrap_err();
+        let _: BError = e;
+    }
+
+    #[test]
+    fn both_backends_have_context() {
+        let e = parse_port("nope").context("parsing the port").unwrap_err();
+        assert_eq!(e.to_string(), "parsing the port");
+
+        let e = parse_port_anyhow("nope")
+            .context("parsing the port")
+            .unwrap_err();
+        assert_eq!(e.to_string(), "parsing the port");
+        assert_eq!(e.root_cause().to_string(), "invalid digit found in string");
+    }
+
+    #[test]
+    fn error_ext_uses_top_level() {
+        assert_eq!(parse_port("80").warn_and_continue(), Some(80));
+        assert_eq!(parse_port("nope").warn_and_continue(), None);
+    }
+}